@@ -0,0 +1,597 @@
+use crate::neighbors::wrap;
+use crate::packed::{PackedBoard, FNV_OFFSET};
+use crate::rule::{Automaton, BoundaryMode, LifeLike, Neighborhood, Rule};
+use crate::viewport::LogicalPos;
+use crate::{run_once_sync, Board, Changes, BOARD_SIZE, COLUMN_SIZE, LINE_SIZE};
+
+/// Margin around a spaceship, for its own size, when looking for it
+const SPACESHIP_MARGIN: u32 = 8;
+/// Generations remembered to detect oscillations, the longest period found
+const PERIOD_HISTORY: usize = 32;
+/// Generations between two samples of the center of mass, a multiple of the periods of the
+/// common spaceships
+pub const DRIFT_EVERY: u32 = 16;
+
+/// Kernel summing the 8 neighbors of a cell, the count Life-like rules go by
+pub const MOORE_KERNEL: [[i16; 3]; 3] = [[1, 1, 1], [1, 0, 1], [1, 1, 1]];
+/// Kernel summing the 4 diagonal neighbors of a cell, for diagonal rules
+pub const DIAGONAL_KERNEL: [[i16; 3]; 3] = [[1, 0, 1], [0, 0, 0], [1, 0, 1]];
+
+/// Where a board ends up after evolving on its own
+pub struct Analysis {
+    pub final_population: u32,
+    /// Most cells alive at once, on the way
+    pub peak_population: u32,
+    /// First generation of the static or periodic state, `None` if none was reached
+    pub generations_to_stabilize: Option<u32>,
+    /// Period of the final state, 1 for a static one
+    pub detected_period: Option<u32>,
+    /// How far the center of mass moved in the last [`DRIFT_EVERY`] generations, when the
+    /// board never settled and kept its population: it's likely a spaceship
+    ///
+    /// The center is taken on the plane, so on a torus it's off while cells are across the
+    /// edges from each other.
+    pub drift: Option<(f32, f32)>,
+}
+
+/// Count the alive cells
+pub fn population(board: &Board<bool>) -> u32 {
+    board.iter().flatten().filter(|&&alive| alive).count() as u32
+}
+
+/// Number of sides alive cells share with dead ones
+///
+/// Cells outside the board are dead, or the ones on the other side on a torus. Compared to
+/// the population, it tells irregular shapes from compact blobs.
+pub fn perimeter_length(board: &Board<bool>, boundary: BoundaryMode) -> u32 {
+    let dead = |x: isize, y: isize| match wrap((x, y), boundary) {
+        Some((x, y)) => !board[x][y],
+        None => true,
+    };
+    let mut perimeter = 0;
+    for (x, column) in board.iter().enumerate() {
+        for (y, _) in column.iter().enumerate().filter(|(_, &alive)| alive) {
+            let (x, y) = (x as isize, y as isize);
+            perimeter += [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .iter()
+                .filter(|(dx, dy)| dead(x + dx, y + dy))
+                .count() as u32;
+        }
+    }
+    perimeter
+}
+
+/// Mean position of the alive cells, `None` if there are none
+pub fn center_of_mass(board: &Board<bool>) -> Option<(f32, f32)> {
+    let (count, sum_x, sum_y) = mass(board, |_, _| true)?;
+    Some((sum_x as f32 / count as f32, sum_y as f32 / count as f32))
+}
+
+/// Number of alive cells for which `counted` holds, and the sums of their coordinates
+///
+/// Both centers of mass are built on it. `None` if no cell is counted.
+fn mass(board: &Board<bool>, counted: impl Fn(u32, u32) -> bool) -> Option<(u32, u32, u32)> {
+    let (mut count, mut sum_x, mut sum_y) = (0u32, 0u32, 0u32);
+    for (x, column) in board.iter().enumerate() {
+        for (y, _) in column.iter().enumerate().filter(|(_, &alive)| alive) {
+            if counted(x as u32, y as u32) {
+                count += 1;
+                sum_x += x as u32;
+                sum_y += y as u32;
+            }
+        }
+    }
+    (count != 0).then_some((count, sum_x, sum_y))
+}
+
+/// Measure how far the pattern at `initial_pos` moves in `generations` generations
+///
+/// The generations are run in Conway's Life on a copy of the board. Only the cells close
+/// enough to `initial_pos` to be reached at the speed of light are considered, so other
+/// patterns on the board don't get measured too.
+/// Returns the displacement of the center of mass, `None` if the pattern died.
+pub fn measure_spaceship_velocity(
+    board: &Board<bool>,
+    initial_pos: LogicalPos,
+    generations: u32,
+) -> Option<(i16, i16)> {
+    let reach = generations + SPACESHIP_MARGIN;
+    let before = center_of_mass_near(board, initial_pos, reach)?;
+
+    let mut copy = *board;
+    let conway = LifeLike {
+        rule: Rule::CONWAY,
+        boundary: BoundaryMode::Dead,
+    };
+    for _ in 0..generations {
+        run_once_sync(&mut copy, &conway);
+    }
+    let after = center_of_mass_near(&copy, initial_pos, reach)?;
+
+    Some(((after.0 - before.0) as i16, (after.1 - before.1) as i16))
+}
+
+/// Evolve a copy of the board until it's static or periodic, for at most `max_gens`
+///
+/// Each generation is compared to the previous one for the static check, and its hash to
+/// the hashes of the last generations for the period, so a hash collision could report a
+/// period too early. It stops early, as if `max_gens` was reached, when `keep_going`
+/// returns false; it's called before every generation.
+pub fn analyze_until(
+    board: &Board<bool>,
+    automaton: &impl Automaton,
+    max_gens: u32,
+    keep_going: impl FnMut() -> bool,
+) -> Analysis {
+    analyze_with(
+        board,
+        |copy| run_once_sync(copy, automaton),
+        max_gens,
+        keep_going,
+    )
+}
+
+/// [`analyze_until`], with the generations run by `run_once` instead of an automaton
+///
+/// For generations depending on more than the board, such as the edge buffer.
+pub fn analyze_with(
+    board: &Board<bool>,
+    mut run_once: impl FnMut(&mut Board<bool>) -> Changes,
+    max_gens: u32,
+    mut keep_going: impl FnMut() -> bool,
+) -> Analysis {
+    let mut copy = *board;
+    // Hashes of the previous generations, by generation modulo the history size
+    let mut history = [0u32; PERIOD_HISTORY];
+    let mut stable = None;
+    let mut alive = population(&copy);
+    let mut peak_population = alive;
+    // Population and center of mass at the last sample, and the drift since the one before
+    let mut sample: Option<(u32, (f32, f32))> = None;
+    let mut drift = None;
+    for generation in 0..max_gens {
+        if !keep_going() {
+            break;
+        }
+        if generation.is_multiple_of(DRIFT_EVERY) {
+            let next = center_of_mass(&copy).map(|center| (alive, center));
+            drift = match (sample, next) {
+                (Some((before, (x0, y0))), Some((after, (x1, y1)))) if before == after => {
+                    Some((x1 - x0, y1 - y0))
+                }
+                _ => None,
+            };
+            sample = next;
+        }
+        let hash = PackedBoard::pack(&copy).checksum(FNV_OFFSET);
+        let remembered = PERIOD_HISTORY.min(generation as usize) as u32;
+        if let Some(period) = (1..=remembered)
+            .find(|&period| history[((generation - period) as usize) % PERIOD_HISTORY] == hash)
+        {
+            stable = Some((generation - period, period));
+            break;
+        }
+        history[generation as usize % PERIOD_HISTORY] = hash;
+
+        let changes = run_once(&mut copy);
+        if changes.born.is_empty() && changes.died.is_empty() {
+            stable = Some((generation, 1));
+            break;
+        }
+        alive = alive + changes.born.len() as u32 - changes.died.len() as u32;
+        peak_population = peak_population.max(alive);
+    }
+    Analysis {
+        final_population: alive,
+        peak_population,
+        generations_to_stabilize: stable.map(|(generation, _)| generation),
+        detected_period: stable.map(|(_, period)| period),
+        drift: drift.filter(|_| stable.is_none()),
+    }
+}
+
+/// Mean number of generations cells stay alive, over `generations` generations of a copy
+///
+/// Only the cells dying within the generations are counted, 0 if none does. Cells alive
+/// at first count from the first generation, and lifetimes stop at 255 generations.
+pub fn life_expectancy(initial: &Board<bool>, automaton: &impl Automaton, generations: u32) -> f32 {
+    let mut board = *initial;
+    // Consecutive generations each cell has been alive for
+    let mut age = [[0u8; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    let mut lifetimes = [0u32; 256];
+    for (ages, column) in age.iter_mut().zip(board.iter()) {
+        for (age, &alive) in ages.iter_mut().zip(column.iter()) {
+            *age = alive as u8;
+        }
+    }
+
+    for _ in 0..generations {
+        let changes = run_once_sync(&mut board, automaton);
+        for &(x, y) in &changes.died {
+            let age = &mut age[x as usize][y as usize];
+            lifetimes[*age as usize] += 1;
+            *age = 0;
+        }
+        for (ages, column) in age.iter_mut().zip(board.iter()) {
+            for (age, _) in ages
+                .iter_mut()
+                .zip(column.iter())
+                .filter(|(_, &alive)| alive)
+            {
+                *age = age.saturating_add(1);
+            }
+        }
+    }
+
+    let (total, count) =
+        lifetimes
+            .iter()
+            .enumerate()
+            .fold((0u64, 0u64), |(total, count), (lifetime, &cells)| {
+                (total + lifetime as u64 * cells as u64, count + cells as u64)
+            });
+    if count == 0 {
+        0.
+    } else {
+        total as f32 / count as f32
+    }
+}
+
+/// Rounded [`center_of_mass`] of the alive cells at most `reach` cells away from `around`
+fn center_of_mass_near(board: &Board<bool>, around: LogicalPos, reach: u32) -> Option<(i32, i32)> {
+    let (count, sum_x, sum_y) = mass(board, |x, y| {
+        x.abs_diff(around.x as u32) <= reach && y.abs_diff(around.y as u32) <= reach
+    })?;
+    let rounded = |sum: u32| ((2 * sum + count) / (2 * count)) as i32;
+    Some((rounded(sum_x), rounded(sum_y)))
+}
+
+/// Count the alive cells in the 5×5 square around each cell, itself included (0 to 25)
+///
+/// The square sums are separable: rows of 5 are summed horizontally first,
+/// then 5 of those sums vertically. Cells outside the board count as dead.
+pub fn neighborhood_density(board: &Board<bool>) -> Board<u8> {
+    let (width, height) = (LINE_SIZE as usize, COLUMN_SIZE as usize);
+    let mut horizontal = [[0u8; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    for (x, sums) in horizontal.iter_mut().enumerate() {
+        for column in &board[x.saturating_sub(2)..(x + 3).min(width)] {
+            sums.iter_mut()
+                .zip(column.iter())
+                .for_each(|(sum, &alive)| *sum += alive as u8);
+        }
+    }
+
+    let mut density = [[0u8; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    for (column, sums) in density.iter_mut().zip(horizontal.iter()) {
+        for (y, cell) in column.iter_mut().enumerate() {
+            *cell = sums[y.saturating_sub(2)..(y + 3).min(height)].iter().sum();
+        }
+    }
+    density
+}
+
+/// Weighted sum of the 3×3 square around each cell
+///
+/// `kernel[dy + 1][dx + 1]` weighs the cell `(dx, dy)` away. Only the alive cells add
+/// their weights, so sparse boards are quick. Cells outside the board count as dead, unless
+/// it wraps around.
+pub fn convolution_3x3(
+    board: &Board<bool>,
+    kernel: &[[i16; 3]; 3],
+    boundary: BoundaryMode,
+) -> Board<i16> {
+    let mut sums = [[0i16; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    for (x, column) in board.iter().enumerate() {
+        for (y, _) in column.iter().enumerate().filter(|(_, &alive)| alive) {
+            for (dy, row) in (-1..=1).zip(kernel.iter()) {
+                for (dx, &weight) in (-1..=1).zip(row.iter()) {
+                    // The cell `(dx, dy)` away from this one sees it at `(-dx, -dy)`
+                    let sum = wrap((x as isize - dx, y as isize - dy), boundary);
+                    if let (true, Some((sum_x, sum_y))) = (weight != 0, sum) {
+                        sums[sum_x][sum_y] += weight;
+                    }
+                }
+            }
+        }
+    }
+    sums
+}
+
+/// Cells on the frontier between alive and dead regions, where births and deaths happen
+///
+/// A cell is on it when it's alive with a dead neighbor, or dead with an alive one, among
+/// its 8 neighbors. Cells outside the board don't count either way, unless it wraps around.
+pub fn edge_detect(board: &Board<bool>, boundary: BoundaryMode) -> Board<bool> {
+    let alive_neighbors = convolution_3x3(board, &MOORE_KERNEL, boundary);
+    // Neighbors on the board along an axis, the cell included
+    let span = |i: usize, size: u16| match boundary {
+        BoundaryMode::Wrap => 3,
+        BoundaryMode::Dead => 1 + (i > 0) as i16 + (i + 1 < size as usize) as i16,
+    };
+    let mut frontier = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    for (x, column) in frontier.iter_mut().enumerate() {
+        for (y, cell) in column.iter_mut().enumerate() {
+            let alive = alive_neighbors[x][y];
+            *cell = if board[x][y] {
+                alive < span(x, LINE_SIZE) * span(y, COLUMN_SIZE) - 1
+            } else {
+                alive > 0
+            };
+        }
+    }
+    frontier
+}
+
+/// Cells born and cells dying at the next generation of a Life-like automaton
+pub fn pending_changes(board: &Board<bool>, automaton: &LifeLike) -> (u32, u32) {
+    let kernel = match automaton.rule.neighborhood {
+        Neighborhood::Moore => &MOORE_KERNEL,
+        Neighborhood::Diagonal => &DIAGONAL_KERNEL,
+    };
+    let neighbors = convolution_3x3(board, kernel, automaton.boundary);
+    let (mut born, mut died) = (0, 0);
+    for (column, counts) in board.iter().zip(neighbors.iter()) {
+        for (&alive, &count) in column.iter().zip(counts.iter()) {
+            match (alive, automaton.rule.next_state(alive, count as u8)) {
+                (false, true) => born += 1,
+                (true, false) => died += 1,
+                _ => {}
+            }
+        }
+    }
+    (born, died)
+}
+
+/// Count the groups of connected alive cells
+///
+/// Cells are connected to their 8 neighbors, across the edges too on a torus, so a pattern
+/// straddling them is counted once.
+/// Cells are scanned left to right, top to bottom, and each alive cell is joined
+/// with its already scanned alive neighbors in a path-compressed union-find. Across the
+/// edges the neighbor may not be scanned yet, which doesn't matter to the union.
+pub fn count_components(board: &Board<bool>, boundary: BoundaryMode) -> u32 {
+    let mut parent = [0u16; BOARD_SIZE];
+    parent
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, p)| *p = i as u16);
+
+    for y in 0..COLUMN_SIZE as isize {
+        for x in 0..LINE_SIZE as isize {
+            if !board[x as usize][y as usize] {
+                continue;
+            }
+            // Left, top left, top and top right neighbors are already scanned
+            for (dx, dy) in [(-1, 0), (-1, -1), (0, -1), (1, -1)] {
+                if let Some((nx, ny)) = wrap((x + dx, y + dy), boundary) {
+                    if board[nx][ny] {
+                        union(
+                            &mut parent,
+                            cell_index(x as usize, y as usize),
+                            cell_index(nx, ny),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    (0..BOARD_SIZE)
+        .filter(|&i| {
+            board[i % LINE_SIZE as usize][i / LINE_SIZE as usize] && find(&mut parent, i) == i
+        })
+        .count() as u32
+}
+
+fn cell_index(x: usize, y: usize) -> usize {
+    y * LINE_SIZE as usize + x
+}
+
+fn find(parent: &mut [u16; BOARD_SIZE], mut i: usize) -> usize {
+    while parent[i] as usize != i {
+        // Path halving
+        parent[i] = parent[parent[i] as usize];
+        i = parent[i] as usize;
+    }
+    i
+}
+
+fn union(parent: &mut [u16; BOARD_SIZE], a: usize, b: usize) {
+    let (a, b) = (find(parent, a), find(parent, b));
+    if a != b {
+        parent[a.max(b)] = a.min(b) as u16;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitgrid::{BitGrid, BlitMode};
+
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    const CONWAY: LifeLike = LifeLike {
+        rule: Rule::CONWAY,
+        boundary: BoundaryMode::Dead,
+    };
+
+    fn board_with(rle: &str, at: (i16, i16)) -> Board<bool> {
+        let mut board = EMPTY;
+        BitGrid::decode(rle)
+            .ok()
+            .unwrap()
+            .blit_onto(&mut board, at, BlitMode::Or);
+        board
+    }
+
+    #[test]
+    fn blocks_are_static_at_once() {
+        let analysis = analyze_until(&board_with("2o$2o!", (10, 10)), &CONWAY, 100, || true);
+        assert_eq!(analysis.final_population, 4);
+        assert_eq!(analysis.peak_population, 4);
+        assert_eq!(analysis.generations_to_stabilize, Some(0));
+        assert_eq!(analysis.detected_period, Some(1));
+        assert!(analysis.drift.is_none());
+    }
+
+    #[test]
+    fn blinkers_have_a_period_of_2() {
+        let analysis = analyze_until(&board_with("3o!", (10, 10)), &CONWAY, 100, || true);
+        assert_eq!(analysis.generations_to_stabilize, Some(0));
+        assert_eq!(analysis.detected_period, Some(2));
+    }
+
+    #[test]
+    fn r_pentomino_keeps_growing() {
+        // Populations from the R-pentomino on an unbounded plane, where it spans 54 × 25
+        // cells by generation 100: it doesn't reach the edges of the board from here
+        let board = board_with("b2o$2o$bo!", (40, 30));
+        let analysis = analyze_until(&board, &CONWAY, 100, || true);
+        assert_eq!(analysis.final_population, 121);
+        assert_eq!(analysis.peak_population, 121);
+        assert_eq!(analysis.generations_to_stabilize, None);
+        assert_eq!(analysis.detected_period, None);
+        assert!(analysis.drift.is_none());
+
+        let analysis = analyze_until(&board, &CONWAY, 8, || true);
+        assert_eq!(analysis.final_population, 18);
+        assert_eq!(analysis.peak_population, 18);
+        let analysis = analyze_until(&board, &CONWAY, 11, || true);
+        assert_eq!(analysis.final_population, 10);
+    }
+
+    #[test]
+    fn gliders_drift_a_cell_every_4_generations() {
+        let analysis = analyze_until(&board_with("bob$2bo$3o!", (20, 20)), &CONWAY, 40, || true);
+        assert_eq!(analysis.final_population, 5);
+        assert_eq!(analysis.detected_period, None);
+        assert_eq!(analysis.drift, Some((4., 4.)));
+    }
+
+    #[test]
+    fn analyses_stop_when_told() {
+        let mut calls = 0;
+        let analysis = analyze_until(&board_with("b2o$2o$bo!", (40, 30)), &CONWAY, 100, || {
+            calls += 1;
+            calls <= 8
+        });
+        assert_eq!(analysis.final_population, 18);
+    }
+
+    #[test]
+    fn centers_of_mass_near_a_cell_leave_the_others_out() {
+        assert!(center_of_mass(&EMPTY).is_none());
+        assert!(center_of_mass_near(&EMPTY, LogicalPos::new(10, 10), 100).is_none());
+
+        // A block around (11.5, 11.5), and a blinker around (50, 40)
+        let mut board = board_with("2o$2o!", (11, 11));
+        board[49][40] = true;
+        board[50][40] = true;
+        board[51][40] = true;
+        let (x, y) = center_of_mass(&board).unwrap();
+        assert!((x - (46. + 150.) / 7.).abs() < 1e-4);
+        assert!((y - (46. + 120.) / 7.).abs() < 1e-4);
+        // Everything in reach, rounded
+        let everything = center_of_mass_near(&board, LogicalPos::new(0, 0), 100);
+        assert_eq!(everything, Some((x.round() as i32, y.round() as i32)));
+        // Halves round up
+        assert_eq!(
+            center_of_mass_near(&board, LogicalPos::new(10, 10), 5),
+            Some((12, 12))
+        );
+        assert_eq!(
+            center_of_mass_near(&board, LogicalPos::new(52, 42), 3),
+            Some((50, 40))
+        );
+        assert_eq!(
+            center_of_mass_near(&board, LogicalPos::new(30, 30), 5),
+            None
+        );
+    }
+
+    #[test]
+    fn gliders_move_a_cell_diagonally_every_4_generations() {
+        let board = board_with("bo$2bo$3o!", (20, 20));
+        let velocity = measure_spaceship_velocity(&board, LogicalPos::new(21, 21), 8);
+        assert_eq!(velocity, Some((2, 2)));
+    }
+
+    const TORUS: LifeLike = LifeLike {
+        rule: Rule::CONWAY,
+        boundary: BoundaryMode::Wrap,
+    };
+    const RIGHT: usize = LINE_SIZE as usize - 1;
+    const BOTTOM: usize = COLUMN_SIZE as usize - 1;
+
+    fn board_of(cells: &[(usize, usize)]) -> Board<bool> {
+        let mut board = EMPTY;
+        cells.iter().for_each(|&(x, y)| board[x][y] = true);
+        board
+    }
+
+    #[test]
+    fn blinkers_across_the_seam_are_one_oscillator() {
+        for board in [
+            board_of(&[(RIGHT, 10), (0, 10), (1, 10)]),
+            board_of(&[(30, BOTTOM), (30, 0), (30, 1)]),
+        ] {
+            assert_eq!(count_components(&board, BoundaryMode::Wrap), 1);
+            assert_eq!(count_components(&board, BoundaryMode::Dead), 2);
+            let analysis = analyze_until(&board, &TORUS, 100, || true);
+            assert_eq!(analysis.final_population, 3);
+            assert_eq!(analysis.generations_to_stabilize, Some(0));
+            assert_eq!(analysis.detected_period, Some(2));
+        }
+    }
+
+    #[test]
+    fn blocks_across_the_corner_are_still_lifes() {
+        let board = board_of(&[(RIGHT, BOTTOM), (0, BOTTOM), (RIGHT, 0), (0, 0)]);
+        assert_eq!(count_components(&board, BoundaryMode::Wrap), 1);
+        assert_eq!(count_components(&board, BoundaryMode::Dead), 4);
+        assert_eq!(perimeter_length(&board, BoundaryMode::Wrap), 8);
+        assert_eq!(perimeter_length(&board, BoundaryMode::Dead), 16);
+        assert_eq!(pending_changes(&board, &TORUS), (0, 0));
+        assert_eq!(pending_changes(&board, &CONWAY), (0, 4));
+
+        let analysis = analyze_until(&board, &TORUS, 100, || true);
+        assert_eq!(analysis.final_population, 4);
+        assert_eq!(analysis.peak_population, 4);
+        assert_eq!(analysis.generations_to_stabilize, Some(0));
+        assert_eq!(analysis.detected_period, Some(1));
+        // Same as a block away from the edges
+        assert!(
+            edge_detect(&board, BoundaryMode::Wrap)
+                .iter()
+                .flatten()
+                .filter(|&&c| c)
+                .count()
+                == 16
+        );
+    }
+
+    #[test]
+    fn convolutions_wrap_around_on_a_torus() {
+        let board = board_of(&[(RIGHT, BOTTOM), (0, BOTTOM), (RIGHT, 0), (0, 0)]);
+        let sums = convolution_3x3(&board, &MOORE_KERNEL, BoundaryMode::Wrap);
+        for &(x, y) in &[(RIGHT, BOTTOM), (0, BOTTOM), (RIGHT, 0), (0, 0)] {
+            assert_eq!(sums[x][y], 3);
+        }
+        assert_eq!(sums[1][1], 1);
+        assert_eq!(sums[RIGHT - 1][BOTTOM - 1], 1);
+        assert_eq!(sums[1][BOTTOM], 2);
+        assert_eq!(sums[2][2], 0);
+        assert_eq!(
+            convolution_3x3(&board, &MOORE_KERNEL, BoundaryMode::Dead)[0][0],
+            0
+        );
+
+        // Weighing only the right neighbor, the cell on the left edge is seen from the right
+        let right = [[0, 0, 0], [0, 0, 1], [0, 0, 0]];
+        let board = board_of(&[(0, 5)]);
+        let sums = convolution_3x3(&board, &right, BoundaryMode::Wrap);
+        assert_eq!(sums[RIGHT][5], 1);
+        assert_eq!(sums.iter().flatten().sum::<i16>(), 1);
+        let sums = convolution_3x3(&board, &right, BoundaryMode::Dead);
+        assert_eq!(sums.iter().flatten().sum::<i16>(), 0);
+    }
+}
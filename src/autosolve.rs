@@ -0,0 +1,122 @@
+use crate::analysis::{self, Analysis};
+use crate::config::AppConfig;
+use crate::eadk::timing;
+use crate::rng::Rng;
+use crate::{apply_noise, run_once, Board, Simulation, COLUMN_SIZE, LINE_SIZE};
+
+/// Time budgets of a search in seconds, cycled through in its prompt
+pub const BUDGETS_S: [u64; 3] = [10, 30, 60];
+/// Most generations a soup is run for, so a single soup can't take the whole budget
+pub const MAX_GENERATIONS: u32 = 1000;
+
+/// What makes a soup better than another
+#[derive(Clone, Copy)]
+pub enum Criterion {
+    /// Takes the most generations to become static or periodic
+    LongestSettling,
+    /// Has the most cells alive at once
+    PeakPopulation,
+}
+
+impl Criterion {
+    pub fn toggle(self) -> Self {
+        match self {
+            Criterion::LongestSettling => Criterion::PeakPopulation,
+            Criterion::PeakPopulation => Criterion::LongestSettling,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Criterion::LongestSettling => "longest-lived",
+            Criterion::PeakPopulation => "most crowded",
+        }
+    }
+
+    fn score(self, analysis: &Analysis) -> u32 {
+        match self {
+            Criterion::LongestSettling => {
+                analysis.generations_to_stabilize.unwrap_or(MAX_GENERATIONS)
+            }
+            Criterion::PeakPopulation => analysis.peak_population,
+        }
+    }
+}
+
+/// The best soup of a search
+pub struct Found {
+    pub seed: u32,
+    pub analysis: Analysis,
+    /// Soups run during the search
+    pub tried: u32,
+}
+
+/// Fill the board with a soup drawn from `seed`, `density` percent of the cells being alive
+pub fn seed_soup(board: &mut Board<bool>, seed: u32, density: u32) {
+    let mut rng = Rng::new(seed);
+    board
+        .iter_mut()
+        .flatten()
+        .for_each(|cell| *cell = rng.below(100) < density);
+}
+
+/// Run soups of random seeds until `deadline_ms`, keeping the best one by the criterion
+///
+/// Soups are run without drawing them, as they would run in the app, with its settings and
+/// the mask of `simulation`; the one running at the deadline is finished first. The edge
+/// buffer is cleared before each soup and its generations draw from [`soup_rng`], so a soup
+/// is replayed the same from a cleared buffer and that generator.
+/// `keep_going` is called before every generation with the soups run so far; when it
+/// returns false the search stops and nothing is found.
+pub fn search(
+    (config, simulation): (&AppConfig, &mut Simulation),
+    criterion: Criterion,
+    deadline_ms: u64,
+    seeds: &mut Rng,
+    mut keep_going: impl FnMut(u32) -> bool,
+) -> Option<Found> {
+    let mut best: Option<Found> = None;
+    let mut tried = 0;
+    let mut soup: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    while tried == 0 || timing::millis() < deadline_ms {
+        let seed = seeds.next_u32();
+        seed_soup(&mut soup, seed, config.rule.soup_density());
+        simulation.edge.clear();
+        simulation.rng = soup_rng(seed);
+        let mut stopped = false;
+        let analysis = analysis::analyze_with(
+            &soup,
+            |board| {
+                let mut changes = run_once(board, config, simulation);
+                apply_noise(board, config.noise as u32, simulation, &mut changes);
+                changes
+            },
+            MAX_GENERATIONS,
+            || {
+                stopped = !keep_going(tried);
+                !stopped
+            },
+        );
+        if stopped {
+            return None;
+        }
+        tried += 1;
+
+        if best
+            .as_ref()
+            .is_none_or(|best| criterion.score(&analysis) > criterion.score(&best.analysis))
+        {
+            best = Some(Found {
+                seed,
+                analysis,
+                tried,
+            });
+        }
+    }
+    best.map(|best| Found { tried, ..best })
+}
+
+/// Generator the generations of a soup draw from, in the search and when it's replayed
+pub fn soup_rng(seed: u32) -> Rng {
+    Rng::new(seed)
+}
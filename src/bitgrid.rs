@@ -0,0 +1,332 @@
+use crate::patterns::{self, Pattern, RleError};
+use crate::{Board, BOARD_SIZE, COLUMN_SIZE, LINE_SIZE};
+
+const WORDS: usize = BOARD_SIZE.div_ceil(32);
+
+/// How the cells of a grid are combined with the board
+#[derive(Clone, Copy)]
+pub enum BlitMode {
+    /// Alive cells of the grid come alive, the others are left as is
+    Or,
+    /// The board under the grid becomes a copy of it
+    Replace,
+    /// Alive cells of the grid flip the board
+    Xor,
+}
+
+/// A small rectangle of cells, one bit per cell in row-major order, to be put on the board
+#[derive(Clone, Copy)]
+pub struct BitGrid {
+    width: u16,
+    height: u16,
+    bits: [u32; WORDS],
+}
+
+impl BitGrid {
+    /// An empty grid, of at most as many cells as the board
+    pub fn new(width: u16, height: u16) -> Self {
+        assert!(width as usize * height as usize <= BOARD_SIZE);
+        Self {
+            width,
+            height,
+            bits: [0; WORDS],
+        }
+    }
+
+    /// A grid with every cell alive
+    pub fn filled(width: u16, height: u16) -> Self {
+        let mut grid = Self::new(width, height);
+        for x in 0..width {
+            for y in 0..height {
+                grid.set(x, y, true);
+            }
+        }
+        grid
+    }
+
+    /// Decode a RLE pattern, cropped to the size of the board
+    pub fn decode(rle: &str) -> Result<Self, RleError> {
+        let (width, height) = patterns::decode(rle, |_, _| {})?;
+        let mut grid = Self::new(width.min(LINE_SIZE), height.min(COLUMN_SIZE));
+        patterns::decode(rle, |x, y| grid.set(x, y, true))?;
+        Ok(grid)
+    }
+
+    /// Unpack a library pattern, cropped to the size of the board
+    pub fn from_pattern(pattern: &Pattern) -> Self {
+        let mut grid = Self::new(
+            pattern.width.min(LINE_SIZE),
+            pattern.height.min(COLUMN_SIZE),
+        );
+        for x in 0..grid.width {
+            for y in 0..grid.height {
+                grid.set(x, y, pattern.get(x, y));
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Whether a cell is alive, cells outside of the grid are dead
+    pub fn get(&self, x: u16, y: u16) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        let i = y as usize * self.width as usize + x as usize;
+        self.bits[i / 32] >> (i % 32) & 1 != 0
+    }
+
+    /// Set a cell, cells outside of the grid are ignored
+    pub fn set(&mut self, x: u16, y: u16, alive: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let i = y as usize * self.width as usize + x as usize;
+        if alive {
+            self.bits[i / 32] |= 1 << (i % 32);
+        } else {
+            self.bits[i / 32] &= !(1 << (i % 32));
+        }
+    }
+
+    /// Put the grid with its top left corner on a cell, which can be off the board
+    ///
+    /// Only the part of the grid over the board is blitted.
+    pub fn blit_onto(&self, board: &mut Board<bool>, at: (i16, i16), mode: BlitMode) {
+        for x in 0..self.width {
+            let board_x = at.0 as i32 + x as i32;
+            if !(0..LINE_SIZE as i32).contains(&board_x) {
+                continue;
+            }
+            for y in 0..self.height {
+                let board_y = at.1 as i32 + y as i32;
+                if !(0..COLUMN_SIZE as i32).contains(&board_y) {
+                    continue;
+                }
+                let cell = &mut board[board_x as usize][board_y as usize];
+                let alive = self.get(x, y);
+                *cell = match mode {
+                    BlitMode::Or => *cell || alive,
+                    BlitMode::Replace => alive,
+                    BlitMode::Xor => *cell != alive,
+                };
+            }
+        }
+    }
+
+    /// Mirror the grid left to right
+    pub fn flip_horizontal(&mut self) {
+        let original = *self;
+        for x in 0..self.width {
+            for y in 0..self.height {
+                self.set(x, y, original.get(self.width - 1 - x, y));
+            }
+        }
+    }
+
+    /// Rotate the grid a quarter turn clockwise, swapping its width and height
+    pub fn rotate_clockwise(&mut self) {
+        let original = *self;
+        *self = Self::new(original.height, original.width);
+        for x in 0..self.width {
+            for y in 0..self.height {
+                self.set(x, y, original.get(y, original.height - 1 - x));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+
+    /// Alive cells of a grid, column by column
+    fn cells(grid: &BitGrid) -> Vec<(u16, u16)> {
+        (0..grid.width())
+            .flat_map(|x| (0..grid.height()).map(move |y| (x, y)))
+            .filter(|&(x, y)| grid.get(x, y))
+            .collect()
+    }
+
+    fn alive(board: &Board<bool>) -> Vec<(usize, usize)> {
+        (0..LINE_SIZE as usize)
+            .flat_map(|x| (0..COLUMN_SIZE as usize).map(move |y| (x, y)))
+            .filter(|&(x, y)| board[x][y])
+            .collect()
+    }
+
+    fn glider() -> BitGrid {
+        BitGrid::decode("bob$2bo$3o!").ok().unwrap()
+    }
+
+    #[test]
+    fn decode_sizes_and_fills_the_grid() {
+        let glider = glider();
+        assert_eq!((glider.width(), glider.height()), (3, 3));
+        assert_eq!(cells(&glider), [(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)]);
+
+        // Blank rows count, trailing dead cells don't
+        let rows = BitGrid::decode("x = 4, y = 3\n2o2b$\n2$o!").ok().unwrap();
+        assert_eq!((rows.width(), rows.height()), (2, 4));
+        assert_eq!(cells(&rows), [(0, 0), (0, 3), (1, 0)]);
+    }
+
+    #[test]
+    fn decode_crops_to_the_board() {
+        let line = BitGrid::decode("100o!").ok().unwrap();
+        assert_eq!((line.width(), line.height()), (LINE_SIZE, 1));
+        assert!(line.get(LINE_SIZE - 1, 0));
+        let column = BitGrid::decode("o99$o!").ok().unwrap();
+        assert_eq!((column.width(), column.height()), (1, COLUMN_SIZE));
+    }
+
+    #[test]
+    fn decode_tells_where_it_failed() {
+        let error = BitGrid::decode("bob$\n2bq!").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn blit_modes_combine_with_the_board() {
+        // The middle row of the glider, over a cell alive under its dead cell and
+        // another under its alive one
+        let mut board = EMPTY;
+        board[10][11] = true;
+        board[12][11] = true;
+
+        let mut or = board;
+        glider().blit_onto(&mut or, (10, 10), BlitMode::Or);
+        assert_eq!(
+            alive(&or),
+            [(10, 11), (10, 12), (11, 10), (11, 12), (12, 11), (12, 12)]
+        );
+
+        let mut replace = board;
+        glider().blit_onto(&mut replace, (10, 10), BlitMode::Replace);
+        assert_eq!(
+            alive(&replace),
+            [(10, 12), (11, 10), (11, 12), (12, 11), (12, 12)]
+        );
+
+        let mut xor = board;
+        glider().blit_onto(&mut xor, (10, 10), BlitMode::Xor);
+        assert_eq!(
+            alive(&xor),
+            [(10, 11), (10, 12), (11, 10), (11, 12), (12, 12)]
+        );
+    }
+
+    #[test]
+    fn blits_are_clipped_at_every_edge() {
+        let (right, bottom) = (LINE_SIZE as i16 - 2, COLUMN_SIZE as i16 - 2);
+        let (last_x, last_y) = (LINE_SIZE as usize - 1, COLUMN_SIZE as usize - 1);
+        let square = BitGrid::filled(3, 3);
+        for (at, expected) in [
+            (
+                (-1, 5),
+                vec![(0, 5), (0, 6), (0, 7), (1, 5), (1, 6), (1, 7)],
+            ),
+            (
+                (5, -1),
+                vec![(5, 0), (5, 1), (6, 0), (6, 1), (7, 0), (7, 1)],
+            ),
+            (
+                (right, 5),
+                vec![(78, 5), (78, 6), (78, 7), (79, 5), (79, 6), (79, 7)],
+            ),
+            (
+                (5, bottom),
+                vec![(5, 58), (5, 59), (6, 58), (6, 59), (7, 58), (7, 59)],
+            ),
+            ((-2, -2), vec![(0, 0)]),
+            ((right + 1, -2), vec![(last_x, 0)]),
+            ((-2, bottom + 1), vec![(0, last_y)]),
+            ((right + 1, bottom + 1), vec![(last_x, last_y)]),
+            ((-3, 5), vec![]),
+            ((5, COLUMN_SIZE as i16), vec![]),
+            ((i16::MIN, i16::MAX), vec![]),
+        ] {
+            let mut board = EMPTY;
+            square.blit_onto(&mut board, at, BlitMode::Or);
+            assert_eq!(alive(&board), expected, "at {:?}", at);
+        }
+    }
+
+    #[test]
+    fn lines_of_cells_transform() {
+        let mut row = BitGrid::new(5, 1);
+        row.set(0, 0, true);
+        row.set(3, 0, true);
+        row.flip_horizontal();
+        assert_eq!(cells(&row), [(1, 0), (4, 0)]);
+        row.rotate_clockwise();
+        assert_eq!((row.width(), row.height()), (1, 5));
+        assert_eq!(cells(&row), [(0, 1), (0, 4)]);
+
+        // A column is its own mirror
+        row.flip_horizontal();
+        assert_eq!(cells(&row), [(0, 1), (0, 4)]);
+        row.rotate_clockwise();
+        assert_eq!((row.width(), row.height()), (5, 1));
+        assert_eq!(cells(&row), [(0, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn non_square_grids_rotate() {
+        // An L, 2 wide and 3 high:
+        // o.
+        // o.
+        // oo
+        let mut l = BitGrid::decode("o$o$2o!").ok().unwrap();
+        l.rotate_clockwise();
+        // ooo
+        // o..
+        assert_eq!((l.width(), l.height()), (3, 2));
+        assert_eq!(cells(&l), [(0, 0), (0, 1), (1, 0), (2, 0)]);
+        l.flip_horizontal();
+        // ooo
+        // ..o
+        assert_eq!(cells(&l), [(0, 0), (1, 0), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn transforms_round_trip() {
+        let original = BitGrid::decode("3o$o2bo$bo!").ok().unwrap();
+        let mut grid = original;
+        for _ in 0..4 {
+            grid.rotate_clockwise();
+        }
+        assert_eq!(cells(&grid), cells(&original));
+        grid.flip_horizontal();
+        assert_ne!(cells(&grid), cells(&original));
+        grid.flip_horizontal();
+        assert_eq!(cells(&grid), cells(&original));
+
+        // Mirroring and half turns commute, so two of each give the grid back
+        let mut turned = original;
+        turned.rotate_clockwise();
+        turned.rotate_clockwise();
+        assert_ne!(cells(&turned), cells(&original));
+        turned.flip_horizontal();
+        turned.rotate_clockwise();
+        turned.rotate_clockwise();
+        turned.flip_horizontal();
+        assert_eq!(cells(&turned), cells(&original));
+    }
+
+    #[test]
+    fn library_patterns_unpack_like_they_decode() {
+        let packed = BitGrid::from_pattern(&crate::patterns::PATTERNS[0]);
+        assert_eq!(cells(&packed), cells(&glider()));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::eadk::{display, key, Point, Rect, State, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::palette::ColorPalette;
+use heapless::String;
+
+/// Most characters in a caption
+pub const MAX_LENGTH: usize = 24;
+/// Space between the border of the box and its text
+const PADDING: u16 = 4;
+
+/// Characters typed by the keys, the ones of the ALPHA layer of the calculator
+const LETTERS: [(u32, u8); 29] = [
+    (key::EXP, b'a'),
+    (key::LN, b'b'),
+    (key::LOG, b'c'),
+    (key::IMAGINARY, b'd'),
+    (key::COMMA, b'e'),
+    (key::POWER, b'f'),
+    (key::SINE, b'g'),
+    (key::COSINE, b'h'),
+    (key::TANGENT, b'i'),
+    (key::PI, b'j'),
+    (key::SQRT, b'k'),
+    (key::SQUARE, b'l'),
+    (key::SEVEN, b'm'),
+    (key::EIGHT, b'n'),
+    (key::NINE, b'o'),
+    (key::LEFTPARENTHESIS, b'p'),
+    (key::RIGHTPARENTHESIS, b'q'),
+    (key::FOUR, b'r'),
+    (key::FIVE, b's'),
+    (key::SIX, b't'),
+    (key::MULTIPLICATION, b'u'),
+    (key::DIVISION, b'v'),
+    (key::ONE, b'w'),
+    (key::TWO, b'x'),
+    (key::THREE, b'y'),
+    (key::PLUS, b'z'),
+    (key::MINUS, b' '),
+    (key::ZERO, b'?'),
+    (key::DOT, b'!'),
+];
+/// Characters typed by the keys while ALPHA is held
+const DIGITS: [(u32, u8); 11] = [
+    (key::ZERO, b'0'),
+    (key::ONE, b'1'),
+    (key::TWO, b'2'),
+    (key::THREE, b'3'),
+    (key::FOUR, b'4'),
+    (key::FIVE, b'5'),
+    (key::SIX, b'6'),
+    (key::SEVEN, b'7'),
+    (key::EIGHT, b'8'),
+    (key::NINE, b'9'),
+    (key::DOT, b'.'),
+];
+
+/// A line of text over the board, telling what a photo of the screen shows
+///
+/// It's drawn again over the board every frame, so the cells drawn under it don't show
+/// through, until it's dismissed.
+pub struct Caption {
+    text: String<MAX_LENGTH>,
+    /// Whether it's at the top of the screen rather than at the bottom
+    pub top: bool,
+}
+
+impl Caption {
+    pub const fn new() -> Self {
+        Self {
+            text: String::new(),
+            top: false,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether there's a caption to show
+    pub fn is_shown(&self) -> bool {
+        !self.text.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+
+    /// Type the character of the key pressed in `pressed`, or erase the last one on BACKSPACE
+    ///
+    /// Holding SHIFT in `held` types capitals, and ALPHA digits. Returns whether the text
+    /// changed, which isn't the case past [`MAX_LENGTH`] characters.
+    pub fn push_key(&mut self, pressed: &State, held: &State) -> bool {
+        if pressed.key_down(key::BACKSPACE) {
+            return self.text.pop().is_some();
+        }
+        let characters = if held.key_down(key::ALPHA) {
+            &DIGITS[..]
+        } else {
+            &LETTERS[..]
+        };
+        match characters.iter().find(|&&(k, _)| pressed.key_down(k)) {
+            Some(&(_, character)) => {
+                let character = if held.key_down(key::SHIFT) {
+                    character.to_ascii_uppercase()
+                } else {
+                    character
+                };
+                self.text.push(character as char).is_ok()
+            }
+            None => false,
+        }
+    }
+
+    /// Part of the screen covered by the box
+    pub fn area(&self) -> Rect {
+        let (_, glyph_height) = display::glyph_size(true);
+        let height = glyph_height + 2 * PADDING;
+        let y = if self.top { 0 } else { SCREEN_HEIGHT - height };
+        Rect::new(0, y, SCREEN_WIDTH, height)
+    }
+
+    /// Draw the text centered in its box, with a trailing underscore while it's typed
+    pub fn draw(&self, typing: bool, palette: &ColorPalette) {
+        let mut line: String<{ MAX_LENGTH + 2 }> = String::new();
+        line.push_str(&self.text).unwrap();
+        if typing {
+            line.push('_').unwrap();
+        }
+        line.push('\0').unwrap();
+        let area = self.area();
+        let width = display::text_width(&line, true);
+        display::push_rect_uniform(area, palette.status_bg);
+        display::draw_string(
+            &line,
+            Point::new(SCREEN_WIDTH.saturating_sub(width) / 2, area.y + PADDING),
+            true,
+            palette.live_cell,
+            palette.status_bg,
+        );
+    }
+}
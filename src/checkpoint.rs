@@ -0,0 +1,236 @@
+use crate::eadk::timing;
+use crate::packed::{fnv1a, PackedBoard, FNV_OFFSET};
+use crate::rng::Rng;
+use crate::Board;
+use core::convert::TryInto;
+
+/// A copy of the simulation, to resume from
+#[derive(Clone, Copy)]
+struct Checkpoint {
+    board: PackedBoard,
+    generation: u32,
+    rng: Rng,
+    /// Only matches the content once it has been fully written
+    checksum: u32,
+}
+
+impl Checkpoint {
+    /// Size of a serialized checkpoint
+    const SIZE: usize = PackedBoard::SIZE + 12;
+
+    fn compute_checksum(&self) -> u32 {
+        let hash = self.board.checksum(FNV_OFFSET);
+        self.generation
+            .to_le_bytes()
+            .iter()
+            .chain(self.rng.state().to_le_bytes().iter())
+            .copied()
+            .fold(hash, fnv1a)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.checksum == self.compute_checksum()
+    }
+
+    fn write_bytes(&self, bytes: &mut [u8]) {
+        let (board, rest) = bytes.split_at_mut(PackedBoard::SIZE);
+        board.copy_from_slice(&self.board.to_bytes());
+        rest[0..4].copy_from_slice(&self.generation.to_le_bytes());
+        rest[4..8].copy_from_slice(&self.rng.state().to_le_bytes());
+        rest[8..12].copy_from_slice(&self.checksum.to_le_bytes());
+    }
+
+    /// Read a serialized checkpoint, whose checksum is checked when it's resumed
+    fn read_bytes(bytes: &[u8]) -> Self {
+        let (board, rest) = bytes.split_at(PackedBoard::SIZE);
+        let word = |i: usize| u32::from_le_bytes([rest[i], rest[i + 1], rest[i + 2], rest[i + 3]]);
+        Self {
+            board: PackedBoard::from_bytes(board.try_into().unwrap()),
+            generation: word(0),
+            rng: Rng::new(word(4)),
+            checksum: word(8),
+        }
+    }
+}
+
+/// Two checkpoint slots, written alternately
+///
+/// The oldest slot is overwritten, so if something goes wrong while copying,
+/// the other one is still there, and the broken one fails its checksum.
+#[derive(Clone)]
+pub struct Checkpoints {
+    slots: [Checkpoint; 2],
+    /// Slot written next
+    next: usize,
+    /// Time taken by the last copy, to make sure it doesn't slow the run down
+    pub last_copy_ms: u64,
+}
+
+impl Checkpoints {
+    /// Size of the serialized checkpoints
+    pub const SIZE: usize = 2 * Checkpoint::SIZE + 1;
+
+    pub const fn new() -> Self {
+        let empty = Checkpoint {
+            board: PackedBoard::EMPTY,
+            generation: 0,
+            rng: Rng::new(0),
+            // The hash of an empty slot isn't zero, so it starts invalid
+            checksum: 0,
+        };
+        Self {
+            slots: [empty; 2],
+            next: 0,
+            last_copy_ms: 0,
+        }
+    }
+
+    /// The checkpoints of an earlier session, or none if they weren't kept
+    pub fn load() -> Self {
+        read_storage()
+            .map(|bytes| Self::from_bytes(&bytes))
+            .unwrap_or(Self::new())
+    }
+
+    /// Copy the board into the oldest slot, and keep both slots for the next session
+    pub fn save(&mut self, board: &Board<bool>, generation: u32, rng: Rng) {
+        let start = timing::millis();
+        self.write(board, generation, rng);
+        write_storage(&self.to_bytes());
+        self.last_copy_ms = timing::millis() - start;
+    }
+
+    fn write(&mut self, board: &Board<bool>, generation: u32, rng: Rng) {
+        let slot = &mut self.slots[self.next];
+        slot.checksum = 0;
+        slot.board = PackedBoard::pack(board);
+        slot.generation = generation;
+        slot.rng = rng;
+        slot.checksum = slot.compute_checksum();
+        self.next = 1 - self.next;
+    }
+
+    /// The newest valid checkpoint, as the board, generation and generator it was saved with
+    pub fn newest(&self) -> Option<(&PackedBoard, u32, Rng)> {
+        [1 - self.next, self.next]
+            .iter()
+            .map(|&i| &self.slots[i])
+            .find(|slot| slot.is_valid())
+            .map(|slot| (&slot.board, slot.generation, slot.rng))
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        for (slot, chunk) in self
+            .slots
+            .iter()
+            .zip(bytes.chunks_exact_mut(Checkpoint::SIZE))
+        {
+            slot.write_bytes(chunk);
+        }
+        bytes[Self::SIZE - 1] = self.next as u8;
+        bytes
+    }
+
+    /// Read serialized checkpoints, a slot failing its checksum is never resumed
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        let slot =
+            |i: usize| Checkpoint::read_bytes(&bytes[i * Checkpoint::SIZE..][..Checkpoint::SIZE]);
+        Self {
+            slots: [slot(0), slot(1)],
+            next: (bytes[Self::SIZE - 1] & 1) as usize,
+            last_copy_ms: 0,
+        }
+    }
+}
+
+// Checkpoints would be resumed in the next session from the storage, but apps get none
+// from Epsilon at this API level: nothing is read back, and writing does nothing.
+fn read_storage() -> Option<[u8; Checkpoints::SIZE]> {
+    None
+}
+
+fn write_storage(_bytes: &[u8; Checkpoints::SIZE]) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{COLUMN_SIZE, LINE_SIZE};
+
+    fn board_with(cells: &[(usize, usize)]) -> Board<bool> {
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        cells.iter().for_each(|&(x, y)| board[x][y] = true);
+        board
+    }
+
+    fn newest(checkpoints: &Checkpoints) -> Option<(PackedBoard, u32, u32)> {
+        checkpoints
+            .newest()
+            .map(|(board, generation, rng)| (*board, generation, rng.state()))
+    }
+
+    #[test]
+    fn the_newest_checkpoint_is_resumed() {
+        let mut checkpoints = Checkpoints::new();
+        assert!(newest(&checkpoints).is_none());
+        let (a, b) = (board_with(&[(1, 2)]), board_with(&[(3, 4), (79, 59)]));
+        checkpoints.write(&a, 100, Rng::new(7));
+        assert!(newest(&checkpoints) == Some((PackedBoard::pack(&a), 100, 7)));
+        checkpoints.write(&b, 200, Rng::new(8));
+        assert!(newest(&checkpoints) == Some((PackedBoard::pack(&b), 200, 8)));
+        // The oldest slot is the one overwritten
+        checkpoints.write(&a, 300, Rng::new(9));
+        assert!(newest(&checkpoints) == Some((PackedBoard::pack(&a), 300, 9)));
+    }
+
+    #[test]
+    fn saved_checkpoints_load_back() {
+        let mut checkpoints = Checkpoints::new();
+        checkpoints.write(&board_with(&[(1, 2)]), 100, Rng::new(7));
+        checkpoints.write(&board_with(&[(3, 4), (79, 59)]), 200, Rng::new(8));
+        let bytes = checkpoints.to_bytes();
+        let loaded = Checkpoints::from_bytes(&bytes);
+        assert!(newest(&loaded) == newest(&checkpoints));
+        assert_eq!(loaded.to_bytes(), bytes);
+        // Without storage, there's nothing to resume in a new session
+        assert!(newest(&Checkpoints::load()).is_none());
+    }
+
+    #[test]
+    fn corrupted_slots_fail_their_checksum() {
+        let mut checkpoints = Checkpoints::new();
+        let (older, newer) = (board_with(&[(1, 2)]), board_with(&[(3, 4)]));
+        checkpoints.write(&older, 100, Rng::new(7));
+        checkpoints.write(&newer, 200, Rng::new(8));
+        let bytes = checkpoints.to_bytes();
+        let older = Some((PackedBoard::pack(&older), 100, 7));
+
+        // A flipped bit anywhere in the newest slot, board, generation, generator or checksum
+        for i in [
+            0,
+            PackedBoard::SIZE / 2,
+            PackedBoard::SIZE,
+            PackedBoard::SIZE + 4,
+            Checkpoint::SIZE - 1,
+        ] {
+            let mut corrupted = bytes;
+            corrupted[Checkpoint::SIZE + i] ^= 0x10;
+            assert!(
+                newest(&Checkpoints::from_bytes(&corrupted)) == older,
+                "byte {}",
+                i
+            );
+        }
+        // Both broken, there's nothing to resume
+        let mut corrupted = bytes;
+        corrupted[3] ^= 1;
+        corrupted[Checkpoint::SIZE + 3] ^= 1;
+        assert!(newest(&Checkpoints::from_bytes(&corrupted)).is_none());
+
+        // A slot cut off halfway while being written, as an interrupted copy leaves it
+        let mut interrupted = checkpoints.clone();
+        interrupted.slots[1].checksum = 0;
+        interrupted.slots[1].board = PackedBoard::pack(&board_with(&[(5, 5)]));
+        assert!(newest(&interrupted) == older);
+    }
+}
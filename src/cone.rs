@@ -0,0 +1,111 @@
+use crate::eadk::{display, Color, Point};
+use crate::palette::ColorPalette;
+use crate::rule::{Automaton, BoundaryMode, Neighborhood};
+use crate::viewport::{LogicalPos, Viewport};
+use crate::{draw_image, run_once_sync, Board, BOARD_SIZE, COLUMN_SIZE, LINE_SIZE};
+use core::fmt::Write;
+use heapless::String;
+
+/// Color the cells in a cone are tinted toward
+pub const TINT: Color = Color::from_rgb888(255, 128, 0);
+/// Share of the tint in the color of the cells in a cone, out of 255
+pub const TINT_AMOUNT: u8 = 120;
+/// Generations a cone is first shown for
+pub const FIRST_GENERATIONS: u32 = 10;
+/// Most generations a cone is shown for, it covers the whole board by then
+pub const MAX_GENERATIONS: u32 = LINE_SIZE as u32;
+
+/// Distance between two coordinates on an axis of `size` cells, the short way on the torus
+fn axis_distance(a: u16, b: u16, size: u16, boundary: BoundaryMode) -> u16 {
+    let distance = a.abs_diff(b);
+    match boundary {
+        BoundaryMode::Wrap => distance.min(size - distance),
+        BoundaryMode::Dead => distance,
+    }
+}
+
+/// Cells whose state after `generations` could depend on the state of `origin` now
+///
+/// Influence spreads by one neighbor a generation, so the cone is the square of cells
+/// within `generations` of `origin`, and with the diagonal neighborhood only the cells of
+/// its color on a checkerboard. It's clipped by dead edges, and on the torus it wraps
+/// around and overlaps itself once it's wider than the board.
+pub fn light_cone(
+    origin: LogicalPos,
+    generations: u32,
+    neighborhood: Neighborhood,
+    boundary: BoundaryMode,
+) -> Board<bool> {
+    let mut cone = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    for (x, column) in cone.iter_mut().enumerate() {
+        for (y, cell) in column.iter_mut().enumerate() {
+            let dx = axis_distance(x as u16, origin.x, LINE_SIZE, boundary);
+            let dy = axis_distance(y as u16, origin.y, COLUMN_SIZE, boundary);
+            *cell = dx.max(dy) as u32 <= generations
+                && match neighborhood {
+                    Neighborhood::Moore => true,
+                    // Both sizes are even, so going around the torus keeps the color
+                    Neighborhood::Diagonal => (dx + dy).is_multiple_of(2),
+                };
+        }
+    }
+    cone
+}
+
+/// Cells whose state after `generations` actually changes when `origin` is flipped now
+///
+/// The board is run twice, as it is and with `origin` flipped, in synchronous generations
+/// and without the edge buffer. The result is always within the [`light_cone`].
+pub fn actual_cone(
+    board: &Board<bool>,
+    origin: LogicalPos,
+    generations: u32,
+    automaton: &impl Automaton,
+) -> Board<bool> {
+    let mut kept = *board;
+    let mut flipped = *board;
+    let cell = &mut flipped[origin.x as usize][origin.y as usize];
+    *cell = !*cell;
+    for _ in 0..generations {
+        run_once_sync(&mut kept, automaton);
+        run_once_sync(&mut flipped, automaton);
+    }
+
+    let mut cone = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    for (x, column) in cone.iter_mut().enumerate() {
+        for (y, cell) in column.iter_mut().enumerate() {
+            *cell = kept[x][y] != flipped[x][y];
+        }
+    }
+    cone
+}
+
+/// Draw the board with the cells of the cone tinted, and a line telling what they are
+pub fn draw(
+    board: &Board<bool>,
+    cone: &Board<bool>,
+    (generations, actual): (u32, bool),
+    palette: &ColorPalette,
+    viewport: &Viewport,
+) {
+    let mut image = [Color::BLACK; BOARD_SIZE];
+    let mut cells = 0;
+    image.iter_mut().enumerate().for_each(|(i, pixel)| {
+        let cell = LogicalPos::new(i as u16 % LINE_SIZE, i as u16 / LINE_SIZE);
+        let (x, y) = (cell.x as usize, cell.y as usize);
+        *pixel = palette.cell(board[x][y], cell);
+        if cone[x][y] {
+            *pixel = pixel.mix(TINT, TINT_AMOUNT);
+            cells += 1;
+        }
+    });
+    draw_image(&image, viewport);
+
+    let mut text: String<48> = String::new();
+    if actual {
+        write!(text, "{} cells changed in {} gen\0", cells, generations).unwrap();
+    } else {
+        write!(text, "{} cells reachable in {} gen\0", cells, generations).unwrap();
+    }
+    display::draw_string(&text, Point::new(2, 2), false, Color::WHITE, Color::BLACK);
+}
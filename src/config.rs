@@ -0,0 +1,344 @@
+use crate::palette::{ColorPalette, Theme, PALETTES, THEMES, UNTINTED_PALETTES};
+use crate::rule::{BoundaryMode, Neighborhood, Rule};
+use crate::UpdateMode;
+
+/// Delay between generations while running
+#[derive(Clone, Copy)]
+pub enum SimSpeed {
+    Slow,
+    Medium,
+    Fast,
+    Max,
+}
+
+impl SimSpeed {
+    /// Time between two generations, in microseconds
+    pub fn period_us(self) -> u32 {
+        match self {
+            SimSpeed::Slow => 200_000,
+            SimSpeed::Medium => 50_000,
+            SimSpeed::Fast => 10_000,
+            SimSpeed::Max => 0,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            SimSpeed::Slow => SimSpeed::Medium,
+            SimSpeed::Medium => SimSpeed::Fast,
+            SimSpeed::Fast => SimSpeed::Max,
+            SimSpeed::Max => SimSpeed::Slow,
+        }
+    }
+}
+
+/// What the app does when it's opened
+#[derive(Clone, Copy, PartialEq)]
+pub enum StartupMode {
+    /// Open on an empty board in the editor
+    Editor,
+    /// Go straight into running a board, a random soup when there's none to resume
+    Run,
+}
+
+impl StartupMode {
+    pub fn toggle(self) -> Self {
+        match self {
+            StartupMode::Editor => StartupMode::Run,
+            StartupMode::Run => StartupMode::Editor,
+        }
+    }
+}
+
+/// Generations between two checkpoints, 0 for none
+pub const CHECKPOINT_INTERVALS: [u16; 4] = [0, 100, 1000, 10000];
+
+/// Chances in 1000 for each cell to flip after a generation, 0 for none
+pub const NOISE_LEVELS: [u8; 4] = [0, 1, 5, 20];
+
+/// User settings, remembered across sessions when storage is available
+#[derive(Clone, Copy)]
+pub struct AppConfig {
+    pub rule: Rule,
+    pub speed: SimSpeed,
+    /// Run as fast as possible, ignoring the speed
+    pub uncapped: bool,
+    pub palette_index: u8,
+    pub theme_index: u8,
+    pub boundary: BoundaryMode,
+    /// Simulate an invisible buffer around the board with dead edges
+    pub edge_buffer: bool,
+    pub update_mode: UpdateMode,
+    /// Generations between two checkpoints, one of [`CHECKPOINT_INTERVALS`]
+    pub checkpoint_every: u16,
+    /// Chances in 1000 for each cell to flip after a generation, one of [`NOISE_LEVELS`]
+    pub noise: u8,
+    /// Tint the rim of the board, where dead edges make the neighborhood smaller
+    pub rim_tint: bool,
+    pub startup: StartupMode,
+    /// Tint the sides of the screen with the state, running, stepping or paused
+    pub state_tint: bool,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            rule: Rule::CONWAY,
+            speed: SimSpeed::Fast,
+            uncapped: false,
+            palette_index: 0,
+            theme_index: 0,
+            boundary: BoundaryMode::Dead,
+            edge_buffer: false,
+            update_mode: UpdateMode::Synchronous,
+            checkpoint_every: 1000,
+            noise: 0,
+            rim_tint: false,
+            startup: StartupMode::Editor,
+            state_tint: false,
+        }
+    }
+}
+
+const MAGIC: u8 = 0x6C;
+const VERSION: u8 = 10;
+
+impl AppConfig {
+    /// Size of the serialized config
+    pub const SIZE: usize = 20;
+
+    /// Load the saved config, or the defaults if there is none
+    pub fn load() -> Self {
+        read_storage()
+            .and_then(|bytes| Self::from_bytes(&bytes))
+            .unwrap_or_default()
+    }
+
+    /// Save the config so the next session starts with it
+    pub fn save(&self) {
+        write_storage(&self.to_bytes());
+    }
+
+    /// Time between two generations while running, in microseconds, `None` when uncapped,
+    /// where generations are neither paced nor wait for the screen refresh
+    pub fn frame_period_us(&self) -> Option<u32> {
+        if self.uncapped {
+            None
+        } else {
+            Some(self.speed.period_us())
+        }
+    }
+
+    /// Switch to the next checkpoint interval
+    pub fn next_checkpoint_interval(&mut self) {
+        let i = CHECKPOINT_INTERVALS
+            .iter()
+            .position(|&every| every == self.checkpoint_every)
+            .unwrap_or(0);
+        self.checkpoint_every = CHECKPOINT_INTERVALS[(i + 1) % CHECKPOINT_INTERVALS.len()];
+    }
+
+    /// Switch to the next noise level
+    pub fn next_noise_level(&mut self) {
+        let i = NOISE_LEVELS
+            .iter()
+            .position(|&level| level == self.noise)
+            .unwrap_or(0);
+        self.noise = NOISE_LEVELS[(i + 1) % NOISE_LEVELS.len()];
+    }
+
+    pub fn palette(&self) -> &'static ColorPalette {
+        // The rim is only different when there is nothing alive past the edges
+        if self.rim_tint && self.boundary == BoundaryMode::Dead && !self.edge_buffer {
+            &PALETTES[self.palette_index as usize]
+        } else {
+            &UNTINTED_PALETTES[self.palette_index as usize]
+        }
+    }
+
+    pub fn theme(&self) -> &'static Theme {
+        &THEMES[self.theme_index as usize]
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let [birth_low, birth_high] = self.rule.birth.to_le_bytes();
+        let [survive_low, survive_high] = self.rule.survive.to_le_bytes();
+        let [checkpoint_low, checkpoint_high] = self.checkpoint_every.to_le_bytes();
+        [
+            MAGIC,
+            VERSION,
+            birth_low,
+            birth_high,
+            survive_low,
+            survive_high,
+            self.speed as u8,
+            self.palette_index,
+            self.boundary as u8,
+            self.update_mode as u8,
+            self.edge_buffer as u8,
+            self.theme_index,
+            self.uncapped as u8,
+            checkpoint_low,
+            checkpoint_high,
+            self.noise,
+            self.rim_tint as u8,
+            self.rule.neighborhood as u8,
+            self.startup as u8,
+            self.state_tint as u8,
+        ]
+    }
+
+    /// Parse a serialized config, `None` if it's corrupted or from another version
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Option<Self> {
+        if bytes[0] != MAGIC || bytes[1] != VERSION {
+            return None;
+        }
+        Some(Self {
+            rule: Rule {
+                birth: u16::from_le_bytes([bytes[2], bytes[3]]),
+                survive: u16::from_le_bytes([bytes[4], bytes[5]]),
+                neighborhood: match bytes[17] {
+                    0 => Neighborhood::Moore,
+                    1 => Neighborhood::Diagonal,
+                    _ => return None,
+                },
+            },
+            speed: match bytes[6] {
+                0 => SimSpeed::Slow,
+                1 => SimSpeed::Medium,
+                2 => SimSpeed::Fast,
+                3 => SimSpeed::Max,
+                _ => return None,
+            },
+            palette_index: if (bytes[7] as usize) < PALETTES.len() {
+                bytes[7]
+            } else {
+                return None;
+            },
+            boundary: match bytes[8] {
+                0 => BoundaryMode::Dead,
+                1 => BoundaryMode::Wrap,
+                _ => return None,
+            },
+            update_mode: match bytes[9] {
+                0 => UpdateMode::Synchronous,
+                1 => UpdateMode::AsyncRowMajor,
+                2 => UpdateMode::AsyncRandom,
+                _ => return None,
+            },
+            edge_buffer: match bytes[10] {
+                0 => false,
+                1 => true,
+                _ => return None,
+            },
+            theme_index: if (bytes[11] as usize) < THEMES.len() {
+                bytes[11]
+            } else {
+                return None;
+            },
+            uncapped: match bytes[12] {
+                0 => false,
+                1 => true,
+                _ => return None,
+            },
+            checkpoint_every: match u16::from_le_bytes([bytes[13], bytes[14]]) {
+                every if CHECKPOINT_INTERVALS.contains(&every) => every,
+                _ => return None,
+            },
+            noise: match bytes[15] {
+                level if NOISE_LEVELS.contains(&level) => level,
+                _ => return None,
+            },
+            rim_tint: match bytes[16] {
+                0 => false,
+                1 => true,
+                _ => return None,
+            },
+            startup: match bytes[18] {
+                0 => StartupMode::Editor,
+                1 => StartupMode::Run,
+                _ => return None,
+            },
+            state_tint: match bytes[19] {
+                0 => false,
+                1 => true,
+                _ => return None,
+            },
+        })
+    }
+}
+
+// Epsilon doesn't give apps a persistent storage at this API level,
+// so there is never a saved config and saving is dropped.
+fn read_storage() -> Option<[u8; AppConfig::SIZE]> {
+    None
+}
+
+fn write_storage(_bytes: &[u8; AppConfig::SIZE]) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEEDS: [SimSpeed; 4] = [
+        SimSpeed::Slow,
+        SimSpeed::Medium,
+        SimSpeed::Fast,
+        SimSpeed::Max,
+    ];
+
+    #[test]
+    fn capped_runs_wait_for_the_speed_and_uncapped_ones_never() {
+        let periods = SPEEDS.map(|speed| {
+            let config = AppConfig {
+                speed,
+                ..AppConfig::default()
+            };
+            let uncapped = AppConfig {
+                uncapped: true,
+                ..config
+            };
+            assert_eq!(uncapped.frame_period_us(), None);
+            config.frame_period_us().unwrap()
+        });
+        // Capped at Max, there's no delay but the screen is still waited for
+        assert_eq!(periods, [200_000, 50_000, 10_000, 0]);
+        assert_eq!(AppConfig::default().frame_period_us(), Some(10_000));
+    }
+
+    #[test]
+    fn saved_configs_load_back() {
+        let config = AppConfig {
+            rule: Rule::diagonal(&[1], &[1, 2]),
+            speed: SimSpeed::Slow,
+            uncapped: true,
+            palette_index: PALETTES.len() as u8 - 1,
+            theme_index: THEMES.len() as u8 - 1,
+            boundary: BoundaryMode::Wrap,
+            edge_buffer: true,
+            update_mode: UpdateMode::AsyncRandom,
+            checkpoint_every: CHECKPOINT_INTERVALS[CHECKPOINT_INTERVALS.len() - 1],
+            noise: NOISE_LEVELS[NOISE_LEVELS.len() - 1],
+            rim_tint: true,
+            startup: StartupMode::Run,
+            state_tint: true,
+        };
+        let bytes = config.to_bytes();
+        let loaded = AppConfig::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.to_bytes(), bytes);
+        assert!(loaded.rule == config.rule);
+        assert_eq!(loaded.frame_period_us(), None);
+        let bytes = AppConfig::default().to_bytes();
+        assert_eq!(AppConfig::from_bytes(&bytes).unwrap().to_bytes(), bytes);
+    }
+
+    #[test]
+    fn corrupted_configs_are_refused() {
+        let bytes = AppConfig::default().to_bytes();
+        for (i, value) in [(0, MAGIC + 1), (1, VERSION + 1), (6, 4), (12, 2), (13, 7)] {
+            let mut corrupted = bytes;
+            corrupted[i] = value;
+            assert!(AppConfig::from_bytes(&corrupted).is_none(), "byte {}", i);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::rule::BoundaryMode;
+use crate::viewport::LogicalPos;
+use crate::{Board, COLUMN_SIZE, LINE_SIZE};
+
+/// A cell of the board, always inside it
+///
+/// Every way to make or move one keeps it on the board, so it can index the board without
+/// checks.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Cursor {
+    x: u16,
+    y: u16,
+}
+
+impl Cursor {
+    pub const CENTER: Self = Self {
+        x: LINE_SIZE / 2,
+        y: COLUMN_SIZE / 2,
+    };
+
+    /// The cell at `(x, y)`, or the closest one on the board
+    pub fn clamped((x, y): (i32, i32)) -> Self {
+        Self {
+            x: x.clamp(0, LINE_SIZE as i32 - 1) as u16,
+            y: y.clamp(0, COLUMN_SIZE as i32 - 1) as u16,
+        }
+    }
+
+    /// The cell at `(x, y)`, `None` if it's off the board
+    pub fn checked((x, y): (i32, i32)) -> Option<Self> {
+        if (0..LINE_SIZE as i32).contains(&x) && (0..COLUMN_SIZE as i32).contains(&y) {
+            Some(Self::clamped((x, y)))
+        } else {
+            None
+        }
+    }
+
+    /// Move by `(dx, dy)`, stopping at the edges, or going across them on a torus
+    pub fn moved(self, (dx, dy): (i32, i32), boundary: BoundaryMode) -> Self {
+        let (x, y) = (self.x as i32 + dx, self.y as i32 + dy);
+        match boundary {
+            BoundaryMode::Dead => Self::clamped((x, y)),
+            BoundaryMode::Wrap => Self {
+                x: x.rem_euclid(LINE_SIZE as i32) as u16,
+                y: y.rem_euclid(COLUMN_SIZE as i32) as u16,
+            },
+        }
+    }
+
+    /// The cell `(dx, dy)` away, `None` if it's off the board
+    pub fn offset(self, (dx, dy): (i32, i32)) -> Option<Self> {
+        Self::checked((self.x as i32 + dx, self.y as i32 + dy))
+    }
+
+    pub fn position(self) -> LogicalPos {
+        LogicalPos::new(self.x, self.y)
+    }
+
+    pub fn cell_mut<T>(self, board: &mut Board<T>) -> &mut T {
+        debug_assert!(self.x < LINE_SIZE && self.y < COLUMN_SIZE);
+        &mut board[self.x as usize][self.y as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    const MOVES: u32 = 20_000;
+
+    /// A move of at most `reach` cells each way
+    fn random_move(rng: &mut Rng, reach: u32) -> (i32, i32) {
+        let mut coordinate = || rng.below(2 * reach + 1) as i32 - reach as i32;
+        (coordinate(), coordinate())
+    }
+
+    fn on_board(cursor: Cursor) -> bool {
+        let LogicalPos { x, y } = cursor.position();
+        x < LINE_SIZE && y < COLUMN_SIZE
+    }
+
+    #[test]
+    fn moves_stay_on_the_board() {
+        let mut rng = Rng::new(0x5EED);
+        for boundary in [BoundaryMode::Dead, BoundaryMode::Wrap] {
+            let mut cursor = Cursor::CENTER;
+            for _ in 0..MOVES {
+                cursor = cursor.moved(random_move(&mut rng, 100), boundary);
+                assert!(on_board(cursor));
+            }
+        }
+    }
+
+    #[test]
+    fn dead_edges_stop_moves_where_the_plane_would_leave() {
+        let mut rng = Rng::new(0xC0FFEE);
+        let mut cursor = Cursor::CENTER;
+        for _ in 0..MOVES {
+            let (dx, dy) = random_move(&mut rng, 100);
+            let LogicalPos { x, y } = cursor.position();
+            let expected = (
+                (x as i32 + dx).clamp(0, LINE_SIZE as i32 - 1),
+                (y as i32 + dy).clamp(0, COLUMN_SIZE as i32 - 1),
+            );
+            cursor = cursor.moved((dx, dy), BoundaryMode::Dead);
+            let LogicalPos { x, y } = cursor.position();
+            assert_eq!((x as i32, y as i32), expected);
+        }
+    }
+
+    #[test]
+    fn wrapped_moves_add_up_modulo_the_board() {
+        let mut rng = Rng::new(0xBEEF);
+        let mut cursor = Cursor::CENTER;
+        let (mut total_x, mut total_y) = (0i64, 0i64);
+        for _ in 0..MOVES {
+            let (dx, dy) = random_move(&mut rng, 100);
+            cursor = cursor.moved((dx, dy), BoundaryMode::Wrap);
+            total_x += dx as i64;
+            total_y += dy as i64;
+            let LogicalPos { x, y } = cursor.position();
+            assert_eq!(
+                (x as i64, y as i64),
+                (
+                    (Cursor::CENTER.x as i64 + total_x).rem_euclid(LINE_SIZE as i64),
+                    (Cursor::CENTER.y as i64 + total_y).rem_euclid(COLUMN_SIZE as i64),
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn offsets_are_on_the_board_or_none() {
+        let mut rng = Rng::new(0xFACE);
+        let mut cursor = Cursor::CENTER;
+        for _ in 0..MOVES {
+            let (dx, dy) = random_move(&mut rng, 10);
+            let LogicalPos { x, y } = cursor.position();
+            let (to_x, to_y) = (x as i32 + dx, y as i32 + dy);
+            let inside =
+                (0..LINE_SIZE as i32).contains(&to_x) && (0..COLUMN_SIZE as i32).contains(&to_y);
+            match cursor.offset((dx, dy)) {
+                Some(offset) => {
+                    assert!(inside);
+                    let LogicalPos { x, y } = offset.position();
+                    assert_eq!((x as i32, y as i32), (to_x, to_y));
+                    cursor = offset;
+                }
+                None => assert!(!inside),
+            }
+        }
+        assert!(Cursor::checked((-1, 0)).is_none());
+        assert!(Cursor::checked((LINE_SIZE as i32, 0)).is_none());
+        assert!(Cursor::checked((0, COLUMN_SIZE as i32)).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::eadk::Color;
+use crate::packed::PackedBoard;
+use crate::palette::Theme;
+use crate::viewport::Viewport;
+use crate::{draw_image, Board, BOARD_SIZE, COLUMN_SIZE, LINE_SIZE};
+
+/// Where a cell is alive, when comparing two snapshots
+#[derive(Clone, Copy, PartialEq)]
+pub enum CellDiff {
+    Neither,
+    OnlyA,
+    OnlyB,
+    Both,
+}
+
+/// Classify every cell between two snapshots
+pub fn diff_snapshots(a: &PackedBoard, b: &PackedBoard) -> Board<CellDiff> {
+    let mut diff = [[CellDiff::Neither; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    for (x, y) in a.live_cells() {
+        diff[x as usize][y as usize] = CellDiff::OnlyA;
+    }
+    for (x, y) in b.live_cells() {
+        let cell = &mut diff[x as usize][y as usize];
+        *cell = match cell {
+            CellDiff::OnlyA => CellDiff::Both,
+            _ => CellDiff::OnlyB,
+        };
+    }
+    diff
+}
+
+/// Share of the cells alive in either snapshot that are alive in both, in percent
+///
+/// Two empty snapshots are alike.
+pub fn likeness(diff: &Board<CellDiff>) -> u32 {
+    let (mut both, mut either) = (0u32, 0);
+    for &cell in diff.iter().flatten() {
+        match cell {
+            CellDiff::Neither => {}
+            CellDiff::Both => {
+                both += 1;
+                either += 1;
+            }
+            CellDiff::OnlyA | CellDiff::OnlyB => either += 1,
+        }
+    }
+    (both * 100).checked_div(either).unwrap_or(100)
+}
+
+/// Color a kind of cell is drawn with
+pub fn color(cell: CellDiff, theme: &Theme) -> Color {
+    match cell {
+        CellDiff::Neither => Color::WHITE,
+        CellDiff::OnlyA => theme.only_a,
+        CellDiff::OnlyB => theme.only_b,
+        CellDiff::Both => Color::BLACK,
+    }
+}
+
+/// Draw the classified cells over the whole board, a color for each kind
+pub fn draw_diff(diff: &Board<CellDiff>, theme: &Theme) {
+    let mut image = [Color::WHITE; BOARD_SIZE];
+    image.iter_mut().enumerate().for_each(|(i, pixel)| {
+        *pixel = color(diff[i % LINE_SIZE as usize][i / LINE_SIZE as usize], theme);
+    });
+    draw_image(&image, &Viewport::FULL);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packed(cells: &[(usize, usize)]) -> PackedBoard {
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        cells.iter().for_each(|&(x, y)| board[x][y] = true);
+        PackedBoard::pack(&board)
+    }
+
+    #[test]
+    fn cells_are_classified_by_the_snapshots_they_are_alive_in() {
+        // From a to b, (1, 1) and the corners survive, (5, 2) dies and (7, 3) is born
+        let corner = (LINE_SIZE as usize - 1, COLUMN_SIZE as usize - 1);
+        let a = packed(&[(0, 0), (1, 1), (5, 2), corner]);
+        let b = packed(&[(0, 0), (1, 1), (7, 3), corner]);
+        let diff = diff_snapshots(&a, &b);
+        for (x, column) in diff.iter().enumerate() {
+            for (y, &cell) in column.iter().enumerate() {
+                let expected = match (x, y) {
+                    (0, 0) | (1, 1) => CellDiff::Both,
+                    (5, 2) => CellDiff::OnlyA,
+                    (7, 3) => CellDiff::OnlyB,
+                    cell if cell == corner => CellDiff::Both,
+                    _ => CellDiff::Neither,
+                };
+                assert!(cell == expected, "({}, {})", x, y);
+            }
+        }
+        // 3 cells alive in both out of the 5 alive in either
+        assert_eq!(likeness(&diff), 60);
+        assert_eq!(likeness(&diff_snapshots(&b, &b)), 100);
+        assert_eq!(likeness(&diff_snapshots(&a, &packed(&[(9, 9)]))), 0);
+        assert_eq!(likeness(&diff_snapshots(&packed(&[]), &packed(&[]))), 100);
+    }
+}
@@ -20,7 +20,7 @@ pub const SCREEN_HEIGHT: u16 = 240;
 /// let color = Color { rgb565: 0b1111_1000_0000_0000 /* red */ };
 /// ```
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct Color {
     pub rgb565: u16,
 }
@@ -77,7 +77,7 @@ impl Color {
     /// use core::f32::consts::PI;
     /// use eadk::Color;
     ///
-    /// let color = Color::from_hsv(PI, 1., 1.); // green
+    /// let color = Color::from_hsv(PI, 1., 1.); // cyan
     /// ```
     #[must_use]
     pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
@@ -91,8 +91,394 @@ impl Color {
             (f(1.) * 255.) as u8,
         )
     }
+
+    /// Create color from an index of the ANSI 256 colors
+    ///
+    /// 0 to 15 are the 16 standard terminal colors, 16 to 231 the 6×6×6 color cube and
+    /// 232 to 255 a ramp of grays from dark to light, as in xterm.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::Color;
+    ///
+    /// let color = Color::from_ansi(196); // red
+    /// ```
+    #[must_use]
+    pub const fn from_ansi(code: u8) -> Self {
+        const STANDARD: [(u8, u8, u8); 16] = [
+            (0, 0, 0),
+            (128, 0, 0),
+            (0, 128, 0),
+            (128, 128, 0),
+            (0, 0, 128),
+            (128, 0, 128),
+            (0, 128, 128),
+            (192, 192, 192),
+            (128, 128, 128),
+            (255, 0, 0),
+            (0, 255, 0),
+            (255, 255, 0),
+            (0, 0, 255),
+            (255, 0, 255),
+            (0, 255, 255),
+            (255, 255, 255),
+        ];
+        const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+        match code {
+            0..=15 => {
+                let (r, g, b) = STANDARD[code as usize];
+                Self::from_rgb888(r, g, b)
+            }
+            16..=231 => {
+                let i = (code - 16) as usize;
+                Self::from_rgb888(
+                    CUBE_LEVELS[i / 36],
+                    CUBE_LEVELS[i / 6 % 6],
+                    CUBE_LEVELS[i % 6],
+                )
+            }
+            _ => {
+                let gray = 8 + 10 * (code - 232);
+                Self::from_rgb888(gray, gray, gray)
+            }
+        }
+    }
+
+    /// Color of `value` in an indexed palette, like [`INFERNO_PALETTE`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{Color, INFERNO_PALETTE};
+    ///
+    /// let color = Color::gradient_map(255, &INFERNO_PALETTE); // pale yellow
+    /// ```
+    #[must_use]
+    pub const fn gradient_map(value: u8, palette: &[Color; 256]) -> Color {
+        palette[value as usize]
+    }
+
+    /// Luminance of the color, from 0 for black to 255 for white
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::Color;
+    ///
+    /// let gray = Color::RED.grayscale(); // 76
+    /// ```
+    #[must_use]
+    pub const fn grayscale(self) -> u8 {
+        let r = (self.rgb565 >> 11) as u32 * 255 / 31;
+        let g = (self.rgb565 >> 5 & 0b11_1111) as u32 * 255 / 63;
+        let b = (self.rgb565 & 0b1_1111) as u32 * 255 / 31;
+        ((299 * r + 587 * g + 114 * b) / 1000) as u8
+    }
+
+    /// The opposite color, every bit flipped
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::Color;
+    ///
+    /// let color = Color::BLACK.invert(); // white
+    /// ```
+    #[must_use]
+    pub const fn invert(self) -> Self {
+        Self {
+            rgb565: !self.rgb565,
+        }
+    }
+
+    /// Blend of two colors, `amount` out of 255 being `other`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::Color;
+    ///
+    /// let color = Color::WHITE.mix(Color::RED, 128); // pink
+    /// ```
+    #[must_use]
+    pub const fn mix(self, other: Color, amount: u8) -> Self {
+        const fn channel(a: u16, b: u16, amount: u8, (shift, mask): (u16, u16)) -> u16 {
+            let (a, b) = ((a >> shift & mask) as u32, (b >> shift & mask) as u32);
+            (((a * (255 - amount as u32) + b * amount as u32) / 255) as u16) << shift
+        }
+        let (a, b) = (self.rgb565, other.rgb565);
+        Self {
+            rgb565: channel(a, b, amount, (11, 0b1_1111))
+                | channel(a, b, amount, (5, 0b11_1111))
+                | channel(a, b, amount, (0, 0b1_1111)),
+        }
+    }
 }
 
+/// The Inferno colormap, from black to purple, orange, yellow and almost white
+///
+/// Interpolated in rgb888 between eleven samples of the matplotlib colormap, then
+/// converted to rgb565.
+pub const INFERNO_PALETTE: [Color; 256] = [
+    Color::new(0x0000),
+    Color::new(0x0000),
+    Color::new(0x0001),
+    Color::new(0x0001),
+    Color::new(0x0001),
+    Color::new(0x0001),
+    Color::new(0x0002),
+    Color::new(0x0002),
+    Color::new(0x0002),
+    Color::new(0x0822),
+    Color::new(0x0823),
+    Color::new(0x0823),
+    Color::new(0x0823),
+    Color::new(0x0823),
+    Color::new(0x0824),
+    Color::new(0x0824),
+    Color::new(0x0824),
+    Color::new(0x0824),
+    Color::new(0x1045),
+    Color::new(0x1045),
+    Color::new(0x1045),
+    Color::new(0x1046),
+    Color::new(0x1046),
+    Color::new(0x1046),
+    Color::new(0x1046),
+    Color::new(0x1047),
+    Color::new(0x1047),
+    Color::new(0x1847),
+    Color::new(0x1847),
+    Color::new(0x1847),
+    Color::new(0x1848),
+    Color::new(0x1848),
+    Color::new(0x2048),
+    Color::new(0x2048),
+    Color::new(0x2049),
+    Color::new(0x2049),
+    Color::new(0x2849),
+    Color::new(0x2849),
+    Color::new(0x284A),
+    Color::new(0x284A),
+    Color::new(0x284A),
+    Color::new(0x304A),
+    Color::new(0x304A),
+    Color::new(0x304B),
+    Color::new(0x304B),
+    Color::new(0x384B),
+    Color::new(0x384B),
+    Color::new(0x384C),
+    Color::new(0x384C),
+    Color::new(0x384C),
+    Color::new(0x404C),
+    Color::new(0x404D),
+    Color::new(0x404D),
+    Color::new(0x404D),
+    Color::new(0x406D),
+    Color::new(0x486D),
+    Color::new(0x486D),
+    Color::new(0x486D),
+    Color::new(0x486D),
+    Color::new(0x486D),
+    Color::new(0x506D),
+    Color::new(0x506D),
+    Color::new(0x508D),
+    Color::new(0x508D),
+    Color::new(0x508D),
+    Color::new(0x588D),
+    Color::new(0x588D),
+    Color::new(0x588D),
+    Color::new(0x588D),
+    Color::new(0x588D),
+    Color::new(0x60AD),
+    Color::new(0x60AD),
+    Color::new(0x60AD),
+    Color::new(0x60AD),
+    Color::new(0x60AD),
+    Color::new(0x68AD),
+    Color::new(0x68AD),
+    Color::new(0x68AD),
+    Color::new(0x68CD),
+    Color::new(0x68CD),
+    Color::new(0x70CD),
+    Color::new(0x70CD),
+    Color::new(0x70CD),
+    Color::new(0x70CD),
+    Color::new(0x70CD),
+    Color::new(0x78ED),
+    Color::new(0x78ED),
+    Color::new(0x78ED),
+    Color::new(0x78ED),
+    Color::new(0x78ED),
+    Color::new(0x80ED),
+    Color::new(0x810D),
+    Color::new(0x810D),
+    Color::new(0x810D),
+    Color::new(0x810D),
+    Color::new(0x890D),
+    Color::new(0x890D),
+    Color::new(0x890D),
+    Color::new(0x892D),
+    Color::new(0x892D),
+    Color::new(0x912D),
+    Color::new(0x912C),
+    Color::new(0x912C),
+    Color::new(0x912C),
+    Color::new(0x912C),
+    Color::new(0x994C),
+    Color::new(0x994C),
+    Color::new(0x994C),
+    Color::new(0x994C),
+    Color::new(0x994C),
+    Color::new(0xA14C),
+    Color::new(0xA16C),
+    Color::new(0xA16C),
+    Color::new(0xA16B),
+    Color::new(0xA16B),
+    Color::new(0xA96B),
+    Color::new(0xA96B),
+    Color::new(0xA98B),
+    Color::new(0xA98B),
+    Color::new(0xA98B),
+    Color::new(0xB18B),
+    Color::new(0xB18B),
+    Color::new(0xB18B),
+    Color::new(0xB1AA),
+    Color::new(0xB1AA),
+    Color::new(0xB9AA),
+    Color::new(0xB9AA),
+    Color::new(0xB9AA),
+    Color::new(0xB9CA),
+    Color::new(0xB9CA),
+    Color::new(0xB9CA),
+    Color::new(0xC1CA),
+    Color::new(0xC1E9),
+    Color::new(0xC1E9),
+    Color::new(0xC1E9),
+    Color::new(0xC1E9),
+    Color::new(0xC209),
+    Color::new(0xCA09),
+    Color::new(0xCA09),
+    Color::new(0xCA09),
+    Color::new(0xCA28),
+    Color::new(0xCA28),
+    Color::new(0xCA28),
+    Color::new(0xD228),
+    Color::new(0xD248),
+    Color::new(0xD248),
+    Color::new(0xD248),
+    Color::new(0xD248),
+    Color::new(0xD267),
+    Color::new(0xDA67),
+    Color::new(0xDA67),
+    Color::new(0xDA67),
+    Color::new(0xDA87),
+    Color::new(0xDA87),
+    Color::new(0xDA87),
+    Color::new(0xDAA6),
+    Color::new(0xE2A6),
+    Color::new(0xE2A6),
+    Color::new(0xE2C6),
+    Color::new(0xE2C6),
+    Color::new(0xE2E6),
+    Color::new(0xE2E6),
+    Color::new(0xE2E5),
+    Color::new(0xE305),
+    Color::new(0xE305),
+    Color::new(0xE305),
+    Color::new(0xEB25),
+    Color::new(0xEB25),
+    Color::new(0xEB44),
+    Color::new(0xEB44),
+    Color::new(0xEB44),
+    Color::new(0xEB64),
+    Color::new(0xEB64),
+    Color::new(0xEB84),
+    Color::new(0xEB83),
+    Color::new(0xF383),
+    Color::new(0xF3A3),
+    Color::new(0xF3A3),
+    Color::new(0xF3A3),
+    Color::new(0xF3C3),
+    Color::new(0xF3C3),
+    Color::new(0xF3E3),
+    Color::new(0xF3E2),
+    Color::new(0xF402),
+    Color::new(0xF402),
+    Color::new(0xF402),
+    Color::new(0xF422),
+    Color::new(0xF422),
+    Color::new(0xF442),
+    Color::new(0xF442),
+    Color::new(0xF462),
+    Color::new(0xF462),
+    Color::new(0xFC82),
+    Color::new(0xFC82),
+    Color::new(0xFC82),
+    Color::new(0xFCA1),
+    Color::new(0xFCA1),
+    Color::new(0xFCC1),
+    Color::new(0xFCC1),
+    Color::new(0xFCE1),
+    Color::new(0xFCE1),
+    Color::new(0xFD01),
+    Color::new(0xFD01),
+    Color::new(0xFD01),
+    Color::new(0xFD21),
+    Color::new(0xFD21),
+    Color::new(0xFD41),
+    Color::new(0xFD42),
+    Color::new(0xFD62),
+    Color::new(0xFD62),
+    Color::new(0xFD83),
+    Color::new(0xFD83),
+    Color::new(0xFDA3),
+    Color::new(0xFDA3),
+    Color::new(0xFDC4),
+    Color::new(0xFDC4),
+    Color::new(0xFDE4),
+    Color::new(0xFDE5),
+    Color::new(0xFE05),
+    Color::new(0xFE05),
+    Color::new(0xFE26),
+    Color::new(0xFE26),
+    Color::new(0xFE46),
+    Color::new(0xFE46),
+    Color::new(0xF667),
+    Color::new(0xF667),
+    Color::new(0xF687),
+    Color::new(0xF688),
+    Color::new(0xF6A8),
+    Color::new(0xF6A8),
+    Color::new(0xF6C9),
+    Color::new(0xF6C9),
+    Color::new(0xF6C9),
+    Color::new(0xF6EA),
+    Color::new(0xF6EA),
+    Color::new(0xF70B),
+    Color::new(0xFF0B),
+    Color::new(0xFF0C),
+    Color::new(0xFF2C),
+    Color::new(0xFF2D),
+    Color::new(0xFF2D),
+    Color::new(0xFF4E),
+    Color::new(0xFF4E),
+    Color::new(0xFF6F),
+    Color::new(0xFF6F),
+    Color::new(0xFF6F),
+    Color::new(0xFF90),
+    Color::new(0xFF90),
+    Color::new(0xFFB1),
+    Color::new(0xFFB1),
+    Color::new(0xFFB2),
+    Color::new(0xFFD2),
+    Color::new(0xFFD3),
+    Color::new(0xFFF3),
+    Color::new(0xFFF4),
+    Color::new(0xFFF4),
+];
+
 /// A rectangle on the screen
 ///
 /// # Example
@@ -137,6 +523,52 @@ impl Rect {
             height,
         }
     }
+
+    /// Create a rectangle centered on a point, `None` if it would cross the top or left edge
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{display, Rect, Color, SCREEN_WIDTH, SCREEN_HEIGHT};
+    ///
+    /// if let Some(rect) = Rect::from_center(SCREEN_WIDTH / 2, SCREEN_HEIGHT / 2, 20, 20) {
+    ///     display::push_rect_uniform(rect, Color::RED); // In the middle of the screen
+    /// }
+    /// ```
+    pub const fn from_center(cx: u16, cy: u16, width: u16, height: u16) -> Option<Self> {
+        match (cx.checked_sub(width / 2), cy.checked_sub(height / 2)) {
+            (Some(x), Some(y)) => Some(Self::new(x, y, width, height)),
+            _ => None,
+        }
+    }
+
+    /// The part of the rectangle on the screen, `None` if none of it is
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{Rect, SCREEN_WIDTH};
+    ///
+    /// let visible = Rect::new(SCREEN_WIDTH - 10, 0, 20, 20).on_screen(); // 10 pixels wide
+    /// ```
+    #[must_use]
+    pub const fn on_screen(self) -> Option<Self> {
+        if self.x >= SCREEN_WIDTH || self.y >= SCREEN_HEIGHT || self.width == 0 || self.height == 0
+        {
+            return None;
+        }
+        let width = if self.width > SCREEN_WIDTH - self.x {
+            SCREEN_WIDTH - self.x
+        } else {
+            self.width
+        };
+        let height = if self.height > SCREEN_HEIGHT - self.y {
+            SCREEN_HEIGHT - self.y
+        } else {
+            self.height
+        };
+        Some(Self::new(self.x, self.y, width, height))
+    }
 }
 
 /// A point on the screen
@@ -191,9 +623,13 @@ impl Point {
 /// let keyboard_state: State = keyboard::scan();
 /// ```
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct State(u64);
 
 impl State {
+    /// No key down
+    pub const NONE: State = State(0);
+
     #[must_use]
     fn new(state: u64) -> Self {
         Self(state)
@@ -209,14 +645,60 @@ impl State {
     /// use eadk::{keyboard, key};
     ///
     /// let keyboard_state = keyboard::scan();
-    /// if keyboard_state.key_down(key::EXE) {
-    ///     todo!();
-    /// }
+    /// let running = keyboard_state.key_down(key::EXE);
     /// ```
     #[must_use]
     pub fn key_down(&self, k: u32) -> bool {
         self.0.wrapping_shr(k) & 1 != 0
     }
+
+    /// Keys down in this state but not in a previous one
+    ///
+    /// Usefull to react once to a key press instead of on every scan while it's held.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{keyboard, key};
+    ///
+    /// let previous = keyboard::scan();
+    /// let keyboard_state = keyboard::scan();
+    /// let mut presses = 0;
+    /// if keyboard_state.pressed_since(&previous).key_down(key::EXE) {
+    ///     presses += 1;
+    /// }
+    /// ```
+    #[must_use]
+    pub fn pressed_since(&self, previous: &State) -> State {
+        Self(self.0 & !previous.0)
+    }
+
+    /// Whether any key is down
+    #[must_use]
+    pub fn any_down(&self) -> bool {
+        self.0 != 0
+    }
+
+    /// The same state with a key up
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{keyboard, key};
+    ///
+    /// // Whatever SHIFT is doing, only the other keys matter here
+    /// let keyboard_state = keyboard::scan().without(key::SHIFT);
+    /// ```
+    #[must_use]
+    pub fn without(&self, k: u32) -> State {
+        Self(self.0 & !(1u64.wrapping_shl(k)))
+    }
+
+    /// The same state with a key down
+    #[must_use]
+    pub fn with(&self, k: u32) -> State {
+        Self(self.0 | 1u64.wrapping_shl(k))
+    }
 }
 
 /// Key constants
@@ -334,13 +816,20 @@ pub mod backlight {
 /// display::push_rect_uniform(Rect::SCREEN, Color::BLUE); // Fill the screen in blue
 /// ```
 pub mod display {
+    use super::timing;
     use super::Color;
     use super::Point;
     use super::Rect;
+    use super::{SCREEN_HEIGHT, SCREEN_WIDTH};
 
     /// Push a frame rect to the frame buffer
     ///
-    /// For a [rect](Rect), push an array of pixel colors.
+    /// For a [rect](Rect), push an array of pixel colors, row by row. The part of the rect
+    /// off the screen is left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer pixels than in the rect.
     ///
     /// # Example
     ///
@@ -354,11 +843,67 @@ pub mod display {
     /// );
     /// ```
     pub fn push_rect(rect: Rect, pixels: &[Color]) {
+        // The system reads as many pixels as the rect holds, whatever the slice
+        assert!(
+            pixels.len() >= rect.width as usize * rect.height as usize,
+            "Fewer pixels than in the rect"
+        );
+        let visible = match rect.on_screen() {
+            Some(visible) => visible,
+            None => return,
+        };
+        if visible.width == rect.width {
+            // Only the bottom rows are cut, the visible ones are the first pixels
+            push_visible_rect(visible, pixels);
+        } else {
+            for dy in 0..visible.height {
+                let row = dy as usize * rect.width as usize;
+                push_visible_rect(
+                    Rect::new(visible.x, visible.y + dy, visible.width, 1),
+                    &pixels[row..],
+                );
+            }
+        }
+    }
+
+    /// Push the pixels of a rect within the screen, with enough of them
+    fn push_visible_rect(rect: Rect, pixels: &[Color]) {
+        #[cfg(feature = "shadow_fb")]
+        shadow::store(rect, |i| pixels[i]);
         unsafe {
             eadk_display_push_rect(rect, pixels.as_ptr());
         }
     }
 
+    /// Push a row of pixels to the frame buffer
+    ///
+    /// Push `pixels` as a 1 pixel tall rect starting at (`x`, `y`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row goes past the right edge of the screen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{display, Color};
+    ///
+    /// // Set colors of the 3 pixels from x = 10 on the line y = 20
+    /// display::push_row(20, 10, &[Color::RED, Color::GREEN, Color::BLUE]);
+    /// ```
+    pub fn push_row(y: u16, x: u16, pixels: &[Color]) {
+        push_rect(row_rect(y, x, pixels.len()), pixels);
+    }
+
+    /// The 1 pixel tall rect of `width` pixels from (`x`, `y`), within the screen
+    pub(super) fn row_rect(y: u16, x: u16, width: usize) -> Rect {
+        assert!(
+            x as usize + width <= SCREEN_WIDTH as usize,
+            "Row doesn't fit in the screen"
+        );
+        Rect::new(x, y, width as u16, 1)
+    }
+
     /// Set the color of all the pixels in a rect
     ///
     /// # Example
@@ -366,14 +911,130 @@ pub mod display {
     /// ```
     /// use eadk::{display, Rect, Color};
     ///
-    /// display::push_rect(Rect::new(0, 0, 100, 100), Color::BLACK);
+    /// display::push_rect_uniform(Rect::new(0, 0, 100, 100), Color::BLACK);
     /// ```
     pub fn push_rect_uniform(rect: Rect, color: Color) {
+        let rect = match rect.on_screen() {
+            Some(visible) => visible,
+            None => return,
+        };
+        #[cfg(feature = "shadow_fb")]
+        shadow::store(rect, |_| color);
         unsafe {
             eadk_display_push_rect_uniform(rect, color);
         }
     }
 
+    /// Set the color of a single pixel
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{display, Color};
+    ///
+    /// display::push_pixel(10, 20, Color::RED);
+    /// ```
+    pub fn push_pixel(x: u16, y: u16, color: Color) {
+        push_rect_uniform(Rect::new(x, y, 1, 1), color);
+    }
+
+    /// Draw a one pixel tall line of `width` pixels, starting at (`x`, `y`)
+    pub fn draw_hline(x: u16, y: u16, width: u16, color: Color) {
+        push_rect_uniform(Rect::new(x, y, width, 1), color);
+    }
+
+    /// Draw a one pixel wide line of `height` pixels, starting at (`x`, `y`)
+    pub fn draw_vline(x: u16, y: u16, height: u16, color: Color) {
+        push_rect_uniform(Rect::new(x, y, 1, height), color);
+    }
+
+    /// Draw the lines of a grid of `line_size` by `column_size` cells of `cell_size` pixels,
+    /// from the top left corner of the screen
+    ///
+    /// Each cell gets a line along its top and left sides, so a cell drawn without its first
+    /// row and column of pixels leaves the grid as it is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{display, Color};
+    ///
+    /// display::draw_grid(16, 20, 15, Color::from_rgb888(200, 200, 200));
+    /// ```
+    pub fn draw_grid(cell_size: u16, line_size: u16, column_size: u16, color: Color) {
+        let (width, height) = (line_size * cell_size, column_size * cell_size);
+        for x in 0..line_size {
+            draw_vline(x * cell_size, 0, height, color);
+        }
+        for y in 0..column_size {
+            draw_hline(0, y * cell_size, width, color);
+        }
+    }
+
+    /// Draw a line across the whole screen in each direction, crossing at `pos`
+    ///
+    /// The crossing pixel gets the [inverted](Color::invert) color, so the exact point stays
+    /// visible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{display, Color, Point};
+    ///
+    /// display::draw_crosshair(Point::new(160, 120), Color::RED);
+    /// ```
+    pub fn draw_crosshair(pos: Point, color: Color) {
+        draw_hline(0, pos.y, SCREEN_WIDTH, color);
+        draw_vline(pos.x, 0, SCREEN_HEIGHT, color);
+        push_pixel(pos.x, pos.y, color.invert());
+    }
+
+    /// Invert the colors of all the pixels in a rect
+    ///
+    /// The screen can't be read back, so the colors come from the shadow frame buffer,
+    /// which needs the `shadow_fb` feature. Inverting twice gives back the original colors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{display, Rect};
+    ///
+    /// display::push_rect_xor(Rect::new(10, 10, 4, 4)); // Visible on any background
+    /// ```
+    #[cfg(feature = "shadow_fb")]
+    pub fn push_rect_xor(rect: Rect) {
+        // Like pushed rects, only the part on the screen is inverted
+        let rect = match rect.on_screen() {
+            Some(visible) => visible,
+            None => return,
+        };
+        let mut pixels = [Color::BLACK; SCREEN_WIDTH as usize];
+        for y in rect.y..rect.y + rect.height {
+            let row = Rect::new(rect.x, y, rect.width, 1);
+            for (i, pixel) in pixels[..rect.width as usize].iter_mut().enumerate() {
+                *pixel = shadow::load(rect.x + i as u16, y).invert();
+            }
+            push_rect(row, &pixels[..rect.width as usize]);
+        }
+    }
+
+    /// Color of a pixel of the screen, as last pushed
+    ///
+    /// Strings aren't kept in memory, so the pixels under them are what was there before.
+    #[cfg(feature = "shadow_fb")]
+    pub fn pixel(x: u16, y: u16) -> Color {
+        shadow::load(x, y)
+    }
+
+    /// Whether every pixel of a rect is of a color, as last pushed
+    ///
+    /// Like with [`pixel`], strings aren't seen, and pixels outside of the screen are black.
+    #[cfg(feature = "shadow_fb")]
+    pub fn region_matches_color(rect: Rect, color: Color) -> bool {
+        (rect.y..rect.y + rect.height)
+            .all(|y| (rect.x..rect.x + rect.width).all(|x| shadow::load(x, y) == color))
+    }
+
     /// Draw a string on the screen
     ///
     /// *The string must end with the '\0' character*
@@ -397,6 +1058,106 @@ pub mod display {
         }
     }
 
+    /// Draw a single ASCII character on the screen, nothing if it isn't ASCII
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::{display, Point, Color};
+    ///
+    /// display::draw_char(b'7', Point::ZERO, false, Color::BLACK, Color::WHITE);
+    /// ```
+    pub fn draw_char(c: u8, pos: Point, large: bool, text_color: Color, background_color: Color) {
+        let buffer = [c, b'\0'];
+        if let Ok(string) = core::str::from_utf8(&buffer) {
+            draw_string(string, pos, large, text_color, background_color);
+        }
+    }
+
+    /// Characters of a [Spinner], one per frame
+    const SPINNER_FRAMES: [u8; 4] = [b'|', b'/', b'-', b'\\'];
+    /// Time each frame of a [Spinner] is shown, in milliseconds
+    const SPINNER_FRAME_MS: u64 = 50;
+
+    /// A rotating character, to show a long computation is still going
+    ///
+    /// It's drawn in the large font, black on white.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::eadk::{display::Spinner, Point};
+    ///
+    /// let mut spinner = Spinner::new(Point::new(300, 10));
+    /// let mut sum: u64 = 0;
+    /// for n in 0..10_000_000u64 {
+    ///     sum = sum.wrapping_add(n * n);
+    ///     if n % 4096 == 0 {
+    ///         spinner.tick(); // Turns every 50ms
+    ///     }
+    /// }
+    /// ```
+    pub struct Spinner {
+        frame: u8,
+        pos: Point,
+        /// When the next frame is due
+        next_ms: u64,
+    }
+
+    impl Spinner {
+        pub fn new(pos: Point) -> Self {
+            Self {
+                frame: 0,
+                pos,
+                next_ms: timing::millis(),
+            }
+        }
+
+        /// Draw the next frame, if the current one was shown long enough
+        pub fn tick(&mut self) {
+            let now = timing::millis();
+            if now < self.next_ms {
+                return;
+            }
+            self.next_ms = now + SPINNER_FRAME_MS;
+            let c = SPINNER_FRAMES[self.frame as usize];
+            draw_char(c, self.pos, true, Color::BLACK, Color::WHITE);
+            self.frame = (self.frame + 1) % SPINNER_FRAMES.len() as u8;
+        }
+
+        /// Erase the spinner, once the computation is over
+        pub fn clear(&self) {
+            draw_char(b' ', self.pos, true, Color::BLACK, Color::WHITE);
+        }
+    }
+
+    /// Width and height in pixel of a character, in the large or small font
+    ///
+    /// Both fonts are monospaced.
+    #[must_use]
+    pub const fn glyph_size(large: bool) -> (u16, u16) {
+        if large {
+            (10, 18)
+        } else {
+            (7, 14)
+        }
+    }
+
+    /// Width in pixel of a string drawn with [draw_string], up to its '\0' character
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::display;
+    ///
+    /// let width = display::text_width("Hello\0", true); // 50
+    /// ```
+    #[must_use]
+    pub fn text_width(string: &str, large: bool) -> u16 {
+        let chars = string.chars().take_while(|&c| c != '\0').count() as u16;
+        chars * glyph_size(large).0
+    }
+
     /// Wait for vertical blanking
     ///
     /// Usefull when you draw on screen in loops to avoid screen blinking.
@@ -419,6 +1180,116 @@ pub mod display {
         }
     }
 
+    /// Direction in which a [wipe](transition_wipe) moves
+    #[derive(Clone, Copy)]
+    pub enum WipeDirection {
+        Left,
+        Right,
+        Up,
+        Down,
+    }
+
+    /// Reveal a new screen with a wipe
+    ///
+    /// The screen is first covered with the `from` color, then `to_fn` draws the new screen
+    /// in the strip revealed by each frame of the wipe, so each part is drawn once.
+    /// The wipe is paced with [`timing::millis`] to last `duration_ms`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::eadk::{display, display::WipeDirection, Color};
+    ///
+    /// display::transition_wipe(
+    ///     Color::WHITE,
+    ///     |strip| display::push_rect_uniform(strip, Color::BLUE),
+    ///     WipeDirection::Down,
+    ///     300,
+    /// );
+    /// ```
+    pub fn transition_wipe(
+        from: Color,
+        to_fn: impl Fn(Rect),
+        direction: WipeDirection,
+        duration_ms: u32,
+    ) {
+        push_rect_uniform(Rect::SCREEN, from);
+        let start = timing::millis();
+        let (mut width, mut height) = (0, 0);
+        loop {
+            let elapsed = (timing::millis() - start).min(duration_ms as u64) as u32;
+            let revealed = |size: u16| {
+                (size as u32 * elapsed)
+                    .checked_div(duration_ms)
+                    .unwrap_or(size as u32) as u16
+            };
+            let (revealed_width, revealed_height) =
+                (revealed(SCREEN_WIDTH), revealed(SCREEN_HEIGHT));
+            let strip = match direction {
+                WipeDirection::Left => Rect::new(
+                    SCREEN_WIDTH - revealed_width,
+                    0,
+                    revealed_width - width,
+                    SCREEN_HEIGHT,
+                ),
+                WipeDirection::Right => Rect::new(width, 0, revealed_width - width, SCREEN_HEIGHT),
+                WipeDirection::Up => Rect::new(
+                    0,
+                    SCREEN_HEIGHT - revealed_height,
+                    SCREEN_WIDTH,
+                    revealed_height - height,
+                ),
+                WipeDirection::Down => Rect::new(0, height, SCREEN_WIDTH, revealed_height - height),
+            };
+            (width, height) = (revealed_width, revealed_height);
+
+            wait_for_vblank();
+            if strip.width > 0 && strip.height > 0 {
+                to_fn(strip);
+            }
+            if elapsed >= duration_ms {
+                break;
+            }
+        }
+    }
+
+    /// Copy of the screen kept in memory, as the screen can't be read back
+    ///
+    /// It follows the rects pushed, but not the strings drawn.
+    #[cfg(feature = "shadow_fb")]
+    mod shadow {
+        use super::super::{Color, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+        const WIDTH: usize = SCREEN_WIDTH as usize;
+        const HEIGHT: usize = SCREEN_HEIGHT as usize;
+
+        static mut PIXELS: [Color; WIDTH * HEIGHT] = [Color::BLACK; WIDTH * HEIGHT];
+
+        /// Store the pixels of a rect, `color` giving the nth pixel in row-major order
+        pub fn store(rect: Rect, color: impl Fn(usize) -> Color) {
+            let width = (rect.width as usize).min(WIDTH.saturating_sub(rect.x as usize));
+            let height = (rect.height as usize).min(HEIGHT.saturating_sub(rect.y as usize));
+            for dy in 0..height {
+                for dx in 0..width {
+                    let index = (rect.y as usize + dy) * WIDTH + rect.x as usize + dx;
+                    // The app is single threaded, nothing else accesses the buffer meanwhile
+                    unsafe {
+                        *core::ptr::addr_of_mut!(PIXELS[index]) =
+                            color(dy * rect.width as usize + dx);
+                    }
+                }
+            }
+        }
+
+        /// Color of a pixel, black outside of the screen
+        pub fn load(x: u16, y: u16) -> Color {
+            if x as usize >= WIDTH || y as usize >= HEIGHT {
+                return Color::BLACK;
+            }
+            unsafe { *core::ptr::addr_of!(PIXELS[y as usize * WIDTH + x as usize]) }
+        }
+    }
+
     extern "C" {
         fn eadk_display_push_rect_uniform(rect: Rect, color: Color);
         fn eadk_display_push_rect(rect: Rect, color: *const Color);
@@ -443,6 +1314,7 @@ pub mod display {
 /// let keyboard_state = keyboard::scan();
 /// ```
 pub mod keyboard {
+    use super::key;
     use super::State;
 
     /// Scan the keyboard state
@@ -461,6 +1333,129 @@ pub mod keyboard {
         unsafe { State::new(eadk_keyboard_scan()) }
     }
 
+    /// Digit keys, in the order of the digit they type
+    const DIGIT_KEYS: [u32; 10] = [
+        key::ZERO,
+        key::ONE,
+        key::TWO,
+        key::THREE,
+        key::FOUR,
+        key::FIVE,
+        key::SIX,
+        key::SEVEN,
+        key::EIGHT,
+        key::NINE,
+    ];
+
+    /// Numeric input typed with the digit keys
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eadk::keyboard::{self, TextScanner};
+    ///
+    /// let mut scanner = TextScanner::new();
+    /// let mut last = keyboard::scan();
+    /// loop {
+    ///     let state = keyboard::scan();
+    ///     scanner.push_key(&state.pressed_since(&last));
+    ///     last = state;
+    ///     let value: Option<u32> = scanner.value_u32();
+    /// }
+    /// ```
+    #[derive(Clone, Copy)]
+    pub struct TextScanner {
+        buffer: [u8; 16],
+        len: usize,
+        /// The whole text goes away with the next key typed
+        selected: bool,
+    }
+
+    impl TextScanner {
+        pub const fn new() -> Self {
+            Self {
+                buffer: [0; 16],
+                len: 0,
+                selected: false,
+            }
+        }
+
+        /// Type the digit pressed in `state`, or remove the last one on BACKSPACE
+        ///
+        /// `state` should only have the keys just pressed, see [`State::pressed_since`].
+        /// Returns whether a digit was added, which isn't the case once the buffer is full.
+        pub fn push_key(&mut self, state: &State) -> bool {
+            let digit = DIGIT_KEYS.iter().position(|&k| state.key_down(k));
+            if self.selected && (digit.is_some() || state.key_down(key::BACKSPACE)) {
+                self.selected = false;
+                self.len = 0;
+            }
+            if state.key_down(key::BACKSPACE) {
+                self.len = self.len.saturating_sub(1);
+                return false;
+            }
+            match digit {
+                Some(digit) if self.len < self.buffer.len() => {
+                    self.buffer[self.len] = b'0' + digit as u8;
+                    self.len += 1;
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        /// The digits typed so far
+        pub fn as_str(&self) -> &str {
+            // Only ASCII digits are ever pushed
+            core::str::from_utf8(&self.buffer[..self.len]).unwrap_or("")
+        }
+
+        /// Parse the digits as a decimal integer, `None` when empty or too large
+        pub fn value_u32(&self) -> Option<u32> {
+            self.as_str().parse().ok()
+        }
+
+        pub fn clear(&mut self) {
+            self.len = 0;
+            self.selected = false;
+        }
+
+        /// Replace the text with a number, selected so typing starts a new one
+        pub fn set_u32(&mut self, value: u32) {
+            let mut digits = [0; 10];
+            let mut count = 0;
+            let mut rest = value;
+            loop {
+                digits[count] = b'0' + (rest % 10) as u8;
+                count += 1;
+                rest /= 10;
+                if rest == 0 {
+                    break;
+                }
+            }
+            for (byte, &digit) in self.buffer.iter_mut().zip(digits[..count].iter().rev()) {
+                *byte = digit;
+            }
+            self.len = count;
+            self.selected = true;
+        }
+
+        /// Select the whole text, so typing starts a new one
+        pub fn select_all(&mut self) {
+            self.selected = self.len > 0;
+        }
+
+        pub fn is_selected(&self) -> bool {
+            self.selected
+        }
+    }
+
+    impl Default for TextScanner {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
     extern "C" {
         fn eadk_keyboard_scan() -> u64;
     }
@@ -523,6 +1518,97 @@ pub mod timing {
         }
     }
 
+    /// Periodic work, done on one call out of a few instead of on every one
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::eadk::timing::PeriodicUpdate;
+    /// use crate::eadk::{display, Color, Rect};
+    ///
+    /// let mut refresh = PeriodicUpdate::new(8);
+    /// let mut frames: u16 = 0;
+    /// loop {
+    ///     frames = frames.wrapping_add(1);
+    ///     if refresh.tick() {
+    ///         // Every 8 frames
+    ///         display::push_rect_uniform(Rect::new(0, 0, frames % 320, 4), Color::RED);
+    ///     }
+    ///     display::wait_for_vblank();
+    /// }
+    /// ```
+    pub struct PeriodicUpdate {
+        interval: u32,
+        counter: u32,
+    }
+
+    impl PeriodicUpdate {
+        /// Tick `true` every `every_n_frames` calls, every call if 0
+        pub const fn new(every_n_frames: u32) -> Self {
+            Self {
+                interval: if every_n_frames == 0 {
+                    1
+                } else {
+                    every_n_frames
+                },
+                counter: 0,
+            }
+        }
+
+        /// Count a call, returns whether it's time for the work
+        pub fn tick(&mut self) -> bool {
+            self.counter += 1;
+            if self.counter < self.interval {
+                return false;
+            }
+            self.counter = 0;
+            true
+        }
+    }
+
+    /// Work done once, on the first check after a delay
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use crate::eadk::timing::ScheduledAction;
+    /// use crate::eadk::{display, Color, Point};
+    ///
+    /// let mut hint = ScheduledAction::schedule(3000);
+    /// loop {
+    ///     // Once, 3s from now
+    ///     hint.poll(|| {
+    ///         let at = Point::new(10, 10);
+    ///         display::draw_string("Press EXE\0", at, false, Color::BLACK, Color::WHITE);
+    ///     });
+    ///     display::wait_for_vblank();
+    /// }
+    /// ```
+    pub struct ScheduledAction {
+        fire_at_ms: u64,
+        fired: bool,
+    }
+
+    impl ScheduledAction {
+        /// Fire `delay_ms` from now
+        pub fn schedule(delay_ms: u64) -> Self {
+            Self {
+                fire_at_ms: millis() + delay_ms,
+                fired: false,
+            }
+        }
+
+        /// Call `action` if it's time and it wasn't called yet, returns whether it was
+        pub fn poll<F: FnOnce()>(&mut self, action: F) -> bool {
+            if self.fired || millis() < self.fire_at_ms {
+                return false;
+            }
+            self.fired = true;
+            action();
+            true
+        }
+    }
+
     extern "C" {
         fn eadk_timing_usleep(us: u32);
         fn eadk_timing_msleep(us: u32);
@@ -530,6 +1616,61 @@ pub mod timing {
     }
 }
 
+/// Colors changing over time, to animate what's drawn
+///
+/// # Example
+///
+/// ```
+/// use eadk::{color_cycle, display, Color, Point};
+///
+/// // Redrawn every frame, the text changes color with time
+/// let color = color_cycle::TRAFFIC_LIGHT.current();
+/// display::draw_string("REC\0", Point::new(10, 10), false, color, Color::BLACK);
+/// ```
+pub mod color_cycle {
+    use super::{timing, Color};
+
+    /// Colors shown one after the other, looping
+    pub struct ColorCycle {
+        colors: &'static [Color],
+        /// Time to go through all the colors, in milliseconds
+        period_ms: u32,
+    }
+
+    impl ColorCycle {
+        /// Color shown at the current time
+        #[must_use]
+        pub fn current(&self) -> Color {
+            let color_ms = (self.period_ms / self.colors.len() as u32).max(1);
+            self.colors[(timing::millis() / color_ms as u64) as usize % self.colors.len()]
+        }
+    }
+
+    /// Red, yellow then green, each for half a second
+    pub const TRAFFIC_LIGHT: ColorCycle = ColorCycle {
+        colors: &[
+            Color::from_ansi(196),
+            Color::from_ansi(226),
+            Color::from_ansi(46),
+        ],
+        period_ms: 1500,
+    };
+
+    /// The colors of the rainbow, from red to violet
+    pub const RAINBOW: ColorCycle = ColorCycle {
+        colors: &[
+            Color::from_ansi(196),
+            Color::from_ansi(208),
+            Color::from_ansi(226),
+            Color::from_ansi(46),
+            Color::from_ansi(51),
+            Color::from_ansi(21),
+            Color::from_ansi(129),
+        ],
+        period_ms: 1400,
+    };
+}
+
 /// Get a random u32
 ///
 /// Get a random number in [0; 4_294_967_295].
@@ -556,8 +1697,36 @@ extern "C" {
     fn eadk_random() -> u32;
 }
 
+/// Get the external data
+///
+/// External data is a file installed along with the app, it's empty when there is none.
+///
+/// # Example
+///
+/// ```
+/// use eadk::external_data;
+///
+/// let data: &[u8] = external_data();
+/// ```
+pub fn external_data() -> &'static [u8] {
+    unsafe {
+        if eadk_external_data_size == 0 {
+            &[]
+        } else {
+            core::slice::from_raw_parts(eadk_external_data, eadk_external_data_size)
+        }
+    }
+}
+
+extern "C" {
+    static eadk_external_data: *const u8;
+    static eadk_external_data_size: usize;
+}
+
+#[cfg(not(test))]
 use core::panic::PanicInfo;
 
+#[cfg(not(test))]
 #[panic_handler]
 fn panic(_panic: &PanicInfo<'_>) -> ! {
     display::push_rect_uniform(Rect::SCREEN, Color::RED);
@@ -571,3 +1740,130 @@ fn panic(_panic: &PanicInfo<'_>) -> ! {
 
     loop {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hues_go_around_the_primaries() {
+        assert_eq!(Color::from_hsv(0., 1., 1.).rgb565, Color::RED.rgb565);
+        assert_eq!(
+            Color::from_hsv(2. * PI / 3., 1., 1.).rgb565,
+            Color::GREEN.rgb565
+        );
+        assert_eq!(
+            Color::from_hsv(4. * PI / 3., 1., 1.).rgb565,
+            Color::BLUE.rgb565
+        );
+        assert_eq!(
+            Color::from_hsv(PI, 1., 1.).rgb565,
+            Color::from_rgb888(0, 255, 255).rgb565
+        );
+    }
+
+    #[test]
+    fn unsaturated_colors_are_grays() {
+        assert_eq!(Color::from_hsv(1., 0., 1.).rgb565, Color::WHITE.rgb565);
+        assert_eq!(Color::from_hsv(1., 0., 0.).rgb565, Color::BLACK.rgb565);
+        assert_eq!(
+            Color::from_hsv(4., 0., 0.5).rgb565,
+            Color::from_rgb888(127, 127, 127).rgb565
+        );
+    }
+
+    #[test]
+    fn numbers_set_in_a_text_scanner_read_back() {
+        let mut scanner = keyboard::TextScanner::new();
+        assert_eq!(scanner.value_u32(), None);
+        for value in [0, 7, 100_000, u32::MAX] {
+            scanner.set_u32(value);
+            assert_eq!(scanner.value_u32(), Some(value));
+            assert!(scanner.is_selected());
+        }
+    }
+
+    #[test]
+    fn typing_replaces_a_number_set_and_a_copy_reverts_it() {
+        let mut scanner = keyboard::TextScanner::new();
+        scanner.set_u32(250);
+        let offered = scanner;
+        scanner.push_key(&State::NONE.with(key::FOUR));
+        scanner.push_key(&State::NONE.with(key::TWO));
+        assert_eq!(scanner.value_u32(), Some(42));
+        scanner = offered;
+        assert_eq!(scanner.value_u32(), Some(250));
+        // Erasing a selected number erases all of it
+        scanner.push_key(&State::NONE.with(key::BACKSPACE));
+        assert_eq!(scanner.value_u32(), None);
+    }
+
+    #[test]
+    fn rects_are_clipped_to_the_screen() {
+        let clipped = |rect: Rect| rect.on_screen().map(|r| (r.x, r.y, r.width, r.height));
+        assert_eq!(clipped(Rect::new(10, 20, 30, 40)), Some((10, 20, 30, 40)));
+        assert_eq!(
+            clipped(Rect::new(SCREEN_WIDTH - 10, SCREEN_HEIGHT - 5, 20, 20)),
+            Some((SCREEN_WIDTH - 10, SCREEN_HEIGHT - 5, 10, 5))
+        );
+        assert_eq!(
+            clipped(Rect::new(0, 0, u16::MAX, u16::MAX)),
+            Some((0, 0, SCREEN_WIDTH, SCREEN_HEIGHT))
+        );
+        assert_eq!(clipped(Rect::new(SCREEN_WIDTH, 0, 10, 10)), None);
+        assert_eq!(clipped(Rect::new(0, SCREEN_HEIGHT, 10, 10)), None);
+        assert_eq!(clipped(Rect::new(5, 5, 0, 10)), None);
+        assert_eq!(clipped(Rect::new(5, 5, 10, 0)), None);
+    }
+
+    // Stand in for the system on the host, where nothing is drawn
+
+    #[no_mangle]
+    extern "C" fn eadk_display_push_rect(_rect: Rect, _pixels: *const Color) {}
+
+    #[no_mangle]
+    extern "C" fn eadk_display_push_rect_uniform(_rect: Rect, _color: Color) {}
+
+    #[test]
+    fn rows_are_one_pixel_tall_rects() {
+        let rect = display::row_rect(20, 10, 3);
+        assert_eq!((rect.x, rect.y, rect.width, rect.height), (10, 20, 3, 1));
+        let rect = display::row_rect(SCREEN_HEIGHT - 1, 0, SCREEN_WIDTH as usize);
+        assert_eq!((rect.x, rect.width), (0, SCREEN_WIDTH));
+        let rect = display::row_rect(0, SCREEN_WIDTH - 1, 1);
+        assert_eq!((rect.x, rect.width), (SCREEN_WIDTH - 1, 1));
+        display::push_row(0, 10, &[Color::BLACK; SCREEN_WIDTH as usize - 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Row doesn't fit in the screen")]
+    fn rows_wider_than_the_screen_are_refused() {
+        display::push_row(0, 0, &[Color::BLACK; SCREEN_WIDTH as usize + 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Row doesn't fit in the screen")]
+    fn rows_going_past_the_right_edge_are_refused() {
+        display::push_row(0, 10, &[Color::BLACK; SCREEN_WIDTH as usize - 9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fewer pixels than in the rect")]
+    fn rects_with_too_few_pixels_are_refused() {
+        display::push_rect(Rect::new(10, 10, 3, 2), &[Color::BLACK; 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fewer pixels than in the rect")]
+    fn rects_off_the_screen_check_their_pixels_too() {
+        // Nothing would be drawn, but the slice is still too short for the rect
+        display::push_rect(Rect::new(SCREEN_WIDTH, 0, 4, 4), &[Color::BLACK; 15]);
+    }
+
+    #[test]
+    fn rects_with_enough_pixels_are_pushed() {
+        display::push_rect(Rect::new(10, 10, 3, 2), &[Color::BLACK; 6]);
+        // Cut on the right, the rows are pushed one by one from the full width pixels
+        display::push_rect(Rect::new(SCREEN_WIDTH - 2, 10, 4, 3), &[Color::BLACK; 12]);
+    }
+}
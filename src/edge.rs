@@ -0,0 +1,158 @@
+use crate::rule::{Neighborhood, Rule};
+use crate::{Board, Changes, COLUMN_SIZE, LINE_SIZE};
+
+/// Cells of invisible buffer on each side of the board
+pub const EDGE_BUFFER: usize = 4;
+
+const WIDTH: usize = LINE_SIZE as usize + 2 * EDGE_BUFFER;
+const HEIGHT: usize = COLUMN_SIZE as usize + 2 * EDGE_BUFFER;
+
+/// Invisible ring of cells around the board, for dead edges
+///
+/// The board is simulated as the inner region of a larger one, so patterns leaving it can
+/// still come back for a few generations before being lost against the real edges.
+#[derive(Clone)]
+pub struct EdgeBuffer {
+    cells: [[bool; HEIGHT]; WIDTH],
+}
+
+impl EdgeBuffer {
+    pub const fn new() -> Self {
+        Self {
+            cells: [[false; HEIGHT]; WIDTH],
+        }
+    }
+
+    /// Forget the cells that left the board
+    pub fn clear(&mut self) {
+        self.cells = [[false; HEIGHT]; WIDTH];
+    }
+
+    /// Run a synchronous generation on the buffered board, without drawing it
+    ///
+    /// Edits made on the board since the last generation are taken into account.
+    pub fn run_once(&mut self, board: &mut Board<bool>, rule: Rule) -> Changes {
+        for (column, board_column) in self.cells[EDGE_BUFFER..].iter_mut().zip(board.iter()) {
+            column[EDGE_BUFFER..EDGE_BUFFER + COLUMN_SIZE as usize].copy_from_slice(board_column);
+        }
+
+        // Only the column on the left is overwritten before being read again,
+        // so a copy of it is enough to see the previous generation.
+        let mut previous = [false; HEIGHT];
+        for x in 0..WIDTH {
+            let current = self.cells[x];
+            let next = self.cells.get(x + 1).copied().unwrap_or([false; HEIGHT]);
+            for y in 0..HEIGHT {
+                let rows = y.saturating_sub(1)..(y + 2).min(HEIGHT);
+                let neighbors = match rule.neighborhood {
+                    Neighborhood::Moore => rows
+                        .map(|row| {
+                            previous[row] as u8 + (row != y && current[row]) as u8 + next[row] as u8
+                        })
+                        .sum(),
+                    Neighborhood::Diagonal => rows
+                        .filter(|&row| row != y)
+                        .map(|row| previous[row] as u8 + next[row] as u8)
+                        .sum(),
+                };
+                self.cells[x][y] = rule.next_state(current[y], neighbors);
+            }
+            previous = current;
+        }
+
+        let mut changes = Changes::new();
+        for (x, board_column) in board.iter_mut().enumerate() {
+            for (y, cell) in board_column.iter_mut().enumerate() {
+                let alive = self.cells[x + EDGE_BUFFER][y + EDGE_BUFFER];
+                if alive != *cell {
+                    *cell = alive;
+                    changes.push((x, y), alive);
+                }
+            }
+        }
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+
+    /// Alive cells of the buffered board, relative to the top left corner of the board
+    fn alive(buffer: &EdgeBuffer) -> Vec<(isize, isize)> {
+        let mut cells = Vec::new();
+        for (x, column) in buffer.cells.iter().enumerate() {
+            for (y, _) in column.iter().enumerate().filter(|(_, &alive)| alive) {
+                cells.push((
+                    x as isize - EDGE_BUFFER as isize,
+                    y as isize - EDGE_BUFFER as isize,
+                ));
+            }
+        }
+        cells
+    }
+
+    #[test]
+    fn gliders_go_on_past_the_edges() {
+        // Heading up and left, a cell every 4 generations
+        let glider = [(0, 0), (1, 0), (2, 0), (0, 1), (1, 2)];
+        let mut board = EMPTY;
+        glider.iter().for_each(|&(x, y)| board[x + 3][y + 3] = true);
+        let mut buffer = EdgeBuffer::new();
+
+        for moved in 1..=6 {
+            for _ in 0..4 {
+                buffer.run_once(&mut board, Rule::CONWAY);
+            }
+            let mut expected: Vec<_> = glider
+                .iter()
+                .map(|&(x, y)| (x as isize + 3 - moved, y as isize + 3 - moved))
+                .collect();
+            expected.sort_unstable();
+            assert_eq!(alive(&buffer), expected, "{} cells", moved);
+            // The board shows the cells still on it
+            for (x, column) in board.iter().enumerate() {
+                for (y, &cell) in column.iter().enumerate() {
+                    assert_eq!(cell, expected.contains(&(x as isize, y as isize)));
+                }
+            }
+        }
+        // Off the board, it's only in the buffer
+        assert!(board == EMPTY);
+        assert!(!alive(&buffer).is_empty());
+
+        buffer.clear();
+        assert!(alive(&buffer).is_empty());
+    }
+
+    #[test]
+    fn blinkers_on_the_edge_come_back() {
+        let vertical = |board: &mut Board<bool>| (9..12).for_each(|y| board[0][y] = true);
+        let mut start = EMPTY;
+        vertical(&mut start);
+
+        let mut board = start;
+        let mut buffer = EdgeBuffer::new();
+        for _ in 0..5 {
+            // Half a period later, the blinker is horizontal with a cell left of the board
+            let changes = buffer.run_once(&mut board, Rule::CONWAY);
+            assert_eq!((changes.born.len(), changes.died.len()), (1, 2));
+            assert_eq!(alive(&buffer), [(-1, 10), (0, 10), (1, 10)]);
+            // And it comes back whole
+            buffer.run_once(&mut board, Rule::CONWAY);
+            assert!(board == start);
+        }
+
+        // Forgetting the cell left of the board, what's left of the blinker dies out
+        let mut board = start;
+        let mut plain = EdgeBuffer::new();
+        for _ in 0..2 {
+            plain.run_once(&mut board, Rule::CONWAY);
+            plain.clear();
+        }
+        assert!(board == EMPTY);
+    }
+}
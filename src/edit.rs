@@ -0,0 +1,139 @@
+use crate::bitgrid::BitGrid;
+use crate::edge::EdgeBuffer;
+use crate::palette::ColorPalette;
+use crate::undo::Undo;
+use crate::viewport::{LogicalPos, Viewport};
+use crate::{draw_board, draw_cell, Board, COLUMN_SIZE, LINE_SIZE};
+use core::ops::{Deref, DerefMut};
+
+/// Changed cells above which the whole board is redrawn, cheaper than as many cells
+const FULL_REPAINT_AT: u32 = 256;
+
+/// Changes to many cells of the board at once, undone together and repainted once
+///
+/// The undo snapshot is taken when the edit begins, so it's consistent even if the edit
+/// is dropped halfway. Nothing is drawn while the board is changed through the edit;
+/// when it's dropped, the cells differing from the snapshot are repainted, and the cells
+/// that had left the board are forgotten, as they came from the board before the edit.
+pub struct Edit<'a> {
+    board: &'a mut Board<bool>,
+    undo: &'a Undo,
+    edge: &'a mut EdgeBuffer,
+    /// How to repaint the board, `None` if it isn't in view
+    view: Option<(ColorPalette, Viewport)>,
+}
+
+/// Begin an edit of the board shown in `viewport`
+pub fn begin_edit<'a>(
+    board: &'a mut Board<bool>,
+    undo: &'a mut Undo,
+    edge: &'a mut EdgeBuffer,
+    palette: &ColorPalette,
+    viewport: &Viewport,
+) -> Edit<'a> {
+    undo.save(board);
+    Edit {
+        board,
+        undo,
+        edge,
+        view: Some((*palette, *viewport)),
+    }
+}
+
+/// Begin an edit from a screen covering the board, which is redrawn when it's revealed
+pub fn begin_hidden_edit<'a>(
+    board: &'a mut Board<bool>,
+    undo: &'a mut Undo,
+    edge: &'a mut EdgeBuffer,
+) -> Edit<'a> {
+    undo.save(board);
+    Edit {
+        board,
+        undo,
+        edge,
+        view: None,
+    }
+}
+
+impl Deref for Edit<'_> {
+    type Target = Board<bool>;
+
+    fn deref(&self) -> &Board<bool> {
+        self.board
+    }
+}
+
+impl DerefMut for Edit<'_> {
+    fn deref_mut(&mut self) -> &mut Board<bool> {
+        self.board
+    }
+}
+
+impl Drop for Edit<'_> {
+    fn drop(&mut self) {
+        self.edge.clear();
+        let (palette, viewport) = match &self.view {
+            Some(view) => view,
+            None => return,
+        };
+        let before = self.undo.snapshot();
+        let mut changed = BitGrid::new(LINE_SIZE, COLUMN_SIZE);
+        let mut count = 0;
+        for x in 0..LINE_SIZE {
+            for y in 0..COLUMN_SIZE {
+                if self.board[x as usize][y as usize] != before[x as usize][y as usize] {
+                    changed.set(x, y, true);
+                    count += 1;
+                }
+            }
+        }
+
+        if count > FULL_REPAINT_AT {
+            draw_board(self.board, palette, viewport);
+            return;
+        }
+        for x in 0..LINE_SIZE {
+            for y in (0..COLUMN_SIZE).filter(|&y| changed.get(x, y)) {
+                draw_cell(self.board, LogicalPos::new(x, y), palette, viewport);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rule::Rule;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn edits_unwinding_halfway_can_be_undone_and_forget_the_edge() {
+        // A blinker on the left edge, half in the edge buffer
+        let mut start = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        (9..12).for_each(|y| start[0][y] = true);
+        let mut edge = EdgeBuffer::new();
+        edge.run_once(&mut start, Rule::CONWAY);
+        let mut board = start;
+        let mut undo = Undo::new();
+
+        let unwound = catch_unwind(AssertUnwindSafe(|| {
+            let mut edit = begin_hidden_edit(&mut board, &mut undo, &mut edge);
+            edit[40][30] = true;
+            edit[0][10] = false;
+            panic!("halfway through the edit");
+        }));
+        assert!(unwound.is_err());
+
+        // The snapshot is the board from before the edit, the changes so far undo together
+        assert!(*undo.snapshot() == start);
+        assert!(board != start);
+        assert!(undo.undo(&mut board));
+        assert!(board == start);
+
+        // The cell left of the board was forgotten: without it the blinker doesn't come back
+        let mut buffered = start;
+        EdgeBuffer::new().run_once(&mut buffered, Rule::CONWAY);
+        edge.run_once(&mut board, Rule::CONWAY);
+        assert!(board == buffered);
+    }
+}
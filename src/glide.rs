@@ -0,0 +1,113 @@
+use crate::cursor::Cursor;
+
+/// Most frames a glide of the pointer lasts
+const GLIDE_FRAMES: u16 = 8;
+
+/// The pointer sliding in a straight line to a cell, one step per frame
+pub struct Glide {
+    from: Cursor,
+    target: Cursor,
+    step: u16,
+    steps: u16,
+}
+
+impl Glide {
+    /// A glide of one step per cell for short distances, and `GLIDE_FRAMES` steps for long ones
+    pub fn new(from: Cursor, target: Cursor) -> Self {
+        let (start, end) = (from.position(), target.position());
+        let distance = start.x.abs_diff(end.x).max(start.y.abs_diff(end.y));
+        Self {
+            from,
+            target,
+            step: 0,
+            steps: distance.min(GLIDE_FRAMES),
+        }
+    }
+
+    pub fn target(&self) -> Cursor {
+        self.target
+    }
+}
+
+impl Iterator for Glide {
+    type Item = Cursor;
+
+    /// Next cell on the way, the target being the last one
+    fn next(&mut self) -> Option<Cursor> {
+        if self.step >= self.steps {
+            return None;
+        }
+        self.step += 1;
+        let along = |from: u16, to: u16| {
+            let (from, to) = (from as i32, to as i32);
+            let (done, steps) = (self.step as i32, self.steps as i32);
+            // Rounded to the nearest cell
+            from + ((to - from) * done * 2 + steps * (to - from).signum()) / (2 * steps)
+        };
+        let (start, end) = (self.from.position(), self.target.position());
+        Some(Cursor::clamped((
+            along(start.x, end.x),
+            along(start.y, end.y),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    fn cursor(x: i32, y: i32) -> Cursor {
+        Cursor::checked((x, y)).unwrap()
+    }
+
+    fn positions(glide: Glide) -> Vec<(u16, u16)> {
+        glide
+            .map(|cursor| (cursor.position().x, cursor.position().y))
+            .collect()
+    }
+
+    fn positions_from(from: (i32, i32), to: (i32, i32)) -> Vec<(u16, u16)> {
+        positions(Glide::new(cursor(from.0, from.1), cursor(to.0, to.1)))
+    }
+
+    #[test]
+    fn short_glides_go_a_cell_at_a_time() {
+        let glide = Glide::new(cursor(10, 10), cursor(13, 8));
+        assert!(glide.target() == cursor(13, 8));
+        // The shorter axis is rounded to the nearest cell
+        assert_eq!(positions(glide), [(11, 9), (12, 9), (13, 8)]);
+        assert_eq!(positions(Glide::new(cursor(5, 5), cursor(4, 5))), [(4, 5)]);
+    }
+
+    #[test]
+    fn long_glides_take_the_same_frames_whatever_the_distance() {
+        let glide = Glide::new(cursor(0, 0), cursor(79, 59));
+        assert!(glide.target() == cursor(79, 59));
+        let positions = positions(glide);
+        assert_eq!(positions.len(), GLIDE_FRAMES as usize);
+        assert_eq!(positions[0], (10, 7));
+        assert_eq!(positions[3], (40, 30));
+        assert_eq!(positions[GLIDE_FRAMES as usize - 1], (79, 59));
+        // Always getting closer, at an even pace
+        for pair in positions.windows(2) {
+            let (dx, dy) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+            assert!(
+                (9..=10).contains(&dx) && (7..=8).contains(&dy),
+                "{:?}",
+                pair
+            );
+        }
+
+        let back = positions_from((79, 59), (0, 0));
+        assert_eq!(back.len(), GLIDE_FRAMES as usize);
+        assert_eq!(back[GLIDE_FRAMES as usize - 1], (0, 0));
+    }
+
+    #[test]
+    fn glides_in_place_are_over_at_once() {
+        let mut glide = Glide::new(cursor(20, 30), cursor(20, 30));
+        assert!(glide.next().is_none());
+        assert!(glide.target() == cursor(20, 30));
+    }
+}
@@ -0,0 +1,172 @@
+use crate::autosolve::seed_soup;
+use crate::bitgrid::{BitGrid, BlitMode};
+use crate::period::checksum;
+use crate::rule::{BoundaryMode, LifeLike, Rule};
+use crate::step::{Progress, StepContext};
+use crate::{run_once_sync, Board, COLUMN_SIZE, LINE_SIZE};
+
+/// Cells computed at once when stepping in parts, uneven so parts end mid-column
+const PART_CELLS: usize = 97;
+
+/// Board an evolution starts from
+pub enum Start {
+    /// A pattern in RLE, with its top left corner at a position
+    Pattern(&'static str, (i16, i16)),
+    /// A random soup from a seed, with a percentage of alive cells
+    Soup { seed: u32, density: u32 },
+}
+
+/// A board evolution known to be right, to check the steppers against
+///
+/// Expected checksums come from `tools/golden.rs`, a plain stepper written apart from the
+/// app: to add an evolution, run it with `cargo make golden` and copy what it prints.
+pub struct Golden {
+    pub name: &'static str,
+    pub start: Start,
+    pub automaton: LifeLike,
+    pub generations: u32,
+    /// Checksum of the final board
+    pub expected: u32,
+}
+
+const GLIDER: &str = "bo$2bo$3o!";
+const DIEHARD: &str = "6bo$2o$bo3b3o!";
+
+pub const CORPUS: [Golden; 5] = [
+    // It dies against the bottom right corner, leaving a block
+    Golden {
+        name: "Glider, dead edges\0",
+        start: Start::Pattern(GLIDER, (10, 10)),
+        automaton: LifeLike {
+            rule: Rule::CONWAY,
+            boundary: BoundaryMode::Dead,
+        },
+        generations: 300,
+        expected: 0xF972_05B5,
+    },
+    // A cell every 4 generations, around 80 and 60 cells
+    Golden {
+        name: "Glider, torus\0",
+        start: Start::Pattern(GLIDER, (10, 10)),
+        automaton: LifeLike {
+            rule: Rule::CONWAY,
+            boundary: BoundaryMode::Wrap,
+        },
+        generations: 4 * 240,
+        expected: 0xCE6C_5C39,
+    },
+    // Gone after 130 generations, without ever reaching the edges
+    Golden {
+        name: "Diehard\0",
+        start: Start::Pattern(DIEHARD, (36, 28)),
+        automaton: LifeLike {
+            rule: Rule::CONWAY,
+            boundary: BoundaryMode::Dead,
+        },
+        generations: 130,
+        expected: 0x2A4F_29A5,
+    },
+    Golden {
+        name: "Seeds soup, torus\0",
+        start: Start::Soup {
+            seed: 0x5EED,
+            density: 20,
+        },
+        automaton: LifeLike {
+            rule: Rule::SEEDS,
+            boundary: BoundaryMode::Wrap,
+        },
+        generations: 50,
+        expected: 0xCDF2_2CD8,
+    },
+    Golden {
+        name: "Diagonal soup\0",
+        start: Start::Soup {
+            seed: 0xD1A6,
+            density: 35,
+        },
+        automaton: LifeLike {
+            rule: Rule::diagonal(&[1], &[1, 2]),
+            boundary: BoundaryMode::Dead,
+        },
+        generations: 100,
+        expected: 0xA58E_69D3,
+    },
+];
+
+impl Golden {
+    /// The board the evolution starts from
+    pub fn start_board(&self) -> Board<bool> {
+        let mut board: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        match self.start {
+            Start::Pattern(rle, at) => {
+                if let Ok(grid) = BitGrid::decode(rle) {
+                    grid.blit_onto(&mut board, at, BlitMode::Or);
+                }
+            }
+            Start::Soup { seed, density } => seed_soup(&mut board, seed, density),
+        }
+        board
+    }
+
+    /// Checksum of the final board, with every generation stepped at once or in parts
+    pub fn run(&self, in_parts: bool) -> u32 {
+        let mut board = self.start_board();
+        for _ in 0..self.generations {
+            if in_parts {
+                let mut step = StepContext::begin(&board, self.automaton);
+                while let Progress::InProgress = step.advance(PART_CELLS) {}
+                step.commit(&mut board);
+            } else {
+                run_once_sync(&mut board, &self.automaton);
+            }
+        }
+        checksum(&board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn golden(name: &str) -> &'static Golden {
+        CORPUS
+            .iter()
+            .find(|golden| golden.name.trim_end_matches('\0') == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn every_evolution_gives_its_checksum_at_once_and_in_parts() {
+        for golden in CORPUS.iter() {
+            assert_eq!(golden.run(false), golden.expected, "{}", golden.name);
+            assert_eq!(golden.run(true), golden.expected, "{}", golden.name);
+        }
+    }
+
+    #[test]
+    fn known_endings_agree_with_the_corpus() {
+        // A glider moves a cell every 4 generations, around 80 by 60 cells in 4 * 240
+        let torus = golden("Glider, torus");
+        assert_eq!(torus.generations, 4 * 240);
+        assert_eq!(torus.expected, checksum(&torus.start_board()));
+
+        let empty = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        assert_eq!(golden("Diehard").expected, checksum(&empty));
+    }
+
+    #[test]
+    fn every_start_is_drawn() {
+        for golden in CORPUS.iter() {
+            if let Start::Pattern(rle, _) = golden.start {
+                let grid = BitGrid::decode(rle).ok().unwrap();
+                let alive = crate::analysis::population(&golden.start_board());
+                let cells = (0..grid.width())
+                    .flat_map(|x| (0..grid.height()).map(move |y| (x, y)))
+                    .filter(|&(x, y)| grid.get(x, y))
+                    .count();
+                assert_eq!(alive as usize, cells, "{}", golden.name);
+            }
+        }
+    }
+}
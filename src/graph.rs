@@ -0,0 +1,187 @@
+use crate::eadk::{display, Color, Point, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::palette::Theme;
+use core::fmt::Write;
+use heapless::String;
+
+/// Number of generations shown, one column each
+pub const GRAPH_LENGTH: usize = 80;
+const GRAPH_HEIGHT: u16 = 40;
+const COLUMN_WIDTH: u16 = SCREEN_WIDTH / GRAPH_LENGTH as u16;
+const LINE_WIDTH: u16 = 2;
+/// Height of the line of statistics above the graph
+const TEXT_HEIGHT: u16 = display::glyph_size(false).1;
+/// Last generations the statistics of the changes are about
+const DELTA_GENERATIONS: usize = 16;
+
+/// Scrolling graph of the births and deaths of the last generations
+///
+/// The oldest generation is on the left, new ones are pushed on the right.
+pub struct Graph {
+    births: [u32; GRAPH_LENGTH],
+    deaths: [u32; GRAPH_LENGTH],
+    len: usize,
+}
+
+impl Graph {
+    /// Screen area covered by the graph and its line of statistics
+    pub const AREA: Rect = Rect::new(
+        0,
+        SCREEN_HEIGHT - GRAPH_HEIGHT - TEXT_HEIGHT,
+        SCREEN_WIDTH,
+        GRAPH_HEIGHT + TEXT_HEIGHT,
+    );
+
+    pub const fn new() -> Self {
+        Self {
+            births: [0; GRAPH_LENGTH],
+            deaths: [0; GRAPH_LENGTH],
+            len: 0,
+        }
+    }
+
+    /// Add a generation, scrolling the oldest one out once the graph is full
+    pub fn push(&mut self, births: u32, deaths: u32) {
+        if self.len == GRAPH_LENGTH {
+            self.births.copy_within(1.., 0);
+            self.deaths.copy_within(1.., 0);
+            self.len -= 1;
+        }
+        self.births[self.len] = births;
+        self.deaths[self.len] = deaths;
+        self.len += 1;
+    }
+
+    pub fn draw(&self, theme: &Theme) {
+        display::push_rect_uniform(Self::AREA, Color::WHITE);
+
+        let max = self.births[..self.len]
+            .iter()
+            .chain(self.deaths[..self.len].iter())
+            .copied()
+            .max()
+            .unwrap_or(0);
+
+        draw_series(&self.births[..self.len], max, theme.born);
+        draw_series(&self.deaths[..self.len], max, theme.died);
+        self.draw_trend();
+    }
+
+    /// Draw the statistics of the cells changed by the last generations, and what they tell
+    ///
+    /// A board whose cells keep changing while its population ends up where it started
+    /// over the last generations is most likely oscillating.
+    fn draw_trend(&self) {
+        let recent = self.len.saturating_sub(DELTA_GENERATIONS)..self.len;
+        let mut changes = [0; DELTA_GENERATIONS];
+        for (change, i) in changes.iter_mut().zip(recent.clone()) {
+            *change = self.births[i] + self.deaths[i];
+        }
+        let (min, max, mean) = delta_stats(&changes[..recent.len()]);
+        let growth = self.births[recent.clone()].iter().sum::<u32>() as i32
+            - self.deaths[recent].iter().sum::<u32>() as i32;
+        let trend = match growth {
+            _ if max == 0 => "stable",
+            0 => "oscillating",
+            1.. => "growing",
+            _ => "shrinking",
+        };
+
+        let mut text: String<64> = String::new();
+        write!(
+            text,
+            "Changes {}-{} avg {:.1} pop {:+} {}\0",
+            min, max, mean, growth, trend
+        )
+        .unwrap();
+        display::draw_string(
+            &text,
+            Point::new(2, Self::AREA.y),
+            false,
+            Color::BLACK,
+            Color::WHITE,
+        );
+    }
+}
+
+/// Fewest, most and mean cells changed by a generation, out of the changes of some generations
+fn delta_stats(changes: &[u32]) -> (u32, u32, f32) {
+    let min = changes.iter().copied().min().unwrap_or(0);
+    let max = changes.iter().copied().max().unwrap_or(0);
+    let mean = match changes.len() {
+        0 => 0.0,
+        len => changes.iter().sum::<u32>() as f32 / len as f32,
+    };
+    (min, max, mean)
+}
+
+/// Map a value in [0; max] to a height in pixel in [0; GRAPH_HEIGHT - LINE_WIDTH]
+fn value_height(value: u32, max: u32) -> u16 {
+    (value.min(max) * (GRAPH_HEIGHT - LINE_WIDTH) as u32)
+        .checked_div(max)
+        .unwrap_or(0) as u16
+}
+
+fn draw_series(values: &[u32], max: u32, color: Color) {
+    let mut previous = values.first().map_or(0, |&v| value_height(v, max));
+    values.iter().enumerate().for_each(|(i, &value)| {
+        let height = value_height(value, max);
+        // Join with the previous column so the series reads as a line
+        let (low, high) = (height.min(previous), height.max(previous));
+        display::push_rect_uniform(
+            Rect::new(
+                i as u16 * COLUMN_WIDTH,
+                SCREEN_HEIGHT - LINE_WIDTH - high,
+                COLUMN_WIDTH,
+                high - low + LINE_WIDTH,
+            ),
+            color,
+        );
+        previous = height;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_fills_then_scrolls() {
+        let mut graph = Graph::new();
+        for i in 0..GRAPH_LENGTH as u32 {
+            graph.push(i, 2 * i);
+        }
+        assert_eq!(graph.len, GRAPH_LENGTH);
+        assert_eq!((graph.births[0], graph.deaths[0]), (0, 0));
+
+        graph.push(1000, 2000);
+        assert_eq!(graph.len, GRAPH_LENGTH);
+        // The oldest generation went out on the left, the new one came in on the right
+        assert_eq!((graph.births[0], graph.deaths[0]), (1, 2));
+        assert_eq!(graph.births[GRAPH_LENGTH - 2], GRAPH_LENGTH as u32 - 1);
+        assert_eq!(
+            (
+                graph.births[GRAPH_LENGTH - 1],
+                graph.deaths[GRAPH_LENGTH - 1]
+            ),
+            (1000, 2000)
+        );
+    }
+
+    #[test]
+    fn value_height_spans_the_graph() {
+        let top = GRAPH_HEIGHT - LINE_WIDTH;
+        assert_eq!(value_height(0, 10), 0);
+        assert_eq!(value_height(10, 10), top);
+        assert_eq!(value_height(5, 10), top / 2);
+        // Clamped to the maximum, and flat when there's nothing to show
+        assert_eq!(value_height(50, 10), top);
+        assert_eq!(value_height(0, 0), 0);
+        assert_eq!(value_height(7, 0), 0);
+    }
+
+    #[test]
+    fn delta_stats_of_recent_changes() {
+        assert_eq!(delta_stats(&[]), (0, 0, 0.0));
+        assert_eq!(delta_stats(&[4, 1, 7]), (1, 7, 4.0));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::eadk::{display, key, timing, Color, Point, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use heapless::String;
+
+/// Space between the panel and the screen edges
+const MARGIN: u16 = 4;
+/// Space between the panel border and its text
+const PADDING: u16 = 4;
+/// Width of the key column, in characters
+const KEY_COLUMN: usize = 10;
+/// Time each page of chords is shown when they don't all fit, in milliseconds
+const PAGE_MS: u64 = 2500;
+
+/// Where a key chord does something
+#[derive(Clone, Copy, PartialEq)]
+pub enum Scope {
+    Everywhere,
+    Editor,
+}
+
+/// A key chord pressed with SHIFT, and what it does
+pub struct Binding {
+    pub keys: &'static str,
+    /// Keys of the chord besides SHIFT, for the keypad map
+    pub codes: &'static [u32],
+    /// Name of the action short enough to fit on a key
+    pub short: &'static str,
+    pub action: &'static str,
+    pub scope: Scope,
+}
+
+impl Binding {
+    /// Whether the chord does something in the editor, or elsewhere
+    pub fn applies(&self, in_editor: bool) -> bool {
+        in_editor || self.scope == Scope::Everywhere
+    }
+}
+
+/// Every SHIFT chord of the app
+pub const SHIFT_BINDINGS: &[Binding] = &[
+    Binding {
+        keys: "ALPHA+EXE",
+        codes: &[key::ALPHA, key::EXE],
+        short: "test",
+        action: "Self-test",
+        scope: Scope::Everywhere,
+    },
+    Binding {
+        keys: "BACK+HOME",
+        codes: &[key::BACK, key::HOME],
+        short: "macro",
+        action: "Record / stop a key macro",
+        scope: Scope::Everywhere,
+    },
+    Binding {
+        keys: "EXP",
+        codes: &[key::EXP],
+        short: "keys",
+        action: "Map of these on the keypad",
+        scope: Scope::Everywhere,
+    },
+    Binding {
+        keys: "+ / -",
+        codes: &[key::PLUS, key::MINUS],
+        short: "zoom",
+        action: "Zoom in / out",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "OK",
+        codes: &[key::OK],
+        short: "cross",
+        action: "Precision crosshair",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "0",
+        codes: &[key::ZERO],
+        short: "near",
+        action: "Go to the nearest alive cell",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "1 / 2",
+        codes: &[key::ONE, key::TWO],
+        short: "snap",
+        action: "Save snapshot A / B",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "i",
+        codes: &[key::IMAGINARY],
+        short: "symm",
+        action: "Make the board symmetric",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "6",
+        codes: &[key::SIX],
+        short: "flips",
+        action: "Cells flipped by perturbations",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "7 / 9",
+        codes: &[key::SEVEN, key::NINE],
+        short: "mirr",
+        action: "Mirror the right / bottom half",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "ln",
+        codes: &[key::LN],
+        short: "chkpt",
+        action: "Checkpoint interval",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "^",
+        codes: &[key::POWER],
+        short: "rec",
+        action: "Record jumps for replay",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "log",
+        codes: &[key::LOG],
+        short: "noise",
+        action: "Noise level",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "÷",
+        codes: &[key::DIVISION],
+        short: "LWSS",
+        action: "Place a LWSS and measure",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "×",
+        codes: &[key::MULTIPLICATION],
+        short: "free",
+        action: "Lift the confinement",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "EE",
+        codes: &[key::EE],
+        short: "rings",
+        action: "Fill in rings, spiraling out",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "√",
+        codes: &[key::SQRT],
+        short: "soup",
+        action: "New random soup",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "ANS",
+        codes: &[key::ANS],
+        short: "boot",
+        action: "Open in the editor / running",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "COS",
+        codes: &[key::COSINE],
+        short: "uncap",
+        action: "Uncapped on / off",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "TAN",
+        codes: &[key::TANGENT],
+        short: "theme",
+        action: "Color-blind theme",
+        scope: Scope::Editor,
+    },
+    Binding {
+        keys: "π",
+        codes: &[key::PI],
+        short: "rim",
+        action: "Tint the rim of the board",
+        scope: Scope::Editor,
+    },
+];
+
+/// Draw the chords available in the editor or elsewhere, returns the area to repaint after
+///
+/// When they don't all fit on the screen, they're shown a page at a time in turn.
+pub fn draw(in_editor: bool) -> Rect {
+    let (glyph_width, glyph_height) = display::glyph_size(false);
+    let max_chars = ((SCREEN_WIDTH - 2 * (MARGIN + PADDING)) / glyph_width) as usize;
+    let max_lines = ((SCREEN_HEIGHT - 2 * (MARGIN + PADDING)) / glyph_height) as usize;
+
+    let available = || {
+        SHIFT_BINDINGS
+            .iter()
+            .filter(move |binding| binding.applies(in_editor))
+    };
+    let count = available().count();
+    let page = (timing::millis() / PAGE_MS) as usize % count.div_ceil(max_lines).max(1);
+    let lines = |all: bool| {
+        available()
+            .skip(if all { 0 } else { page * max_lines })
+            .take(if all { count } else { max_lines })
+            .map(move |binding| {
+                let mut line: String<64> = String::new();
+                for c in binding
+                    .keys
+                    .chars()
+                    .chain(core::iter::repeat(' '))
+                    .take(KEY_COLUMN)
+                    .chain(binding.action.chars())
+                    .take(max_chars)
+                {
+                    line.push(c).unwrap();
+                }
+                line.push('\0').unwrap();
+                line
+            })
+    };
+
+    // Sized for every page, so one covers what the previous one drew
+    let width = lines(true)
+        .map(|line| display::text_width(&line, false))
+        .max()
+        .unwrap_or(0);
+    let area = Rect::new(
+        MARGIN,
+        MARGIN,
+        width + 2 * PADDING,
+        count.min(max_lines) as u16 * glyph_height + 2 * PADDING,
+    );
+    display::push_rect_uniform(area, Color::BLACK);
+    for (row, line) in lines(false).enumerate() {
+        display::draw_string(
+            &line,
+            Point::new(
+                MARGIN + PADDING,
+                MARGIN + PADDING + row as u16 * glyph_height,
+            ),
+            false,
+            Color::WHITE,
+            Color::BLACK,
+        );
+    }
+    area
+}
@@ -0,0 +1,160 @@
+use crate::bitgrid::{BitGrid, BlitMode};
+use crate::eadk::{display, Color, Point, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::palette::Theme;
+use crate::patterns::{self, RleError};
+use crate::rule::Rule;
+use crate::{board_to_image, draw_stat, Board, COLUMN_SIZE, LINE_SIZE};
+
+/// How an imported pattern is combined with the board
+#[derive(Clone, Copy)]
+pub enum ImportMode {
+    /// Add the pattern to the alive cells of the board
+    Merge,
+    /// Clear the board before adding the pattern
+    Replace,
+}
+
+/// A pattern parsed and validated, waiting for confirmation before touching the board
+pub struct Staged {
+    /// The pattern from the top left corner, cropped to the board
+    cells: BitGrid,
+    width: u16,
+    height: u16,
+    population: u32,
+    /// Rule from the RLE header, if it gives one
+    pub rule: Option<Rule>,
+    mode: ImportMode,
+}
+
+/// Parse and validate a RLE pattern into a staging board
+///
+/// Nothing is staged unless the whole text decodes, so a broken pattern
+/// never ends up half applied.
+pub fn stage(data: &[u8]) -> Result<Staged, RleError> {
+    let rle =
+        core::str::from_utf8(data).map_err(|error| text_position(data, error.valid_up_to()))?;
+
+    let mut population = 0;
+    let (width, height) = patterns::decode(rle, |_, _| population += 1)?;
+    Ok(Staged {
+        cells: BitGrid::decode(rle)?,
+        width,
+        height,
+        population,
+        rule: patterns::header(rle).and_then(|header| header.rule),
+        mode: ImportMode::Merge,
+    })
+}
+
+/// Line and column (starting from 1) of a byte offset
+fn text_position(data: &[u8], offset: usize) -> RleError {
+    let before = &data[..offset];
+    let line_start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    RleError {
+        line: before.iter().filter(|&&b| b == b'\n').count() as u16 + 1,
+        column: (offset - line_start) as u16 + 1,
+    }
+}
+
+impl Staged {
+    /// Whether the pattern doesn't fit on the board, and only its top left part is imported
+    pub fn cropped(&self) -> bool {
+        self.width > LINE_SIZE || self.height > COLUMN_SIZE
+    }
+
+    /// Turn the pattern a quarter turn clockwise
+    pub fn rotate(&mut self) {
+        self.cells.rotate_clockwise();
+        core::mem::swap(&mut self.width, &mut self.height);
+    }
+
+    /// Mirror the pattern left to right
+    pub fn flip(&mut self) {
+        self.cells.flip_horizontal();
+    }
+
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            ImportMode::Merge => ImportMode::Replace,
+            ImportMode::Replace => ImportMode::Merge,
+        };
+    }
+
+    /// Draw the confirmation screen, with what's known about the pattern
+    pub fn draw(&self, theme: &Theme) {
+        display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+        draw_stat(0, "Size", format_args!("{}x{}", self.width, self.height));
+        draw_stat(1, "Population", self.population);
+        match self.rule {
+            Some(rule) => draw_stat(2, "Rule", rule),
+            None => draw_stat(2, "Rule", "unchanged"),
+        }
+        draw_stat(
+            3,
+            "Mode",
+            match self.mode {
+                ImportMode::Merge => "< Merge >",
+                ImportMode::Replace => "< Replace >",
+            },
+        );
+        if self.cropped() {
+            display::draw_string(
+                "Too large, will be cropped\0",
+                Point::new(10, 110),
+                false,
+                theme.warning,
+                Color::WHITE,
+            );
+        }
+        display::draw_string(
+            "UP: rotate  DOWN: flip  EXE: import\0",
+            Point::new(10, SCREEN_HEIGHT - 20),
+            false,
+            Color::BLACK,
+            Color::WHITE,
+        );
+
+        let thumbnail = Rect::new(
+            SCREEN_WIDTH - LINE_SIZE - 10,
+            SCREEN_HEIGHT - COLUMN_SIZE - 30,
+            LINE_SIZE,
+            COLUMN_SIZE,
+        );
+        display::push_rect_uniform(
+            Rect::new(
+                thumbnail.x - 1,
+                thumbnail.y - 1,
+                LINE_SIZE + 2,
+                COLUMN_SIZE + 2,
+            ),
+            Color::BLACK,
+        );
+        let mut preview = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        self.cells.blit_onto(&mut preview, (0, 0), BlitMode::Or);
+        display::push_rect(
+            thumbnail,
+            &board_to_image(&preview, Color::BLACK, Color::WHITE),
+        );
+    }
+
+    /// Apply the pattern centered on the board
+    ///
+    /// A pattern rotated to be larger than the board is cropped evenly on both sides.
+    pub fn apply(&self, board: &mut Board<bool>) {
+        let mode = match self.mode {
+            ImportMode::Merge => BlitMode::Or,
+            ImportMode::Replace => {
+                *board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+                BlitMode::Replace
+            }
+        };
+        let at = (
+            (LINE_SIZE as i16 - self.cells.width() as i16) / 2,
+            (COLUMN_SIZE as i16 - self.cells.height() as i16) / 2,
+        );
+        self.cells.blit_onto(board, at, mode);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::eadk::{key, State};
+
+/// What is kept of two conflicting keys held together
+#[derive(Clone, Copy)]
+pub enum Policy {
+    /// Neither, they cancel out
+    Cancel,
+    /// Only the first one
+    FirstWins,
+}
+
+/// Two keys with opposite meanings
+pub struct Conflict {
+    pub keys: (u32, u32),
+    pub policy: Policy,
+}
+
+/// Every pair of opposite keys, and which one is kept when both are down
+pub const CONFLICTS: &[Conflict] = &[
+    Conflict {
+        keys: (key::LEFT, key::RIGHT),
+        policy: Policy::Cancel,
+    },
+    Conflict {
+        keys: (key::UP, key::DOWN),
+        policy: Policy::Cancel,
+    },
+    // Drawing over erasing, and zooming in over zooming out
+    Conflict {
+        keys: (key::PLUS, key::MINUS),
+        policy: Policy::FirstWins,
+    },
+];
+
+/// Keys switching to another screen, by priority: only the first one down is kept
+///
+/// Keys also used for typing or in other screens, like the digits, aren't part of it.
+pub const MODE_KEYS: &[u32] = &[
+    key::XNT,
+    key::VAR,
+    key::TOOLBOX,
+    key::EXP,
+    key::LEFTPARENTHESIS,
+    key::SQUARE,
+    key::COMMA,
+    key::POWER,
+    key::RIGHTPARENTHESIS,
+];
+
+/// Keys making chords with the others
+pub const MODIFIERS: &[u32] = &[key::SHIFT, key::ALPHA];
+
+/// Keys a [`State`] can hold, one per bit
+pub const KEY_COUNT: u32 = 64;
+
+/// Drop the keys losing a conflict, and every mode key but the one with the most priority
+pub fn resolve(mut state: State) -> State {
+    for conflict in CONFLICTS {
+        let (first, second) = conflict.keys;
+        if state.key_down(first) && state.key_down(second) {
+            state = state.without(second);
+            if let Policy::Cancel = conflict.policy {
+                state = state.without(first);
+            }
+        }
+    }
+    if let Some(&kept) = MODE_KEYS.iter().find(|&&k| state.key_down(k)) {
+        state = MODE_KEYS
+            .iter()
+            .filter(|&&k| k != kept)
+            .fold(state, |state, &k| state.without(k));
+    }
+    state
+}
+
+/// The keys pressed on their own: while a modifier is held, chords take every other key
+pub fn plain(state: State) -> State {
+    if !MODIFIERS.iter().any(|&modifier| state.key_down(modifier)) {
+        return state;
+    }
+    (0..KEY_COUNT)
+        .filter(|k| !MODIFIERS.contains(k))
+        .fold(state, |state, k| state.without(k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec;
+
+    fn down(state: State) -> Vec<u32> {
+        (0..KEY_COUNT).filter(|&k| state.key_down(k)).collect()
+    }
+
+    fn holding(keys: &[u32]) -> State {
+        keys.iter().fold(State::NONE, |state, &k| state.with(k))
+    }
+
+    /// Every key with a policy, conflicts first then mode keys
+    fn contested() -> Vec<u32> {
+        CONFLICTS
+            .iter()
+            .flat_map(|conflict| [conflict.keys.0, conflict.keys.1])
+            .chain(MODE_KEYS.iter().copied())
+            .collect()
+    }
+
+    fn expected(held: &[u32]) -> Vec<u32> {
+        let mut kept: Vec<u32> = held.to_vec();
+        for conflict in CONFLICTS {
+            let (first, second) = conflict.keys;
+            if held.contains(&first) && held.contains(&second) {
+                kept.retain(|&k| k != second);
+                if let Policy::Cancel = conflict.policy {
+                    kept.retain(|&k| k != first);
+                }
+            }
+        }
+        if let Some(winner) = MODE_KEYS.iter().find(|k| held.contains(k)) {
+            kept.retain(|k| k == winner || !MODE_KEYS.contains(k));
+        }
+        kept.sort_unstable();
+        kept
+    }
+
+    #[test]
+    fn policies_hold_for_every_combination_of_contested_keys() {
+        let contested = contested();
+        // OK has no policy and must come through untouched
+        for background in [&[][..], &[key::OK][..]] {
+            for subset in 0u32..1 << contested.len() {
+                let mut held: Vec<u32> = contested
+                    .iter()
+                    .enumerate()
+                    .filter(|&(bit, _)| subset & 1 << bit != 0)
+                    .map(|(_, &k)| k)
+                    .collect();
+                held.extend_from_slice(background);
+                assert_eq!(down(resolve(holding(&held))), expected(&held), "{:?}", held);
+            }
+        }
+    }
+
+    #[test]
+    fn cancel_drops_both_keys_and_first_wins_keeps_the_first() {
+        for conflict in CONFLICTS {
+            let (first, second) = conflict.keys;
+            assert_eq!(down(resolve(holding(&[first]))), [first]);
+            assert_eq!(down(resolve(holding(&[second]))), [second]);
+            let both = down(resolve(holding(&[first, second])));
+            match conflict.policy {
+                Policy::Cancel => assert!(both.is_empty()),
+                Policy::FirstWins => assert_eq!(both, [first]),
+            }
+        }
+    }
+
+    #[test]
+    fn resolving_twice_changes_nothing() {
+        let contested = contested();
+        for subset in 0u32..1 << contested.len() {
+            let state = contested
+                .iter()
+                .enumerate()
+                .filter(|&(bit, _)| subset & 1 << bit != 0)
+                .fold(State::NONE, |state, (_, &k)| state.with(k));
+            let once = resolve(state);
+            assert_eq!(down(resolve(once)), down(once));
+        }
+    }
+
+    #[test]
+    fn keys_with_a_policy_are_distinct_and_not_modifiers() {
+        let contested = contested();
+        for (i, k) in contested.iter().enumerate() {
+            assert!(*k < KEY_COUNT);
+            assert!(!MODIFIERS.contains(k), "modifier {} has a policy", k);
+            assert!(
+                !contested[i + 1..].contains(k),
+                "key {} has two policies",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn modifiers_take_every_other_key() {
+        for k in 0..KEY_COUNT {
+            let alone = holding(&[k]);
+            assert_eq!(down(plain(alone)), [k]);
+            for modifiers in 1u32..1 << MODIFIERS.len() {
+                let held: Vec<u32> = MODIFIERS
+                    .iter()
+                    .enumerate()
+                    .filter(|&(bit, _)| modifiers & 1 << bit != 0)
+                    .map(|(_, &m)| m)
+                    .collect();
+                let mut kept = held.clone();
+                if MODIFIERS.contains(&k) && !held.contains(&k) {
+                    kept.push(k);
+                }
+                kept.sort_unstable();
+                assert_eq!(down(plain(holding(&held).with(k))), kept);
+            }
+        }
+    }
+
+    #[test]
+    fn no_modifier_leaves_every_key() {
+        let everything = (0..KEY_COUNT)
+            .filter(|k| !MODIFIERS.contains(k))
+            .fold(State::NONE, |state, k| state.with(k));
+        assert_eq!(down(plain(everything)), down(everything));
+        assert_eq!(down(plain(State::NONE)), Vec::<u32>::new());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::eadk::{display, key, Color, Point, Rect, SCREEN_WIDTH};
+use crate::hints::SHIFT_BINDINGS;
+use crate::palette::Theme;
+use heapless::String;
+
+/// Height of the title line above the keypad
+const TITLE_HEIGHT: u16 = 18;
+/// Height of a row of keys, space between two rows included
+const ROW_HEIGHT: u16 = 27;
+/// Space between two keys
+const GAP: u16 = 3;
+/// Color of the keys without a chord
+const KEY_COLOR: Color = Color::from_rgb888(64, 64, 64);
+
+/// A key of the keypad, and where it's drawn
+struct Cap {
+    code: u32,
+    label: &'static str,
+    rect: Rect,
+}
+
+/// Key in `column` of `row`, in a row of `columns` keys sharing the width of the screen
+const fn cap(code: u32, label: &'static str, (row, column, columns): (u16, u16, u16)) -> Cap {
+    let width = SCREEN_WIDTH / columns;
+    Cap {
+        code,
+        label,
+        rect: Rect::new(
+            column * width + GAP / 2,
+            TITLE_HEIGHT + row * ROW_HEIGHT,
+            width - GAP,
+            ROW_HEIGHT - GAP,
+        ),
+    }
+}
+
+/// The keys of the calculator, in rows from the top: the navigation keys, three rows of
+/// six function keys, then four rows of five for the digits and operations
+const CAPS: [Cap; 45] = [
+    cap(key::LEFT, "left", (0, 0, 7)),
+    cap(key::UP, "up", (0, 1, 7)),
+    cap(key::DOWN, "down", (0, 2, 7)),
+    cap(key::RIGHT, "right", (0, 3, 7)),
+    cap(key::OK, "OK", (0, 4, 7)),
+    cap(key::BACK, "back", (0, 5, 7)),
+    cap(key::HOME, "home", (0, 6, 7)),
+    cap(key::SHIFT, "shift", (1, 0, 6)),
+    cap(key::ALPHA, "alpha", (1, 1, 6)),
+    cap(key::XNT, "x,n,t", (1, 2, 6)),
+    cap(key::VAR, "var", (1, 3, 6)),
+    cap(key::TOOLBOX, "tbox", (1, 4, 6)),
+    cap(key::BACKSPACE, "del", (1, 5, 6)),
+    cap(key::EXP, "e^x", (2, 0, 6)),
+    cap(key::LN, "ln", (2, 1, 6)),
+    cap(key::LOG, "log", (2, 2, 6)),
+    cap(key::IMAGINARY, "i", (2, 3, 6)),
+    cap(key::COMMA, ",", (2, 4, 6)),
+    cap(key::POWER, "x^y", (2, 5, 6)),
+    cap(key::SINE, "sin", (3, 0, 6)),
+    cap(key::COSINE, "cos", (3, 1, 6)),
+    cap(key::TANGENT, "tan", (3, 2, 6)),
+    cap(key::PI, "pi", (3, 3, 6)),
+    cap(key::SQRT, "sqrt", (3, 4, 6)),
+    cap(key::SQUARE, "x^2", (3, 5, 6)),
+    cap(key::SEVEN, "7", (4, 0, 5)),
+    cap(key::EIGHT, "8", (4, 1, 5)),
+    cap(key::NINE, "9", (4, 2, 5)),
+    cap(key::LEFTPARENTHESIS, "(", (4, 3, 5)),
+    cap(key::RIGHTPARENTHESIS, ")", (4, 4, 5)),
+    cap(key::FOUR, "4", (5, 0, 5)),
+    cap(key::FIVE, "5", (5, 1, 5)),
+    cap(key::SIX, "6", (5, 2, 5)),
+    cap(key::MULTIPLICATION, "*", (5, 3, 5)),
+    cap(key::DIVISION, "/", (5, 4, 5)),
+    cap(key::ONE, "1", (6, 0, 5)),
+    cap(key::TWO, "2", (6, 1, 5)),
+    cap(key::THREE, "3", (6, 2, 5)),
+    cap(key::PLUS, "+", (6, 3, 5)),
+    cap(key::MINUS, "-", (6, 4, 5)),
+    cap(key::ZERO, "0", (7, 0, 5)),
+    cap(key::DOT, ".", (7, 1, 5)),
+    cap(key::EE, "EE", (7, 2, 5)),
+    cap(key::ANS, "ans", (7, 3, 5)),
+    cap(key::EXE, "EXE", (7, 4, 5)),
+];
+
+/// Fill `rect` with its corners cut, like a key
+fn push_rounded_rect(rect: Rect, color: Color) {
+    display::push_rect_uniform(
+        Rect::new(rect.x + 1, rect.y, rect.width - 2, rect.height),
+        color,
+    );
+    display::push_rect_uniform(
+        Rect::new(rect.x, rect.y + 1, rect.width, rect.height - 2),
+        color,
+    );
+}
+
+/// Draw the keypad, with the keys making a SHIFT chord in the editor or elsewhere labeled
+/// with what they do
+pub fn draw(in_editor: bool, theme: &Theme) {
+    display::push_rect_uniform(Rect::SCREEN, Color::BLACK);
+    let title = if in_editor {
+        "SHIFT + key, in the editor   < >\0"
+    } else {
+        "SHIFT + key, everywhere   < >\0"
+    };
+    display::draw_string(title, Point::new(4, 2), false, Color::WHITE, Color::BLACK);
+
+    for cap in CAPS.iter() {
+        let binding = SHIFT_BINDINGS
+            .iter()
+            .filter(|binding| binding.applies(in_editor))
+            .find(|binding| binding.codes.contains(&cap.code));
+        let (label, background, text_color) = match binding {
+            Some(binding) => (binding.short, theme.success, Color::BLACK),
+            None if cap.code == key::SHIFT => (cap.label, theme.warning, Color::BLACK),
+            None => (cap.label, KEY_COLOR, Color::WHITE),
+        };
+        push_rounded_rect(cap.rect, background);
+
+        let mut text: String<8> = String::new();
+        text.push_str(label).unwrap();
+        text.push('\0').unwrap();
+        let (_, glyph_height) = display::glyph_size(false);
+        let width = display::text_width(&text, false);
+        display::draw_string(
+            &text,
+            Point::new(
+                cap.rect.x + cap.rect.width.saturating_sub(width) / 2,
+                cap.rect.y + (cap.rect.height - glyph_height) / 2,
+            ),
+            false,
+            text_color,
+            background,
+        );
+    }
+}
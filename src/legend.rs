@@ -0,0 +1,195 @@
+use crate::cone;
+use crate::diff::{self, CellDiff};
+use crate::eadk::{display, Color, Point, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::palette::{ColorPalette, Theme};
+use crate::{density_color, FRONTIER_TINT_AMOUNT};
+use heapless::{String, Vec};
+
+/// Space between the legend and the screen edges
+const MARGIN: u16 = 4;
+/// Space between the legend border and its lines
+const PADDING: u16 = 4;
+/// Side of the square showing a color, in pixels
+const SWATCH: u16 = 10;
+/// Most colors in a legend
+const ENTRIES: usize = 4;
+
+/// Ways of drawing the board in colors that mean something
+#[derive(Clone, Copy, PartialEq)]
+pub enum RenderMode {
+    /// Two snapshots compared
+    Diff,
+    /// The board compared with its copy run with Conway's rule
+    Versus,
+    /// Density of alive cells
+    HeatMap,
+    /// Cells a cell can influence, or does if `actual`
+    Cone { actual: bool },
+    /// Cells between alive and dead regions
+    Frontier,
+}
+
+impl RenderMode {
+    /// Bit of the mode in a set of modes, the same whatever its fields
+    pub fn bit(self) -> u8 {
+        match self {
+            RenderMode::Diff => 1,
+            RenderMode::Versus => 1 << 1,
+            RenderMode::HeatMap => 1 << 2,
+            RenderMode::Cone { .. } => 1 << 3,
+            RenderMode::Frontier => 1 << 4,
+        }
+    }
+
+    /// Colors the mode draws with and what they mean, from the colors the mode is drawn with
+    ///
+    /// The match has no catch-all arm, so no mode can be added without its legend.
+    pub fn legend(
+        self,
+        palette: &ColorPalette,
+        theme: &Theme,
+    ) -> Vec<(Color, &'static str), ENTRIES> {
+        let entries: &[(Color, &'static str)] = match self {
+            RenderMode::Diff => &[
+                (diff::color(CellDiff::OnlyA, theme), "Only in A"),
+                (diff::color(CellDiff::OnlyB, theme), "Only in B"),
+                (diff::color(CellDiff::Both, theme), "In both"),
+            ],
+            RenderMode::Versus => &[
+                (diff::color(CellDiff::OnlyA, theme), "Only Conway"),
+                (diff::color(CellDiff::OnlyB, theme), "Only this rule"),
+                (diff::color(CellDiff::Both, theme), "Both"),
+            ],
+            RenderMode::HeatMap => &[
+                (density_color(0), "Empty"),
+                (density_color(8), "Sparse"),
+                (density_color(16), "Dense"),
+                (density_color(25), "Full"),
+            ],
+            RenderMode::Cone { actual } => &[
+                (palette.live_cell, "Alive"),
+                (
+                    palette.dead_cell.mix(cone::TINT, cone::TINT_AMOUNT),
+                    if actual { "Changed" } else { "Reachable" },
+                ),
+            ],
+            RenderMode::Frontier => &[
+                (palette.live_cell, "Alive"),
+                (
+                    palette.dead_cell.mix(theme.frontier, FRONTIER_TINT_AMOUNT),
+                    "Frontier",
+                ),
+            ],
+        };
+        entries.iter().copied().collect()
+    }
+}
+
+/// Draw the legend of `mode` in the bottom right corner, returns the area to repaint after
+pub fn draw(mode: RenderMode, palette: &ColorPalette, theme: &Theme) -> Rect {
+    let legend = mode.legend(palette, theme);
+    let (glyph_width, glyph_height) = display::glyph_size(false);
+    let label_width = legend
+        .iter()
+        .map(|(_, label)| label.len() as u16 * glyph_width)
+        .max()
+        .unwrap_or(0);
+    let width = SWATCH + PADDING + label_width + 2 * PADDING;
+    let height = legend.len() as u16 * glyph_height + 2 * PADDING;
+    let area = Rect::new(
+        SCREEN_WIDTH - MARGIN - width,
+        SCREEN_HEIGHT - MARGIN - height,
+        width,
+        height,
+    );
+    display::push_rect_uniform(area, Color::BLACK);
+    for (row, (color, label)) in legend.iter().enumerate() {
+        let y = area.y + PADDING + row as u16 * glyph_height;
+        // White around the swatch, so black and dark colors show on the black box
+        display::push_rect_uniform(
+            Rect::new(
+                area.x + PADDING,
+                y + (glyph_height - SWATCH) / 2,
+                SWATCH,
+                SWATCH,
+            ),
+            Color::WHITE,
+        );
+        display::push_rect_uniform(
+            Rect::new(
+                area.x + PADDING + 1,
+                y + (glyph_height - SWATCH) / 2 + 1,
+                SWATCH - 2,
+                SWATCH - 2,
+            ),
+            *color,
+        );
+        let mut text: String<16> = String::new();
+        text.push_str(label).unwrap();
+        text.push('\0').unwrap();
+        display::draw_string(
+            &text,
+            Point::new(area.x + 2 * PADDING + SWATCH, y),
+            false,
+            Color::WHITE,
+            Color::BLACK,
+        );
+    }
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette::{PALETTES, THEMES};
+
+    /// Every mode, listed next to a match that stops compiling when one is added
+    fn every_mode() -> [RenderMode; 6] {
+        let modes = [
+            RenderMode::Diff,
+            RenderMode::Versus,
+            RenderMode::HeatMap,
+            RenderMode::Cone { actual: false },
+            RenderMode::Cone { actual: true },
+            RenderMode::Frontier,
+        ];
+        for mode in modes {
+            match mode {
+                RenderMode::Diff
+                | RenderMode::Versus
+                | RenderMode::HeatMap
+                | RenderMode::Cone { .. }
+                | RenderMode::Frontier => {}
+            }
+        }
+        modes
+    }
+
+    #[test]
+    fn every_mode_tells_its_colors_apart() {
+        for palette in PALETTES.iter() {
+            for theme in THEMES.iter() {
+                for mode in every_mode() {
+                    let legend = mode.legend(palette, theme);
+                    assert!(!legend.is_empty());
+                    for (i, (color, label)) in legend.iter().enumerate() {
+                        // Drawn from a string of 16 bytes, the last one ending it
+                        assert!(label.len() < 16, "{}", label);
+                        for (other_color, other_label) in &legend[i + 1..] {
+                            assert!(color.rgb565 != other_color.rgb565, "{}", label);
+                            assert_ne!(label, other_label);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn modes_have_a_bit_each() {
+        let bits = every_mode()
+            .iter()
+            .fold(0u8, |bits, mode| bits | mode.bit());
+        assert_eq!(bits.count_ones(), 5);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::eadk::{display, Color, Point, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::patterns::{Pattern, PATTERNS};
+use heapless::Vec;
+
+const THUMBNAIL_WIDTH: u16 = 32;
+const THUMBNAIL_HEIGHT: u16 = 24;
+/// Size in pixel of a thumbnail cell on screen
+const THUMBNAIL_SCALE: u16 = 3;
+const CACHE_SIZE: usize = 8;
+
+const ROW_HEIGHT: u16 = 22;
+const VISIBLE_ROWS: usize = (SCREEN_HEIGHT / ROW_HEIGHT) as usize;
+
+/// A pattern downscaled to fit in a 32×24 bit grid
+struct Thumbnail {
+    rows: [u32; THUMBNAIL_HEIGHT as usize],
+}
+
+impl Thumbnail {
+    /// Scale a pattern down into a thumbnail
+    fn render(pattern: &Pattern) -> Self {
+        let scale = 1
+            .max(pattern.width.div_ceil(THUMBNAIL_WIDTH))
+            .max(pattern.height.div_ceil(THUMBNAIL_HEIGHT));
+
+        let mut thumbnail = Self {
+            rows: [0; THUMBNAIL_HEIGHT as usize],
+        };
+        for x in 0..pattern.width {
+            for y in (0..pattern.height).filter(|&y| pattern.get(x, y)) {
+                thumbnail.rows[(y / scale) as usize] |= 1 << (x / scale);
+            }
+        }
+        thumbnail
+    }
+
+    fn draw(&self, origin: Point) {
+        display::push_rect_uniform(
+            Rect::new(
+                origin.x - 1,
+                origin.y - 1,
+                THUMBNAIL_WIDTH * THUMBNAIL_SCALE + 2,
+                THUMBNAIL_HEIGHT * THUMBNAIL_SCALE + 2,
+            ),
+            Color::BLACK,
+        );
+        display::push_rect_uniform(
+            Rect::new(
+                origin.x,
+                origin.y,
+                THUMBNAIL_WIDTH * THUMBNAIL_SCALE,
+                THUMBNAIL_HEIGHT * THUMBNAIL_SCALE,
+            ),
+            Color::WHITE,
+        );
+        for (y, row) in self.rows.iter().enumerate() {
+            for x in 0..THUMBNAIL_WIDTH {
+                if row >> x & 1 != 0 {
+                    display::push_rect_uniform(
+                        Rect::new(
+                            origin.x + x * THUMBNAIL_SCALE,
+                            origin.y + y as u16 * THUMBNAIL_SCALE,
+                            THUMBNAIL_SCALE,
+                            THUMBNAIL_SCALE,
+                        ),
+                        Color::BLACK,
+                    );
+                }
+            }
+        }
+    }
+}
+
+struct CacheEntry {
+    pattern: usize,
+    thumbnail: Thumbnail,
+    last_used: u32,
+}
+
+/// Least recently used cache of pattern thumbnails
+struct ThumbnailCache {
+    entries: Vec<CacheEntry, CACHE_SIZE>,
+    clock: u32,
+}
+
+impl ThumbnailCache {
+    const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            clock: 0,
+        }
+    }
+
+    /// The thumbnail of a pattern, rendering it on a miss
+    fn get(&mut self, pattern: usize) -> &Thumbnail {
+        self.clock += 1;
+
+        let index = match self.entries.iter().position(|e| e.pattern == pattern) {
+            Some(hit) => hit,
+            None => {
+                let entry = CacheEntry {
+                    pattern,
+                    thumbnail: Thumbnail::render(&PATTERNS[pattern]),
+                    last_used: 0,
+                };
+                if self.entries.is_full() {
+                    let oldest = (0..self.entries.len())
+                        .min_by_key(|&i| self.entries[i].last_used)
+                        .unwrap();
+                    self.entries[oldest] = entry;
+                    oldest
+                } else {
+                    self.entries.push(entry).ok().unwrap();
+                    self.entries.len() - 1
+                }
+            }
+        };
+
+        self.entries[index].last_used = self.clock;
+        &self.entries[index].thumbnail
+    }
+}
+
+/// Browser of the pattern library
+pub struct Library {
+    selected: usize,
+    cache: ThumbnailCache,
+}
+
+impl Library {
+    pub const fn new() -> Self {
+        Self {
+            selected: 0,
+            cache: ThumbnailCache::new(),
+        }
+    }
+
+    pub fn selected(&self) -> &'static Pattern {
+        &PATTERNS[self.selected]
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + PATTERNS.len() - 1) % PATTERNS.len();
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % PATTERNS.len();
+    }
+
+    /// Draw the list of patterns, and the thumbnail of the selected one
+    pub fn draw(&mut self) {
+        display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+
+        let first = self.selected.saturating_sub(VISIBLE_ROWS - 1);
+        for (row, (i, pattern)) in PATTERNS
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(VISIBLE_ROWS)
+            .enumerate()
+        {
+            let (text, background) = if i == self.selected {
+                (Color::WHITE, Color::BLACK)
+            } else {
+                (Color::BLACK, Color::WHITE)
+            };
+            display::draw_string(
+                pattern.name,
+                Point::new(4, 2 + row as u16 * ROW_HEIGHT),
+                true,
+                text,
+                background,
+            );
+        }
+
+        // Against the right edge, halfway down, which the thumbnail fits in
+        let (width, height) = (
+            THUMBNAIL_WIDTH * THUMBNAIL_SCALE,
+            THUMBNAIL_HEIGHT * THUMBNAIL_SCALE,
+        );
+        let area = Rect::from_center(
+            SCREEN_WIDTH - width / 2 - 4,
+            SCREEN_HEIGHT / 2,
+            width,
+            height,
+        )
+        .unwrap();
+        let origin = Point::new(area.x, area.y);
+        self.cache.get(self.selected).draw(origin);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::GLIDER_GUN;
+
+    fn cached(cache: &ThumbnailCache) -> std::vec::Vec<usize> {
+        let mut patterns: std::vec::Vec<usize> = cache.entries.iter().map(|e| e.pattern).collect();
+        patterns.sort_unstable();
+        patterns
+    }
+
+    #[test]
+    fn hits_keep_a_single_entry() {
+        let mut cache = ThumbnailCache::new();
+        cache.get(0);
+        cache.get(0);
+        cache.get(0);
+        assert_eq!(cached(&cache), [0]);
+    }
+
+    #[test]
+    fn misses_evict_the_least_recently_used() {
+        let mut cache = ThumbnailCache::new();
+        for pattern in 0..CACHE_SIZE {
+            cache.get(pattern);
+        }
+        // 0 is used again, so 1 is the oldest one left
+        cache.get(0);
+        cache.get(CACHE_SIZE);
+        assert_eq!(cache.entries.len(), CACHE_SIZE);
+        let mut expected: std::vec::Vec<usize> = (0..=CACHE_SIZE).filter(|&p| p != 1).collect();
+        expected.sort_unstable();
+        assert_eq!(cached(&cache), expected);
+    }
+
+    #[test]
+    fn glider_thumbnail_is_unscaled() {
+        let thumbnail = Thumbnail::render(&PATTERNS[0]);
+        assert_eq!(thumbnail.rows[..4], [0b010, 0b100, 0b111, 0]);
+    }
+
+    #[test]
+    fn wide_patterns_are_scaled_down() {
+        // The glider gun is 36 cells wide, more than the 32 of a thumbnail
+        let thumbnail = Thumbnail::render(GLIDER_GUN);
+        assert!(thumbnail.rows.iter().all(|&row| row < 1 << 18));
+        assert!(thumbnail.rows.iter().any(|&row| row != 0));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::eadk::State;
+use crate::input::KEY_COUNT;
+use heapless::Vec;
+
+/// Most key presses and releases in a macro
+const MAX_EVENTS: usize = 256;
+/// Flag of the events releasing their key, the others press it
+const RELEASED: u32 = 1 << 31;
+
+/// Key sequences recorded to be played back, for demos
+///
+/// Each event is a key pressed or released, with the time since the start of the recording
+/// in milliseconds. A replay goes through them at the same pace, standing in for the
+/// keyboard, until it runs out of events or a key is pressed. Times are given by the caller,
+/// in milliseconds.
+pub struct MacroRecorder {
+    events: Vec<(u64, u32), MAX_EVENTS>,
+    recording: bool,
+    /// The macro ran out of room, the recording goes on without events until it's stopped
+    full: bool,
+    replaying: bool,
+    replay_pos: usize,
+    /// When the recording or the replay started
+    start_ms: u64,
+    /// Keys down in the recording, or in the replay
+    held: State,
+    /// Keys down on the keyboard during a replay, a press interrupts it
+    real: State,
+}
+
+impl MacroRecorder {
+    pub const fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            recording: false,
+            full: false,
+            replaying: false,
+            replay_pos: 0,
+            start_ms: 0,
+            held: State::NONE,
+            real: State::NONE,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Key presses in the macro
+    pub fn presses(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|&&(_, event)| event & RELEASED == 0)
+            .count()
+    }
+
+    /// Forget the last macro and record a new one, from the keys down in `state`
+    pub fn start_record(&mut self, state: State, now: u64) {
+        self.replaying = false;
+        self.events.clear();
+        self.recording = true;
+        self.full = false;
+        self.start_ms = now;
+        self.held = state;
+    }
+
+    /// Stop recording, without the keys still down which were pressed to stop it
+    ///
+    /// Once the macro is full, the keys pressed to stop it weren't recorded.
+    pub fn stop_record(&mut self) {
+        self.recording = false;
+        if core::mem::take(&mut self.full) {
+            return;
+        }
+        while let Some(&(_, event)) = self.events.last() {
+            if event & RELEASED != 0 || !self.held.key_down(event) {
+                break;
+            }
+            self.events.pop();
+        }
+    }
+
+    /// Record the keys pressed and released since the last state, when recording
+    ///
+    /// Returns whether the macro just got full. Nothing more is recorded then, but it's still
+    /// recording until stopped, so the keys stopping it don't start another one.
+    pub fn record(&mut self, state: State, now: u64) -> bool {
+        if !self.recording || self.full {
+            return false;
+        }
+        let time = now - self.start_ms;
+        for k in 0..KEY_COUNT {
+            let event = match (self.held.key_down(k), state.key_down(k)) {
+                (false, true) => k,
+                (true, false) => k | RELEASED,
+                _ => continue,
+            };
+            if self.events.push((time, event)).is_err() {
+                self.full = true;
+                return true;
+            }
+        }
+        self.held = state;
+        false
+    }
+
+    /// Play the macro back from its start, `real` being the keys down on the keyboard
+    pub fn start_replay(&mut self, real: State, now: u64) {
+        self.recording = false;
+        self.full = false;
+        self.replaying = !self.events.is_empty();
+        self.replay_pos = 0;
+        self.start_ms = now;
+        self.held = State::NONE;
+        self.real = real;
+    }
+
+    /// The next key to press or release, with [`RELEASED`], once its time has come
+    fn next_event(&mut self, now: u64) -> Option<u32> {
+        let &(time, event) = self.events.get(self.replay_pos)?;
+        if now - self.start_ms < time {
+            return None;
+        }
+        self.replay_pos += 1;
+        Some(event)
+    }
+
+    /// Keys down in the replay, standing in for `real`, or `None` once it's over
+    ///
+    /// Pressing any key on the keyboard stops the replay.
+    pub fn replay(&mut self, real: State, now: u64) -> Option<State> {
+        if !self.replaying {
+            return None;
+        }
+        let interrupted = real.pressed_since(&self.real).any_down();
+        self.real = real;
+        if interrupted || self.replay_pos == self.events.len() {
+            self.replaying = false;
+            return None;
+        }
+        while let Some(event) = self.next_event(now) {
+            self.held = if event & RELEASED != 0 {
+                self.held.without(event & !RELEASED)
+            } else {
+                self.held.with(event)
+            };
+        }
+        Some(self.held)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eadk::key;
+
+    /// Press and release `k`, once per millisecond from `now`
+    fn tap(recorder: &mut MacroRecorder, k: u32, now: u64) -> bool {
+        let pressed = recorder.record(State::NONE.with(k), now);
+        recorder.record(State::NONE, now + 1) || pressed
+    }
+
+    /// Whether a replay is going on with exactly `keys` down
+    fn replaying(replayed: Option<State>, keys: &[u32]) -> bool {
+        replayed.is_some_and(|state| (0..KEY_COUNT).all(|k| state.key_down(k) == keys.contains(&k)))
+    }
+
+    #[test]
+    fn recordings_stop_without_the_chord() {
+        let mut recorder = MacroRecorder::new();
+        let chord = State::NONE.with(key::SHIFT).with(key::BACK).with(key::HOME);
+        recorder.start_record(chord, 0);
+        recorder.record(State::NONE, 10);
+        assert!(!tap(&mut recorder, key::OK, 20));
+        recorder.record(chord, 30);
+        recorder.stop_record();
+        assert!(!recorder.is_recording());
+        assert_eq!(recorder.presses(), 1);
+    }
+
+    #[test]
+    fn full_macros_are_stopped_by_the_chord_not_restarted() {
+        let mut recorder = MacroRecorder::new();
+        recorder.start_record(State::NONE, 0);
+        let filled = (0..MAX_EVENTS as u64 / 2)
+            .filter(|&i| tap(&mut recorder, key::OK, 2 * i))
+            .count();
+        assert_eq!(filled, 0);
+        // The next press doesn't fit, the user is told once
+        assert!(recorder.record(State::NONE.with(key::UP), 1000));
+        assert!(!recorder.record(State::NONE, 1001));
+        assert!(recorder.is_recording());
+
+        // Stopping keeps every event, the last press included
+        let chord = State::NONE.with(key::SHIFT).with(key::BACK).with(key::HOME);
+        assert!(!recorder.record(chord, 1002));
+        recorder.stop_record();
+        assert!(!recorder.is_recording());
+        assert_eq!(recorder.presses(), MAX_EVENTS / 2);
+        assert_eq!(recorder.events.len(), MAX_EVENTS);
+    }
+
+    #[test]
+    fn replays_keep_the_pace_until_a_key_is_pressed() {
+        let mut recorder = MacroRecorder::new();
+        recorder.start_record(State::NONE, 100);
+        tap(&mut recorder, key::OK, 110);
+        tap(&mut recorder, key::UP, 150);
+        recorder.stop_record();
+
+        recorder.start_replay(State::NONE, 1000);
+        assert!(replaying(recorder.replay(State::NONE, 1005), &[]));
+        assert!(replaying(recorder.replay(State::NONE, 1010), &[key::OK]));
+        assert!(replaying(recorder.replay(State::NONE, 1011), &[]));
+        assert!(replaying(recorder.replay(State::NONE, 1050), &[key::UP]));
+        assert!(recorder.replay(State::NONE.with(key::DOWN), 1051).is_none());
+        assert!(recorder.replay(State::NONE, 1052).is_none());
+    }
+}
@@ -1,10 +1,104 @@
-#![no_main]
-#![no_std]
+// Tests run on the host, with the standard library
+#![cfg_attr(not(test), no_main)]
+#![cfg_attr(not(test), no_std)]
 
 pub mod eadk;
-use eadk::{display, key, keyboard, timing, Color, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use eadk::display::{Spinner, WipeDirection};
+use eadk::keyboard::TextScanner;
+use eadk::timing::PeriodicUpdate;
+use eadk::{
+    color_cycle, display, key, keyboard, timing, Color, Point, Rect, INFERNO_PALETTE,
+    SCREEN_HEIGHT, SCREEN_WIDTH,
+};
 
-use heapless::Vec;
+mod analysis;
+mod autosolve;
+mod bitgrid;
+mod caption;
+mod checkpoint;
+mod cone;
+mod config;
+mod cursor;
+mod diff;
+mod edge;
+mod edit;
+mod glide;
+mod golden;
+mod graph;
+mod hints;
+mod import;
+mod input;
+mod keypad;
+mod legend;
+mod library;
+mod macro_recorder;
+mod morphology;
+mod nearest;
+mod neighbors;
+mod outcome;
+mod packed;
+mod palette;
+mod patterns;
+mod period;
+mod perturb;
+mod ramp;
+mod rate;
+mod records;
+mod render;
+mod replay;
+mod rim;
+mod rng;
+mod rule;
+mod rule_editor;
+mod scene;
+mod selection;
+mod selftest;
+mod soup;
+mod step;
+mod symmetry;
+mod toast;
+mod tooltip;
+mod turbo;
+mod undo;
+mod viewport;
+use analysis::Analysis;
+use autosolve::{Criterion, BUDGETS_S};
+use bitgrid::{BitGrid, BlitMode};
+use caption::Caption;
+use checkpoint::Checkpoints;
+use config::{AppConfig, StartupMode};
+use cursor::Cursor;
+use edge::EdgeBuffer;
+use glide::Glide;
+use graph::Graph;
+use legend::RenderMode;
+use library::Library;
+use macro_recorder::MacroRecorder;
+use outcome::{Dodge, LastRunOutcome, RunWatch};
+use packed::PackedBoard;
+use palette::{ColorPalette, Theme, PALETTES, THEMES};
+use patterns::Pattern;
+use perturb::{PerturbMode, FLIP_COUNTS};
+use ramp::Ramp;
+use rate::{FramePacer, FrameSkip, GenRate, Tenths};
+use records::{Beaten, Records, SoupRun, SoupSettings};
+use render::RenderBudget;
+use replay::Recording;
+use rng::Rng;
+use rule::{Automaton, BoundaryMode, LifeLike, Rule};
+use rule_editor::RuleEditor;
+use scene::{Player, Scene, Step};
+use selection::CellRect;
+use step::{Progress, StepContext};
+use symmetry::{MirrorSource, SymmetryMode};
+use toast::Toast;
+use tooltip::Tooltip;
+use turbo::TurboLog;
+use undo::Undo;
+use viewport::{LogicalPos, ViewPos, Viewport, ZOOM_LEVELS};
+
+use core::fmt::{Display, Write};
+use heapless::{String, Vec};
 
 #[used]
 #[link_section = ".rodata.eadk_app_name"]
@@ -23,172 +117,3351 @@ const LINE_SIZE: u16 = SCREEN_WIDTH / CELL_SIZE;
 const COLUMN_SIZE: u16 = SCREEN_HEIGHT / CELL_SIZE;
 const BOARD_SIZE: usize = LINE_SIZE as usize * COLUMN_SIZE as usize;
 
+/// Time allowed to draw the changes of a generation before falling back to full repaints
+const RENDER_BUDGET_MS: u64 = 12;
+/// Generations a pattern is run for to measure its velocity
+const VELOCITY_GENERATIONS: u32 = 4;
+/// Generations a board is evolved for at most when analyzing it
+const ANALYSIS_MAX_GENERATIONS: u32 = 2000;
+/// Cells computed between two scans of the keyboard, in uncapped runs
+const STEP_CELL_BUDGET: usize = 1200;
+/// Calls to go on with a generation in parts after which it's considered stuck
+///
+/// A generation takes at most a call per budget of cells, this is many times that.
+const WATCHDOG_ADVANCES: u32 = 16 * (BOARD_SIZE / STEP_CELL_BUDGET + 1) as u32;
+/// Generations run to measure how long cells live
+const LIFESPAN_GENERATIONS: u32 = 200;
+/// Generations between two updates of the progress of a jump
+const JUMP_PROGRESS_EVERY: u32 = 100;
+/// Most generations a jump can be set to with the arrows, typing a number has no limit
+const JUMP_MAX_GENERATIONS: u32 = 100_000;
+/// Most cells of a pattern defined in the pattern editor
+const PATTERN_CELLS: usize = 256;
+/// Longest time between the two presses of EXE showing the RLE of a pattern
+const DOUBLE_PRESS_MS: u64 = 400;
+/// Lines of text of a RLE pattern fitting on the screen
+const RLE_LINES: usize = 14;
+/// How long BACK has to be held to get back to the editor from anywhere
+const EMERGENCY_HOLD_MS: u64 = 1000;
+/// Left of the spinner shown at the end of a line of stats during long computations
+const SPINNER_X: u16 = SCREEN_WIDTH - 20;
+/// Corner of the board telling that jumps are recorded
+const RECORDING_AREA: Rect = Rect {
+    x: SCREEN_WIDTH - 25,
+    y: 0,
+    width: 25,
+    height: 18,
+};
+/// Strips along the left and right sides of the screen tinted with the state
+const STATE_STRIPS: [Rect; 2] = [
+    Rect::new(0, 0, STATE_STRIP_WIDTH, SCREEN_HEIGHT),
+    Rect::new(
+        SCREEN_WIDTH - STATE_STRIP_WIDTH,
+        0,
+        STATE_STRIP_WIDTH,
+        SCREEN_HEIGHT,
+    ),
+];
+const STATE_STRIP_WIDTH: u16 = 3;
+/// Share of the theme's frontier tint in the color of the cells on the frontier
+const FRONTIER_TINT_AMOUNT: u8 = 128;
+/// How long the legend of a colored screen is shown the first time, in milliseconds
+const LEGEND_INTRO_MS: u64 = 2000;
+/// Erosions and dilations of a smoothing of the board, with ALPHA + ×
+const SMOOTH_PASSES: u8 = 1;
+/// Largest cell size, the height of the rows of cells pushed at once
+const MAX_CELL_SIZE: usize = ZOOM_LEVELS[ZOOM_LEVELS.len() - 1] as usize;
+/// Generations stepped at once in step by step, with × and ^
+const SHORT_STRIDE: u32 = 10;
+const LONG_STRIDE: u32 = 100;
+
 type Board<T> = [[T; COLUMN_SIZE as usize]; LINE_SIZE as usize];
 type OnBoard<T> = Vec<(T, T), BOARD_SIZE>;
 
+// Without a heap to box the pattern editor cells in, and there's a single state anyway
+#[allow(clippy::large_enum_variant)]
 enum AppState {
     Editor,
     Running,
     StepByStep,
+    Stats,
+    Library,
+    RuleEditor,
+    Import,
+    Diff,
+    /// Selecting a rect of cells, from an anchor to the pointer
+    Select,
+    /// Typing a number of generations to run at once
+    Jump,
+    /// Typing a caption to show over the board
+    Caption,
+    /// Local density of alive cells
+    HeatMap,
+    /// Playing a scripted scene
+    Scene(&'static Scene),
+    /// Choosing how to search for an interesting soup
+    Autosolve,
+    /// Best random soups of each rule
+    Records,
+    /// Playing back the boards recorded during the last jump, from a frame
+    Replay {
+        frame: usize,
+    },
+    /// Cells a cell can influence within some generations, or does influence if `actual`
+    Cone {
+        origin: LogicalPos,
+        generations: u32,
+        actual: bool,
+    },
+    /// A run without drawing stopped, offering to replay it or go on
+    TurboPaused,
+    /// Playing back the last run without drawing, at a generation
+    TurboReplay {
+        generation: u32,
+        playing: bool,
+    },
+    /// Running the board next to a copy of it run with Conway's rule, the diff of the two shown
+    Versus {
+        reference: PackedBoard,
+        playing: bool,
+    },
+    /// Map of the SHIFT chords on the keypad, those of the editor or of everywhere
+    Keypad {
+        in_editor: bool,
+    },
+    /// Defining a pattern alone, by cells relative to an origin
+    PatternEditor {
+        origin: LogicalPos,
+        cells: Vec<(i16, i16), PATTERN_CELLS>,
+    },
 }
 
-fn get_cell(board: &Board<bool>, (x, y): (i16, i16)) -> u8 {
-    if x < 0 || y < 0 || x > LINE_SIZE as i16 - 1 || y > COLUMN_SIZE as i16 - 1 {
-        0
-    } else {
-        board[x as usize][y as usize] as u8
+impl AppState {
+    /// Screens drawn over the board, which has to be redrawn when leaving them
+    fn covers_board(&self) -> bool {
+        matches!(
+            self,
+            AppState::Stats
+                | AppState::Library
+                | AppState::RuleEditor
+                | AppState::Import
+                | AppState::Diff
+                | AppState::Jump
+                | AppState::Caption
+                | AppState::HeatMap
+                | AppState::Cone { .. }
+                | AppState::PatternEditor { .. }
+                | AppState::Autosolve
+                | AppState::Records
+                | AppState::Replay { .. }
+                | AppState::TurboPaused
+                | AppState::TurboReplay { .. }
+                | AppState::Versus { .. }
+                | AppState::Keypad { .. }
+        )
     }
 }
 
-fn run_cell(board: &Board<bool>, (x, y): (usize, usize)) -> Option<bool> {
-    let (ix, iy) = (x as i16, y as i16);
+/// Order in which cells are updated during a generation
+#[derive(Clone, Copy, PartialEq)]
+enum UpdateMode {
+    /// Every cell sees the previous generation (classic Life)
+    Synchronous,
+    /// Cells update in place, row by row
+    AsyncRowMajor,
+    /// Cells update in place, in a random order
+    AsyncRandom,
+}
 
-    let neighbor_count = get_cell(&board, (ix - 1, iy - 1))
-        + get_cell(&board, (ix, iy - 1))
-        + get_cell(&board, (ix + 1, iy - 1))
-        + get_cell(&board, (ix - 1, iy))
-        + get_cell(&board, (ix + 1, iy))
-        + get_cell(&board, (ix - 1, iy + 1))
-        + get_cell(&board, (ix, iy + 1))
-        + get_cell(&board, (ix + 1, iy + 1));
+impl UpdateMode {
+    fn next(self) -> Self {
+        match self {
+            UpdateMode::Synchronous => UpdateMode::AsyncRowMajor,
+            UpdateMode::AsyncRowMajor => UpdateMode::AsyncRandom,
+            UpdateMode::AsyncRandom => UpdateMode::Synchronous,
+        }
+    }
+}
 
-    if neighbor_count == 3 && !board[x][y] {
-        Some(true)
-    } else if neighbor_count != 2 && neighbor_count != 3 && board[x][y] {
-        Some(false)
+/// State of a cell on the next generation, `None` if it doesn't change
+fn run_cell(
+    board: &Board<bool>,
+    (x, y): (usize, usize),
+    automaton: &impl Automaton,
+) -> Option<bool> {
+    let alive = automaton.next_state(board, x, y);
+    if alive != board[x][y] {
+        Some(alive)
     } else {
         None
     }
 }
 
-fn run_once(board: &mut Board<bool>) {
-    // Store wich cells have been done
-    let mut updated_board: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
-    // Store cells to update
-    let mut born: OnBoard<usize> = Vec::new();
-    let mut died: OnBoard<usize> = Vec::new();
+/// Cells changed by a generation
+struct Changes {
+    born: OnBoard<u8>,
+    died: OnBoard<u8>,
+}
 
-    board.iter().enumerate().for_each(|(x, col)| {
-        col.iter().enumerate().for_each(|(y, _)| {
-            if board[x][y] {
-                for dx in -1..=1 {
-                    for dy in -1..=1 {
-                        let c = (
-                            ((x as isize + dx).max(0).min(LINE_SIZE as isize - 1) as usize),
-                            ((y as isize + dy).max(0).min(COLUMN_SIZE as isize - 1) as usize),
-                        );
-                        if !updated_board[c.0][c.1] {
-                            updated_board[c.0][c.1] = true;
-                            match run_cell(&board, c) {
-                                Some(true) => born.push(c).unwrap(),
-                                Some(false) => died.push(c).unwrap(),
-                                _ => {}
-                            }
-                        }
-                    }
+impl Changes {
+    fn new() -> Self {
+        Self {
+            born: Vec::new(),
+            died: Vec::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.born.len() + self.died.len()
+    }
+
+    fn push(&mut self, (x, y): (usize, usize), alive: bool) {
+        if alive {
+            self.born.push((x as u8, y as u8)).unwrap();
+        } else {
+            self.died.push((x as u8, y as u8)).unwrap();
+        }
+    }
+}
+
+/// What generations need besides the board and the settings
+struct Simulation {
+    /// Only used by synchronous generations with dead edges
+    edge: EdgeBuffer,
+    rng: Rng,
+    /// Region the simulation is confined to, cells outside of it stay dead
+    mask: Option<Board<bool>>,
+    /// The automaton a generation in parts got stuck with, its generations are all run at
+    /// once since
+    reference_stepper: Option<LifeLike>,
+}
+
+/// Flip each cell with a chance of `flip_probability_per_mille` in 1000, without drawing it
+///
+/// Applied after every generation, it makes a stochastic variant of the rule. Cells outside
+/// of the mask stay dead, and flipped cells are added to the changes of the generation.
+/// Returns whether any cell was flipped.
+fn apply_noise(
+    board: &mut Board<bool>,
+    flip_probability_per_mille: u32,
+    simulation: &mut Simulation,
+    changes: &mut Changes,
+) -> bool {
+    // Not even drawing numbers, so runs without noise stay the same
+    if flip_probability_per_mille == 0 {
+        return false;
+    }
+    let mut flipped = false;
+    for x in 0..LINE_SIZE as usize {
+        for y in 0..COLUMN_SIZE as usize {
+            if simulation.rng.random_bool(flip_probability_per_mille, 1000)
+                && simulation.mask.as_ref().is_none_or(|mask| mask[x][y])
+            {
+                let cell = &mut board[x][y];
+                *cell = !*cell;
+                changes.push((x, y), *cell);
+                flipped = true;
+            }
+        }
+    }
+    flipped
+}
+
+/// Run a generation, without drawing it
+fn run_once(board: &mut Board<bool>, config: &AppConfig, simulation: &mut Simulation) -> Changes {
+    let changes = run_unmasked(board, config, simulation);
+    if let Some(mask) = &simulation.mask {
+        selection::apply_mask(board, mask);
+    }
+    changes
+}
+
+/// What a generation changes besides the board, to step back from it
+struct Rewind {
+    edge: EdgeBuffer,
+    rng: Rng,
+    checkpoints: Checkpoints,
+}
+
+impl Rewind {
+    /// Keep what the next generation can change besides the board
+    fn before(simulation: &Simulation, checkpoints: &Checkpoints) -> Self {
+        Self {
+            edge: simulation.edge.clone(),
+            rng: simulation.rng,
+            checkpoints: checkpoints.clone(),
+        }
+    }
+
+    /// Put back what was kept
+    fn restore(self, simulation: &mut Simulation, checkpoints: &mut Checkpoints) {
+        simulation.edge = self.edge;
+        simulation.rng = self.rng;
+        *checkpoints = self.checkpoints;
+    }
+}
+
+/// Where a stride of step by step began, to undo it as a whole
+///
+/// Kept apart from the undo of the editor, which the stride isn't an edit for.
+struct StrideStart {
+    generation: u32,
+    board: PackedBoard,
+    rewind: Rewind,
+}
+
+impl StrideStart {
+    fn before(board: &Board<bool>, generation: u32, rewind: Rewind) -> Self {
+        Self {
+            generation,
+            board: PackedBoard::pack(board),
+            rewind,
+        }
+    }
+
+    /// Back to the start of the stride, returns its generation
+    fn undo(
+        self,
+        board: &mut Board<bool>,
+        simulation: &mut Simulation,
+        checkpoints: &mut Checkpoints,
+    ) -> u32 {
+        self.board.unpack(board);
+        self.rewind.restore(simulation, checkpoints);
+        self.generation
+    }
+}
+
+/// Undo the generation that gave `changes`, and redraw their cells
+///
+/// Only cheaper than a snapshot of the board for the last generation, before it changes
+/// in any other way. The edge buffer, the generator and the checkpoints are put back
+/// as they were before it too.
+fn step_backward(
+    board: &mut Board<bool>,
+    (changes, rewind): (&Changes, Rewind),
+    (simulation, checkpoints): (&mut Simulation, &mut Checkpoints),
+    palette: &ColorPalette,
+    viewport: &Viewport,
+) {
+    rewind.restore(simulation, checkpoints);
+    let (born, died) = (&changes.born, &changes.died);
+    born.iter()
+        .for_each(|&(x, y)| board[x as usize][y as usize] = false);
+    died.iter()
+        .for_each(|&(x, y)| board[x as usize][y as usize] = true);
+    born.iter().chain(died.iter()).for_each(|&(x, y)| {
+        draw_cell(
+            board,
+            LogicalPos::new(x as u16, y as u16),
+            palette,
+            viewport,
+        )
+    });
+}
+
+/// Go on with the generation in progress for `cell_budget` cells, beginning one if needed
+///
+/// Returns the changes once the generation is over. Only synchronous generations without
+/// the edge buffer can be split, the others are run at once.
+///
+/// A generation taking far more parts than it can is dropped, the board left as it was
+/// before it, and run again at once by `automaton`, as all the next ones until the rule or
+/// the edges change.
+fn run_in_parts<A: Automaton>(
+    board: &mut Board<bool>,
+    config: &AppConfig,
+    simulation: &mut Simulation,
+    (in_progress, automaton): (&mut Option<StepContext<A>>, A),
+    cell_budget: usize,
+) -> Option<Changes> {
+    let current = LifeLike {
+        rule: config.rule,
+        boundary: config.boundary,
+    };
+    if simulation
+        .reference_stepper
+        .is_some_and(|stuck| stuck != current)
+    {
+        simulation.reference_stepper = None;
+    }
+    if in_progress
+        .as_ref()
+        .is_some_and(|step| step.advances() >= WATCHDOG_ADVANCES)
+    {
+        *in_progress = None;
+        simulation.reference_stepper = Some(current);
+    }
+    if !matches!(config.update_mode, UpdateMode::Synchronous)
+        || (config.edge_buffer && config.boundary == BoundaryMode::Dead)
+    {
+        return Some(run_once(board, config, simulation));
+    }
+
+    let changes = if simulation.reference_stepper.is_some() {
+        run_once_sync(board, &automaton)
+    } else {
+        let step = in_progress.get_or_insert_with(|| StepContext::begin(board, automaton));
+        if let Progress::InProgress = step.advance(cell_budget) {
+            return None;
+        }
+        in_progress.take()?.commit(board)
+    };
+    if let Some(mask) = &simulation.mask {
+        selection::apply_mask(board, mask);
+    }
+    Some(changes)
+}
+
+/// Run exactly `n` generations, without drawing them
+fn evolve_n(board: &mut Board<bool>, config: &AppConfig, simulation: &mut Simulation, n: u32) {
+    evolve_n_with_progress(board, config, simulation, n, n, |_| true);
+}
+
+/// Run `n` generations without drawing them, calling `progress` every `every` generations
+///
+/// `progress` gets the number of generations run so far, and returns whether to go on.
+/// Returns the number of generations run.
+fn evolve_n_with_progress(
+    board: &mut Board<bool>,
+    config: &AppConfig,
+    simulation: &mut Simulation,
+    n: u32,
+    every: u32,
+    mut progress: impl FnMut(u32) -> bool,
+) -> u32 {
+    let mut report = PeriodicUpdate::new(every);
+    for generation in 1..=n {
+        run_once(board, config, simulation);
+        if report.tick() && !progress(generation) {
+            return generation;
+        }
+    }
+    n
+}
+
+/// Run a generation over the whole board, whatever the mask
+fn run_unmasked(
+    board: &mut Board<bool>,
+    config: &AppConfig,
+    simulation: &mut Simulation,
+) -> Changes {
+    let automaton = LifeLike {
+        rule: config.rule,
+        boundary: config.boundary,
+    };
+    let mut changes = Changes::new();
+    match config.update_mode {
+        UpdateMode::Synchronous if config.edge_buffer && config.boundary == BoundaryMode::Dead => {
+            return simulation.edge.run_once(board, config.rule)
+        }
+        UpdateMode::Synchronous => return run_once_sync(board, &automaton),
+        UpdateMode::AsyncRowMajor => {
+            for y in 0..COLUMN_SIZE as usize {
+                for x in 0..LINE_SIZE as usize {
+                    run_cell_in_place(board, (x, y), &automaton, &mut changes);
                 }
             }
-        });
+        }
+        UpdateMode::AsyncRandom => {
+            // Fisher-Yates shuffle of the cell indices
+            let mut order = [0u16; BOARD_SIZE];
+            order
+                .iter_mut()
+                .enumerate()
+                .for_each(|(i, c)| *c = i as u16);
+            for i in (1..BOARD_SIZE).rev() {
+                order.swap(i, simulation.rng.below(i as u32 + 1) as usize);
+            }
+
+            order.iter().for_each(|&i| {
+                let i = i as usize;
+                run_cell_in_place(
+                    board,
+                    (i / COLUMN_SIZE as usize, i % COLUMN_SIZE as usize),
+                    &automaton,
+                    &mut changes,
+                );
+            });
+        }
+    }
+    changes
+}
+
+fn run_cell_in_place(
+    board: &mut Board<bool>,
+    (x, y): (usize, usize),
+    automaton: &impl Automaton,
+    changes: &mut Changes,
+) {
+    if let Some(alive) = run_cell(board, (x, y), automaton) {
+        board[x][y] = alive;
+        changes.push((x, y), alive);
+    }
+}
+
+/// Run a generation where every cell sees the previous one, without drawing it
+fn run_once_sync(board: &mut Board<bool>, automaton: &impl Automaton) -> Changes {
+    let mut step = StepContext::begin(board, automaton);
+    step.advance(usize::MAX);
+    step.commit(board)
+}
+
+/// Turn on the cells of a pattern, with its top left corner at `at`
+///
+/// Cells falling outside of the board are dropped.
+fn place_pattern(board: &mut Board<bool>, pattern: &Pattern, at: LogicalPos) {
+    BitGrid::from_pattern(pattern).blit_onto(board, (at.x as i16, at.y as i16), BlitMode::Or);
+}
+
+/// Bring to life the dead neighbors of every alive cell
+///
+/// Not a generation: it grows a one cell halo around the alive regions, so the cells
+/// around them are considered by rules that would otherwise lose them at the edges.
+fn expand_border_cells(board: &mut Board<bool>) {
+    let original = *board;
+    for (x, column) in original.iter().enumerate() {
+        for (y, _) in column.iter().enumerate().filter(|(_, &alive)| alive) {
+            let rows = y.saturating_sub(1)..(y + 2).min(COLUMN_SIZE as usize);
+            for neighbor_column in &mut board[x.saturating_sub(1)..(x + 2).min(LINE_SIZE as usize)]
+            {
+                neighbor_column[rows.clone()].fill(true);
+            }
+        }
+    }
+}
+
+/// Draw a cell, if it's in view
+fn draw_cell(board: &Board<bool>, cell: LogicalPos, palette: &ColorPalette, viewport: &Viewport) {
+    debug_assert!(cell.x < LINE_SIZE && cell.y < COLUMN_SIZE);
+    if let Some(rect) = viewport.cell_rect(cell) {
+        display::push_rect_uniform(
+            viewport.inside_grid(rect),
+            palette.cell(board[cell.x as usize][cell.y as usize], cell),
+        );
+    }
+}
+
+/// Draw the crosshair of the precision mode through the center of the pointer
+fn draw_crosshair(pointer: Cursor, palette: &ColorPalette, viewport: &Viewport) {
+    if let Some(cell) = viewport.cell_rect(pointer.position()) {
+        display::draw_crosshair(
+            Point::new(cell.x + cell.width / 2, cell.y + cell.height / 2),
+            palette.cursor,
+        );
+    }
+}
+
+/// Redraw the row and column of cells the crosshair went through
+fn erase_crosshair(
+    board: &Board<bool>,
+    pointer: Cursor,
+    palette: &ColorPalette,
+    viewport: &Viewport,
+) {
+    let LogicalPos { x: column, y: row } = pointer.position();
+    for x in 0..LINE_SIZE {
+        draw_cell(board, LogicalPos::new(x, row), palette, viewport);
+    }
+    for y in 0..COLUMN_SIZE {
+        draw_cell(board, LogicalPos::new(column, y), palette, viewport);
+    }
+}
+
+/// Move the pointer with the arrows, returns where it was if it moved
+///
+/// The pointer stops at the edges, unless the board wraps around.
+fn move_pointer(
+    keyboard_state: &eadk::State,
+    pointer: &mut Cursor,
+    boundary: BoundaryMode,
+) -> Option<Cursor> {
+    let previous = *pointer;
+    let keys = input::plain(*keyboard_state);
+    let dy = if keys.key_down(key::UP) {
+        -1
+    } else if keys.key_down(key::DOWN) {
+        1
+    } else {
+        0
+    };
+    let dx = if keys.key_down(key::LEFT) {
+        -1
+    } else if keys.key_down(key::RIGHT) {
+        1
+    } else {
+        0
+    };
+    *pointer = pointer.moved((dx, dy), boundary);
+    if *pointer != previous {
+        Some(previous)
+    } else {
+        None
+    }
+}
+
+/// Where a shortcut sends the pointer: 0 to the center, SHIFT + 0 to the nearest alive
+/// cell, ALPHA + arrows to the edges
+fn jump_target(
+    board: &Board<bool>,
+    keyboard_state: &eadk::State,
+    pressed: &eadk::State,
+    pointer: Cursor,
+) -> Option<Cursor> {
+    if pressed.key_down(key::ZERO) {
+        if keyboard_state.key_down(key::SHIFT) {
+            let LogicalPos { x, y } = nearest::nearest_live_cell(board, pointer.position())?;
+            return Some(Cursor::clamped((x as i32, y as i32)));
+        }
+        return Some(Cursor::CENTER);
+    }
+    if !keyboard_state.key_down(key::ALPHA) {
+        return None;
+    }
+    let LogicalPos { x, y } = pointer.position();
+    let target = Cursor::clamped((
+        if pressed.key_down(key::LEFT) {
+            0
+        } else if pressed.key_down(key::RIGHT) {
+            LINE_SIZE as i32 - 1
+        } else {
+            x as i32
+        },
+        if pressed.key_down(key::UP) {
+            0
+        } else if pressed.key_down(key::DOWN) {
+            COLUMN_SIZE as i32 - 1
+        } else {
+            y as i32
+        },
+    ));
+    (target != pointer).then_some(target)
+}
+
+/// Draw the editor cursor on the pointed cell, as a cross inverting the cell
+///
+/// Inverted colors are visible on any background.
+#[cfg(feature = "shadow_fb")]
+fn draw_cursor(board: &Board<bool>, pointer: Cursor, palette: &ColorPalette, viewport: &Viewport) {
+    if let Some(cell) = viewport.cell_rect(pointer.position()) {
+        let (size, arm) = (cell.width, cell.width / 4);
+        // Inverting twice gives the cell back, so start from the cell itself
+        draw_cell(board, pointer.position(), palette, viewport);
+        display::push_rect_xor(Rect::new(cell.x, cell.y + arm, size, size - 2 * arm));
+        display::push_rect_xor(Rect::new(cell.x + arm, cell.y, size - 2 * arm, arm));
+        display::push_rect_xor(Rect::new(
+            cell.x + arm,
+            cell.y + size - arm,
+            size - 2 * arm,
+            arm,
+        ));
+    }
+}
+
+/// Turn what is on screen into a board, a cell for each `CELL_SIZE`×`CELL_SIZE` block
+///
+/// The center pixel of each block is sampled, and bright ones make alive cells.
+#[cfg(feature = "shadow_fb")]
+fn screenshot_to_board(board: &mut Board<bool>) {
+    for (x, column) in board.iter_mut().enumerate() {
+        for (y, cell) in column.iter_mut().enumerate() {
+            let pixel = display::pixel(
+                x as u16 * CELL_SIZE + CELL_SIZE / 2,
+                y as u16 * CELL_SIZE + CELL_SIZE / 2,
+            );
+            *cell = pixel.grayscale() > 128;
+        }
+    }
+}
+
+/// Draw the editor cursor on the pointed cell, filled with the cursor color
+#[cfg(not(feature = "shadow_fb"))]
+fn draw_cursor(_board: &Board<bool>, pointer: Cursor, palette: &ColorPalette, viewport: &Viewport) {
+    if let Some(cell) = viewport.cell_rect(pointer.position()) {
+        display::push_rect_uniform(viewport.inside_grid(cell), palette.cursor);
+    }
+}
+
+/// Render the board as an image, one pixel per cell
+///
+/// Pixels are in row-major order, so the image can be pushed as is in a
+/// `LINE_SIZE`×`COLUMN_SIZE` rect with [`display::push_rect`].
+fn board_to_image(
+    board: &Board<bool>,
+    alive_color: Color,
+    dead_color: Color,
+) -> [Color; BOARD_SIZE] {
+    let mut image = [dead_color; BOARD_SIZE];
+    image.iter_mut().enumerate().for_each(|(i, pixel)| {
+        if board[i % LINE_SIZE as usize][i / LINE_SIZE as usize] {
+            *pixel = alive_color;
+        }
     });
+    image
+}
+
+/// Redraw the cells under a part of the screen
+fn redraw_area(board: &Board<bool>, area: Rect, palette: &ColorPalette, viewport: &Viewport) {
+    let (columns, rows) = viewport.size();
+    let size = viewport.cell_size;
+    for x in area.x / size..(area.x + area.width).div_ceil(size).min(columns) {
+        for y in area.y / size..(area.y + area.height).div_ceil(size).min(rows) {
+            let cell = viewport.logical_pos(ViewPos { x, y });
+            draw_cell(board, cell, palette, viewport);
+        }
+    }
+    // The lines crossing the area were covered too
+    if viewport.grid {
+        display::draw_grid(viewport.cell_size, columns, rows, palette.grid);
+    }
+}
+
+/// Redraw the part of the board in view, a line of pixels at a time
+fn draw_board(board: &Board<bool>, palette: &ColorPalette, viewport: &Viewport) {
+    let mut image = board_to_image(board, palette.live_cell, palette.dead_cell);
+    for (i, pixel) in image.iter_mut().enumerate() {
+        let cell = LogicalPos::new(i as u16 % LINE_SIZE, i as u16 / LINE_SIZE);
+        if rim::contains(cell) {
+            *pixel = palette.cell(board[cell.x as usize][cell.y as usize], cell);
+        }
+    }
+    draw_image(&image, viewport);
+    if viewport.grid {
+        let (columns, rows) = viewport.size();
+        display::draw_grid(viewport.cell_size, columns, rows, palette.grid);
+    }
+}
+
+/// Draw the part in view of an image of one pixel per cell, scaled to the cell size
+///
+/// Each row of cells is pushed at once, its line of pixels repeated down the cells, so a
+/// full redraw takes a call to the system per row of cells rather than per line of pixels.
+fn draw_image(image: &[Color; BOARD_SIZE], viewport: &Viewport) {
+    let (columns, rows) = viewport.size();
+    let (size, width) = (viewport.cell_size as usize, SCREEN_WIDTH as usize);
+    let mut block = [Color::BLACK; SCREEN_WIDTH as usize * MAX_CELL_SIZE];
+    for y in 0..rows {
+        let start =
+            (viewport.origin.y + y) as usize * LINE_SIZE as usize + viewport.origin.x as usize;
+        image[start..start + columns as usize]
+            .iter()
+            .enumerate()
+            .for_each(|(x, &color)| block[x * size..(x + 1) * size].fill(color));
+        for dy in 1..size {
+            block.copy_within(0..width, dy * width);
+        }
+        display::push_rect(
+            Rect::new(0, y * viewport.cell_size, SCREEN_WIDTH, viewport.cell_size),
+            &block[..width * size],
+        );
+    }
+}
 
-    born.into_iter().for_each(|(x, y)| {
-        board[x][y] = true;
-        draw_cell(&board, (x as u16, y as u16));
+/// Come back to the board from a screen drawn over it
+fn reveal_board(board: &Board<bool>, palette: &ColorPalette, viewport: &Viewport) {
+    display::transition_wipe(
+        palette.dead_cell,
+        |strip| redraw_area(board, strip, palette, viewport),
+        WipeDirection::Down,
+        250,
+    );
+}
+
+fn draw_stat(row: u16, label: &str, value: impl Display) {
+    let mut line: String<40> = String::new();
+    write!(line, "{}: {}\0", label, value).unwrap();
+    display::draw_string(
+        &line,
+        Point::new(10, 10 + row * 22),
+        true,
+        Color::BLACK,
+        Color::WHITE,
+    );
+}
+
+/// Draw where the board ends up and how long its cells live, over the stats
+///
+/// `long_period` is the period found by a longer search, when the analysis found none
+fn draw_analysis(analysis: &Analysis, long_period: Option<u32>, life_expectancy: f32) {
+    display::push_rect_uniform(Rect::new(0, 10 + 6 * 22, SCREEN_WIDTH, 22), Color::WHITE);
+    match (analysis.generations_to_stabilize, analysis.detected_period) {
+        (Some(generation), Some(period)) => draw_stat(
+            6,
+            "Stable after",
+            format_args!("{} (period {})", generation, period),
+        ),
+        _ => match analysis.drift {
+            // Kept moving without changing its population, like a spaceship
+            Some((dx, dy)) if dx != 0. || dy != 0. => draw_stat(
+                6,
+                "Moving",
+                format_args!("{:.1}, {:.1} per {} gen", dx, dy, analysis::DRIFT_EVERY),
+            ),
+            _ => match long_period {
+                Some(period) => draw_stat(6, "Repeats", format_args!("every {} gen", period)),
+                None => draw_stat(
+                    6,
+                    "Stable after",
+                    format_args!("> {}", ANALYSIS_MAX_GENERATIONS),
+                ),
+            },
+        },
+    }
+    draw_stat(7, "Final pop.", analysis.final_population);
+    draw_stat(8, "Peak pop.", analysis.peak_population);
+    draw_stat(
+        9,
+        "Lifespan",
+        format_args!("{} gen", Tenths((life_expectancy * 10.) as u32)),
+    );
+}
+
+/// Render the share of alive cells in the 5×5 square around each cell as an image, one
+/// pixel per cell, in the Inferno colormap from black for empty to pale yellow for full
+///
+/// Pixels are in the same order as [`board_to_image`].
+fn density_map_image(board: &Board<bool>) -> [Color; BOARD_SIZE] {
+    let density = analysis::neighborhood_density(board);
+    let mut image = [Color::BLACK; BOARD_SIZE];
+    image.iter_mut().enumerate().for_each(|(i, pixel)| {
+        *pixel = density_color(density[i % LINE_SIZE as usize][i / LINE_SIZE as usize]);
     });
-    died.into_iter().for_each(|(x, y)| {
-        board[x][y] = false;
-        draw_cell(&board, (x as u16, y as u16));
+    image
+}
+
+/// Color of a cell of the density map with `cells` alive around it, out of 25
+fn density_color(cells: u8) -> Color {
+    Color::gradient_map((cells as u16 * 255 / 25) as u8, &INFERNO_PALETTE)
+}
+
+/// Draw the diff of the board and of its copy run with Conway's rule, and how alike they are
+fn draw_versus(board: &Board<bool>, reference: &PackedBoard, generation: u32, theme: &Theme) {
+    let diff = diff::diff_snapshots(reference, &PackedBoard::pack(board));
+    diff::draw_diff(&diff, theme);
+    let mut text: String<40> = String::new();
+    write!(
+        text,
+        "Gen {}, {}% alike with B3/S23\0",
+        generation,
+        diff::likeness(&diff)
+    )
+    .unwrap();
+    display::draw_string(&text, Point::new(2, 2), false, Color::BLACK, Color::WHITE);
+}
+
+/// Draw the cells `origin` can influence within `generations`, or the ones it does if `actual`
+fn draw_cone(
+    board: &Board<bool>,
+    (origin, generations, actual): (LogicalPos, u32, bool),
+    config: &AppConfig,
+    viewport: &Viewport,
+) {
+    let cone = if actual {
+        let automaton = LifeLike {
+            rule: config.rule,
+            boundary: config.boundary,
+        };
+        cone::actual_cone(board, origin, generations, &automaton)
+    } else {
+        cone::light_cone(
+            origin,
+            generations,
+            config.rule.neighborhood,
+            config.boundary,
+        )
+    };
+    cone::draw(
+        board,
+        &cone,
+        (generations, actual),
+        config.palette(),
+        viewport,
+    );
+}
+
+/// Draw the board with the cells on the frontier between alive and dead regions tinted
+fn draw_frontier(
+    board: &Board<bool>,
+    boundary: BoundaryMode,
+    (palette, theme): (&ColorPalette, &Theme),
+    viewport: &Viewport,
+) {
+    let frontier = analysis::edge_detect(board, boundary);
+    let mut image = [Color::BLACK; BOARD_SIZE];
+    image.iter_mut().enumerate().for_each(|(i, pixel)| {
+        let cell = LogicalPos::new(i as u16 % LINE_SIZE, i as u16 / LINE_SIZE);
+        let (x, y) = (cell.x as usize, cell.y as usize);
+        *pixel = palette.cell(board[x][y], cell);
+        if frontier[x][y] {
+            *pixel = pixel.mix(theme.frontier, FRONTIER_TINT_AMOUNT);
+        }
     });
+    draw_image(&image, viewport);
+}
+
+/// Draw the density map of the whole board, each cell scaled to fill the screen
+fn draw_heat_map(board: &Board<bool>) {
+    draw_image(&density_map_image(board), &Viewport::FULL);
 }
 
-fn draw_cell(board: &Board<bool>, (x, y): (u16, u16)) {
+/// Tell that a run isn't drawn, the screen looks frozen otherwise, and how far back it can
+/// be replayed
+fn draw_turbo_hud(log: &TurboLog, per_second: Option<u32>, reference_stepper: bool) {
+    let (_, glyph_height) = display::glyph_size(false);
+    let (first, generation) = log.range();
+    let mut text: String<80> = String::new();
+    write!(text, "TURBO gen {}", generation).unwrap();
+    if let Some(per_second) = per_second {
+        write!(text, ", {} gen/s", Tenths(per_second)).unwrap();
+    }
+    write!(text, ", {} back", generation - first).unwrap();
+    if reference_stepper {
+        text.push_str(", fallback stepper").unwrap();
+    }
+    text.push('\0').unwrap();
     display::push_rect_uniform(
-        Rect {
-            x: x * CELL_SIZE,
-            y: y * CELL_SIZE,
-            width: CELL_SIZE,
-            height: CELL_SIZE,
-        },
-        if board[x as usize][y as usize] {
-            Color::BLACK
-        } else {
-            Color::WHITE
-        },
+        Rect::new(0, 0, SCREEN_WIDTH, glyph_height + 4),
+        Color::BLACK,
     );
+    // Changes color with each update, so it doesn't look stuck either
+    let color = color_cycle::RAINBOW.current();
+    display::draw_string(&text, Point::new(2, 2), false, color, Color::BLACK);
 }
 
-#[no_mangle]
-pub fn main() {
+/// Tint of the state strips in a state, `None` where they aren't shown, like in the editor
+fn state_tint(state: &AppState, theme: &Theme) -> Option<Color> {
+    match state {
+        AppState::Running | AppState::TurboReplay { playing: true, .. } => Some(theme.running),
+        AppState::StepByStep => Some(theme.stepping),
+        AppState::TurboPaused | AppState::TurboReplay { playing: false, .. } => Some(theme.paused),
+        _ => None,
+    }
+}
+
+/// Tell that jumps are recorded, in a color pulsing with each frame it's drawn
+fn draw_recording_indicator() {
+    display::push_rect_uniform(RECORDING_AREA, Color::BLACK);
+    display::draw_string(
+        "REC\0",
+        Point::new(RECORDING_AREA.x + 2, RECORDING_AREA.y + 2),
+        false,
+        color_cycle::TRAFFIC_LIGHT.current(),
+        Color::BLACK,
+    );
+}
+
+/// Offer to replay a run that wasn't drawn, or to go on with it
+fn draw_turbo_paused(log: &TurboLog) {
+    display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+    let (first, last) = log.range();
+    draw_stat(0, "Paused at", format_args!("gen {}", last));
+    draw_stat(1, "Replay from", format_args!("gen {}", first));
+    if log.truncated() {
+        display::draw_string(
+            "The start of the run was dropped\0",
+            Point::new(10, 10 + 2 * 22),
+            false,
+            Color::BLACK,
+            Color::WHITE,
+        );
+    }
+    display::draw_string(
+        "Replay: EXE play  LEFT/RIGHT scrub  UP/DOWN speed\0",
+        Point::new(10, SCREEN_HEIGHT - 40),
+        false,
+        Color::BLACK,
+        Color::WHITE,
+    );
+    display::draw_string(
+        "EXE: replay  OK: continue  BACK: stop\0",
+        Point::new(10, SCREEN_HEIGHT - 20),
+        false,
+        Color::BLACK,
+        Color::WHITE,
+    );
+}
+
+fn draw_jump(input: &TextScanner) {
+    display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+    let cursor = if input.is_selected() { "" } else { "_" };
+    draw_stat(
+        0,
+        "Run",
+        format_args!("{}{} generations", input.as_str(), cursor),
+    );
+    display::draw_string(
+        "LEFT/RIGHT: -/+  EXE: run  BACK: cancel\0",
+        Point::new(10, SCREEN_HEIGHT - 20),
+        false,
+        Color::BLACK,
+        Color::WHITE,
+    );
+}
+
+/// Draw a pattern being defined alone, with its origin and the pointer
+fn draw_pattern_editor(
+    (origin, cells): (Cursor, &[(i16, i16)]),
+    pointer: Cursor,
+    (palette, theme): (&ColorPalette, &Theme),
+    viewport: &Viewport,
+) {
+    let mut pattern: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    cells
+        .iter()
+        .filter_map(|&(dx, dy)| origin.offset((dx as i32, dy as i32)))
+        .for_each(|cell| *cell.cell_mut(&mut pattern) = true);
+    draw_board(&pattern, palette, viewport);
+    if let Some(rect) = viewport.cell_rect(origin.position()) {
+        display::push_rect_uniform(rect, theme.highlight);
+    }
+    draw_cursor(&pattern, pointer, palette, viewport);
+}
+
+/// Draw a RLE pattern wrapped to the width of the screen, to be copied
+fn draw_rle(rle: &str) {
+    display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+    let (glyph_width, glyph_height) = display::glyph_size(false);
+    let columns = ((SCREEN_WIDTH - 20) / glyph_width) as usize;
+    rle.lines()
+        .flat_map(|line| line.as_bytes().chunks(columns))
+        .take(RLE_LINES)
+        .enumerate()
+        .for_each(|(row, chunk)| {
+            let mut line: String<64> = String::new();
+            // RLE patterns are ASCII, so chunks are always whole characters
+            line.push_str(core::str::from_utf8(chunk).unwrap_or(""))
+                .unwrap();
+            line.push('\0').unwrap();
+            display::draw_string(
+                &line,
+                Point::new(10, 10 + row as u16 * glyph_height),
+                false,
+                Color::BLACK,
+                Color::WHITE,
+            );
+        });
+    display::draw_string(
+        "BACK: back to the pattern\0",
+        Point::new(10, SCREEN_HEIGHT - 20),
+        false,
+        Color::BLACK,
+        Color::WHITE,
+    );
+}
+
+fn draw_autosolve(budget_s: u64, criterion: Criterion) {
+    display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+    draw_stat(0, "Search for", format_args!("{} s", budget_s));
+    draw_stat(1, "Keep the", criterion.name());
+    display::draw_string(
+        "UP/DOWN: time  LEFT/RIGHT: criterion\0",
+        Point::new(10, SCREEN_HEIGHT - 40),
+        false,
+        Color::BLACK,
+        Color::WHITE,
+    );
+    display::draw_string(
+        "EXE: search  BACK: cancel\0",
+        Point::new(10, SCREEN_HEIGHT - 20),
+        false,
+        Color::BLACK,
+        Color::WHITE,
+    );
+}
+
+/// Announce the records a soup just beat
+fn show_record(toast: &mut Toast, beaten: &Beaten) {
+    let mut text: String<48> = String::new();
+    match (beaten.settling, beaten.peak) {
+        (Some(generations), Some(cells)) => write!(
+            text,
+            "New records: {} generations, {} cells!\0",
+            generations, cells
+        ),
+        (Some(generations), None) => write!(text, "New record: {} generations!\0", generations),
+        (None, Some(cells)) => write!(text, "New record: {} cells at once!\0", cells),
+        (None, None) => return,
+    }
+    .unwrap();
+    toast.show(&text, 3000);
+}
+
+/// Draw how far a jump went, as a bar under its input
+fn draw_progress(done: u32, total: u32) {
+    let width = SCREEN_WIDTH - 20;
+    let filled = (done as u64 * width as u64 / total.max(1) as u64) as u16;
+    display::push_rect_uniform(Rect::new(10, 40, filled, 10), Color::BLACK);
+    display::push_rect_uniform(
+        Rect::new(10 + filled, 40, width - filled, 10),
+        Color::from_rgb888(200, 200, 200),
+    );
+}
+
+fn draw_stats(
+    board: &Board<bool>,
+    generation: u32,
+    outcome: Option<&LastRunOutcome>,
+    automaton: &LifeLike,
+    (checkpoints, rate, reference_stepper): (&Checkpoints, &GenRate, bool),
+    caption: &str,
+) {
     display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+    draw_stat(
+        0,
+        "Population",
+        format_args!(
+            "{} ({} edges)",
+            analysis::population(board),
+            analysis::perimeter_length(board, automaton.boundary)
+        ),
+    );
+    draw_stat(
+        1,
+        "Components",
+        analysis::count_components(board, automaton.boundary),
+    );
+    match outcome {
+        Some(outcome) => draw_stat(
+            2,
+            "Generation",
+            format_args!("{}, {}", outcome.generation, outcome.reason),
+        ),
+        None => draw_stat(2, "Generation", generation),
+    }
+    // The one ln resumes in the editor
+    match checkpoints.newest() {
+        Some((_, saved_generation, _)) => draw_stat(
+            3,
+            "Checkpoint",
+            format_args!(
+                "gen {}, {} ms copy",
+                saved_generation, checkpoints.last_copy_ms
+            ),
+        ),
+        None => draw_stat(3, "Checkpoint", "none yet"),
+    }
+    // The fallback is worth a bug report
+    if reference_stepper {
+        draw_stat(
+            4,
+            "Speed",
+            format_args!("{} gen/s, fallback", Tenths(rate.last)),
+        );
+    } else {
+        draw_stat(4, "Speed", format_args!("{} gen/s", Tenths(rate.last)));
+    }
+    let (born, died) = analysis::pending_changes(board, automaton);
+    draw_stat(5, "Next gen", format_args!("+{} -{}", born, died));
+    draw_stat(6, "Evolution", "EXE to analyze");
+    if !caption.is_empty() {
+        // Below the rows of the analysis, left of the thumbnail
+        let (_, glyph_height) = display::glyph_size(false);
+        let mut line: String<{ caption::MAX_LENGTH + 3 }> = String::new();
+        write!(line, "\"{}\"\0", caption).unwrap();
+        display::draw_string(
+            &line,
+            Point::new(10, SCREEN_HEIGHT - glyph_height),
+            false,
+            Color::BLACK,
+            Color::WHITE,
+        );
+    }
+
+    // Thumbnail of the board, one pixel per cell
+    let thumbnail = Rect::new(
+        SCREEN_WIDTH - LINE_SIZE - 10,
+        SCREEN_HEIGHT - COLUMN_SIZE - 10,
+        LINE_SIZE,
+        COLUMN_SIZE,
+    );
+    display::push_rect_uniform(
+        Rect::new(
+            thumbnail.x - 1,
+            thumbnail.y - 1,
+            LINE_SIZE + 2,
+            COLUMN_SIZE + 2,
+        ),
+        Color::BLACK,
+    );
+    display::push_rect(
+        thumbnail,
+        &board_to_image(board, Color::BLACK, Color::WHITE),
+    );
+}
+
+#[cfg_attr(not(test), no_mangle)]
+pub fn main() {
+    let mut config = AppConfig::load();
 
     let mut state: AppState = AppState::Editor;
-    let mut pointer: (u16, u16) = (LINE_SIZE / 2, COLUMN_SIZE / 2);
+    let mut generation: u32 = 0;
+    let mut checkpoints = Checkpoints::load();
+    let mut simulation = Simulation {
+        edge: EdgeBuffer::new(),
+        rng: Rng::new(eadk::random()),
+        mask: None,
+        reference_stepper: None,
+    };
+    let mut pointer = Cursor::CENTER;
+    let mut viewport = Viewport::FULL;
+    // Whether a crosshair is drawn through the pointer in the editor
+    let mut precision = false;
+    // The pointer sliding to where a shortcut sent it
+    let mut glide: Option<Glide> = None;
+    // Part of the screen under the SHIFT hints, while they are shown
+    let mut hints_area: Option<Rect> = None;
+    let mut graph = Graph::new();
+    let mut show_graph = false;
+    // Whether runs tint the cells between alive and dead regions
+    let mut show_frontier = false;
+    // Colored mode of the screen, and the modes whose legend was already shown by itself
+    let mut render_mode: Option<RenderMode> = None;
+    let mut legends_introduced = 0u8;
+    // Whether the legend is shown by hand, or until when it's shown by itself
+    let mut show_legend = false;
+    let mut legend_until_ms = 0;
+    // Part of the screen under the legend, while it's shown
+    let mut legend_area: Option<Rect> = None;
+    let mut render = RenderBudget::new(RENDER_BUDGET_MS);
+    let mut rate = GenRate::new();
+    let mut pacer = FramePacer::new();
+    let mut frame_skip = FrameSkip::new();
+    let mut library = Library::new();
+    let mut rule_editor = RuleEditor::new();
+    let mut staging: Option<import::Staged> = None;
+    let mut undo = Undo::new();
+    let mut toast = Toast::new();
+    let mut tooltip = Tooltip::new();
+    let mut symmetry = SymmetryMode::Horizontal;
+    // Index in FLIP_COUNTS of the number of cells flipped by a perturbation
+    let mut flip_count = 0;
+    let mut perturb_mode = PerturbMode::Anywhere;
+    let mut selection_anchor = pointer;
+    let mut jump_input = TextScanner::new();
+    // What the jump prompt opened with, back to it when the prompt is cancelled
+    let mut jump_offered = jump_input;
+    let mut caption = Caption::new();
+    let mut jump_ramp = Ramp::new();
+    let mut cone_ramp = Ramp::new();
+    let mut recording = Recording::new();
+    let mut macro_recorder = MacroRecorder::new();
+    let mut record_replay = false;
+    let mut replay_speed = 1;
+    let mut replay_next_ms = 0;
+    let mut player: Option<Player> = None;
+    // Runs not drawn at all, logged to be replayed once paused
+    let mut turbo = false;
+    let mut turbo_log = TurboLog::new();
+    // Whether the log follows the board, until the run stops for good
+    let mut turbo_logging = false;
+    let mut turbo_ramp = Ramp::new();
+    let mut turbo_speed = 1;
+    let mut turbo_next_ms = 0;
+    // Generation being computed across frames, dropped when leaving the running mode
+    let mut in_progress: Option<StepContext<LifeLike>> = None;
+    // The changes of the last generation, while nothing else changed the board, and how to
+    // rewind the rest of it
+    let mut last_changes: Option<(Changes, Rewind)> = None;
+    // Start of the last stride of step by step
+    let mut stride_start: Option<StrideStart> = None;
+    let mut autosolve_budget = 0;
+    let mut records = Records::new();
+    // Random soup on the board, followed for the records while it's left untouched
+    let mut soup_run: Option<SoupRun> = None;
+    let mut was_simulating = false;
+    // The run being followed, and how the last one ended
+    let mut run_watch: Option<RunWatch> = None;
+    let mut last_outcome: Option<LastRunOutcome> = None;
+    let mut status_shown = false;
+    // Whether the state strips are on the screen
+    let mut strips_shown = false;
+    let mut autosolve_criterion = Criterion::LongestSettling;
+    // Last press of EXE in the pattern editor, and whether its RLE is shown
+    let mut pattern_exe_at: Option<u64> = None;
+    let mut showing_rle = false;
+    // Snapshots A and B, to compare them
+    let mut snapshots: [Option<PackedBoard>; 2] = [None, None];
 
     let mut board: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    // Decided before the first frame, so a demo doesn't flash the empty editor. The run
+    // resumes the newest checkpoint kept from the last session, or starts from a soup, its
+    // seed shown to play the same run again.
+    let mut startup_text: String<32> = String::new();
+    if config.startup == StartupMode::Run {
+        match checkpoints.newest() {
+            Some((packed, saved_generation, saved_rng)) => {
+                packed.unpack(&mut board);
+                generation = saved_generation;
+                simulation.rng = saved_rng;
+                write!(startup_text, "Resumed generation {}\0", generation).unwrap();
+            }
+            None => {
+                let seed = simulation.rng.next_u32();
+                autosolve::seed_soup(&mut board, seed, config.rule.soup_density());
+                soup_run = Some(SoupRun::new(seed, SoupSettings::of(&config), &board));
+                write!(startup_text, "Soup {:08X}\0", seed).unwrap();
+            }
+        }
+        state = AppState::Running;
+    }
+    draw_board(&board, config.palette(), &viewport);
+    if !startup_text.is_empty() {
+        toast.show(&startup_text, 3000);
+    }
+
+    let mut last_keyboard_state = input::resolve(keyboard::scan());
+    let mut back_held_since: Option<u64> = None;
 
     loop {
-        let keyboard_state = keyboard::scan();
+        // A macro being played back stands in for the keyboard
+        let scanned = input::resolve(keyboard::scan());
+        let now = timing::millis();
+        let keyboard_state = macro_recorder.replay(scanned, now).unwrap_or(scanned);
+        if macro_recorder.record(keyboard_state, now) {
+            toast.show("Macro full, SHIFT + BACK + HOME ends it\0", 3000);
+        }
+        let pressed = keyboard_state.pressed_since(&last_keyboard_state);
+        last_keyboard_state = keyboard_state;
 
-        if keyboard_state.key_down(key::XNT) {
-            state = AppState::Editor;
-        } else if keyboard_state.key_down(key::VAR) {
-            draw_cell(&board, pointer);
-            state = AppState::Running;
-        } else if keyboard_state.key_down(key::TOOLBOX) {
-            draw_cell(&board, pointer);
-            state = AppState::StepByStep;
+        // SHIFT + BACK + HOME records a macro, ALPHA + BACK + HOME plays it back
+        if keyboard_state.key_down(key::BACK)
+            && keyboard_state.key_down(key::HOME)
+            && (pressed.key_down(key::BACK) || pressed.key_down(key::HOME))
+        {
+            if keyboard_state.key_down(key::SHIFT) {
+                if macro_recorder.is_recording() {
+                    macro_recorder.stop_record();
+                    let mut text: String<32> = String::new();
+                    write!(text, "Macro of {} keys\0", macro_recorder.presses()).unwrap();
+                    toast.show(&text, 1500);
+                } else {
+                    macro_recorder.start_record(keyboard_state, now);
+                    toast.show("Recording a macro\0", 1500);
+                }
+                continue;
+            } else if keyboard_state.key_down(key::ALPHA) {
+                if macro_recorder.presses() == 0 {
+                    toast.show("Record a macro first\0", 2000);
+                } else {
+                    macro_recorder.start_replay(scanned, now);
+                    toast.show("Playing the macro, any key stops\0", 1500);
+                }
+                continue;
+            }
         }
 
-        match state {
-            AppState::Editor => {
-                let current = &mut board[pointer.0 as usize][pointer.1 as usize];
-                if keyboard_state.key_down(key::EXE) {
-                    *current = !*current;
-                } else if keyboard_state.key_down(key::PLUS) {
-                    *current = true;
-                } else if keyboard_state.key_down(key::MINUS) {
-                    *current = false;
+        // Emergency stop, before anything else so no state can miss it
+        if keyboard_state.key_down(key::BACK) {
+            let since = *back_held_since.get_or_insert(timing::millis());
+            if timing::millis().saturating_sub(since) >= EMERGENCY_HOLD_MS {
+                // Pending operations are dropped before touching the board, never half applied
+                staging = None;
+                if let Some(player) = player.take() {
+                    player.stop(&mut config);
                 }
+                show_graph = false;
+                toast.hide();
+                last_changes = None;
+                in_progress = None;
+                state = AppState::Editor;
+                draw_board(&board, config.palette(), &viewport);
+                // Only once per hold
+                back_held_since = Some(u64::MAX);
+                continue;
+            }
+        } else {
+            back_held_since = None;
+        }
 
-                if keyboard_state.key_down(key::UP) && pointer.1 > 0 {
-                    draw_cell(&board, pointer);
-                    pointer.1 -= 1;
-                } else if keyboard_state.key_down(key::DOWN) && pointer.1 < COLUMN_SIZE - 1 {
-                    draw_cell(&board, pointer);
-                    pointer.1 += 1;
-                }
-                if keyboard_state.key_down(key::LEFT) && pointer.0 > 0 {
-                    draw_cell(&board, pointer);
-                    pointer.0 -= 1;
-                } else if keyboard_state.key_down(key::RIGHT) && pointer.0 < LINE_SIZE - 1 {
-                    draw_cell(&board, pointer);
-                    pointer.0 += 1;
-                }
-
-                display::push_rect_uniform(
-                    Rect {
-                        x: pointer.0 * CELL_SIZE,
-                        y: pointer.1 * CELL_SIZE,
-                        width: CELL_SIZE,
-                        height: CELL_SIZE,
-                    },
-                    Color::RED,
-                );
+        if keyboard_state.key_down(key::SHIFT)
+            && keyboard_state.key_down(key::ALPHA)
+            && pressed.key_down(key::EXE)
+        {
+            selftest::run(config.theme());
+            draw_board(&board, config.palette(), &viewport);
+            last_keyboard_state = input::resolve(keyboard::scan());
+            continue;
+        }
 
-                timing::msleep(50);
+        #[cfg(feature = "shadow_fb")]
+        if keyboard_state.key_down(key::SHIFT)
+            && keyboard_state.key_down(key::ALPHA)
+            && pressed.key_down(key::OK)
+        {
+            screenshot_to_board(&mut edit::begin_hidden_edit(
+                &mut board,
+                &mut undo,
+                &mut simulation.edge,
+            ));
+            // Whatever was on screen, the board is edited from now on
+            staging = None;
+            if let Some(player) = player.take() {
+                player.stop(&mut config);
             }
-            AppState::Running => {
-                run_once(&mut board);
-                timing::msleep(10);
+            show_graph = false;
+            toast.hide();
+            last_changes = None;
+            in_progress = None;
+            // Not how any run ended
+            run_watch = None;
+            state = AppState::Editor;
+            viewport = Viewport::FULL;
+            draw_board(&board, config.palette(), &viewport);
+            continue;
+        }
+
+        let requested_state = if turbo
+            && matches!(state, AppState::Running)
+            && pressed.without(key::VAR).any_down()
+        {
+            Some(AppState::TurboPaused)
+        } else if matches!(state, AppState::Caption) {
+            // Every key types something
+            None
+        } else if keyboard_state.key_down(key::XNT) {
+            Some(AppState::Editor)
+        } else if keyboard_state.key_down(key::VAR) {
+            Some(AppState::Running)
+        } else if keyboard_state.key_down(key::TOOLBOX) {
+            Some(AppState::StepByStep)
+        } else if pressed.key_down(key::EXP)
+            && keyboard_state.key_down(key::SHIFT)
+            && !state.covers_board()
+        {
+            Some(AppState::Keypad {
+                in_editor: matches!(state, AppState::Editor),
+            })
+        } else if pressed.key_down(key::EXP) {
+            Some(AppState::Stats)
+        } else if pressed.key_down(key::LEFTPARENTHESIS) {
+            Some(AppState::Library)
+        } else if pressed.key_down(key::SQUARE) {
+            Some(AppState::RuleEditor)
+        } else if pressed.key_down(key::COMMA) && !state.covers_board() {
+            Some(AppState::HeatMap)
+        } else if pressed.key_down(key::POWER)
+            && keyboard_state.key_down(key::SHIFT)
+            && !state.covers_board()
+        {
+            record_replay = !record_replay;
+            if record_replay {
+                toast.show("Jumps recorded for replay\0", 1500);
+            } else {
+                redraw_area(&board, RECORDING_AREA, config.palette(), &viewport);
+                toast.show("Jumps not recorded\0", 1500);
+            }
+            None
+        } else if pressed.key_down(key::POWER)
+            && keyboard_state.key_down(key::ALPHA)
+            && !state.covers_board()
+        {
+            if recording.is_empty() {
+                toast.show("Record a jump first\0", 2000);
+                None
+            } else {
+                Some(AppState::Replay { frame: 0 })
+            }
+        } else if pressed.key_down(key::POWER)
+            && !state.covers_board()
+            && !matches!(state, AppState::StepByStep)
+        {
+            // The last jump is offered again, typing replaces it
+            jump_input.select_all();
+            jump_offered = jump_input;
+            Some(AppState::Jump)
+        } else if pressed.key_down(key::THREE)
+            && keyboard_state.key_down(key::ALPHA)
+            && matches!(state, AppState::Editor)
+        {
+            if config.rule == Rule::CONWAY {
+                toast.show("Choose another rule to compare\0", 2000);
+                None
+            } else {
+                Some(AppState::Versus {
+                    reference: PackedBoard::pack(&board),
+                    playing: true,
+                })
+            }
+        } else if pressed.key_down(key::LN)
+            && keyboard_state.key_down(key::ALPHA)
+            && !state.covers_board()
+        {
+            if caption.is_shown() {
+                caption.clear();
+                redraw_area(&board, caption.area(), config.palette(), &viewport);
+                None
+            } else if matches!(state, AppState::Editor) {
+                Some(AppState::Caption)
+            } else {
+                toast.show("Write a caption from the editor\0", 2000);
+                None
+            }
+        } else if pressed.key_down(key::THREE) && !state.covers_board() {
+            if let [Some(_), Some(_)] = snapshots {
+                Some(AppState::Diff)
+            } else {
+                toast.show("Save snapshots A and B first\0", 2000);
+                None
+            }
+        } else if pressed.key_down(key::FOUR) && !state.covers_board() {
+            Some(AppState::Scene(&scene::GLIDER_GUN_DEMO))
+        } else if pressed.key_down(key::FIVE) && !state.covers_board() {
+            Some(AppState::Scene(&scene::OSCILLATORS))
+        } else if pressed.key_down(key::SQRT)
+            && keyboard_state.key_down(key::ALPHA)
+            && !state.covers_board()
+        {
+            Some(AppState::Records)
+        } else if pressed.key_down(key::EIGHT)
+            && keyboard_state.key_down(key::ALPHA)
+            && matches!(state, AppState::Editor)
+        {
+            Some(AppState::Cone {
+                origin: pointer.position(),
+                generations: cone::FIRST_GENERATIONS,
+                actual: false,
+            })
+        } else if pressed.key_down(key::SQRT)
+            && !keyboard_state.key_down(key::SHIFT)
+            && !state.covers_board()
+        {
+            Some(AppState::Autosolve)
+        } else if pressed.key_down(key::RIGHTPARENTHESIS) && !state.covers_board() {
+            match import::stage(eadk::external_data()) {
+                Ok(staged) => {
+                    staging = Some(staged);
+                    Some(AppState::Import)
+                }
+                Err(error) => {
+                    let mut text: String<40> = String::new();
+                    write!(text, "Invalid RLE at {}:{}\0", error.line, error.column).unwrap();
+                    toast.show(&text, 2000);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if let Some(new_state) = requested_state {
+            render.catch_up(&board, config.palette(), &viewport);
+            if let Some(player) = player.take() {
+                player.stop(&mut config);
+            }
+            if !matches!(new_state, AppState::Running | AppState::StepByStep) {
+                last_changes = None;
+            }
+            stride_start = None;
+            in_progress = None;
+            if let AppState::Select = state {
+                CellRect::from_corners(selection_anchor.position(), pointer.position())
+                    .border_cells()
+                    .for_each(|cell| draw_cell(&board, cell, config.palette(), &viewport));
+            }
+            if state.covers_board() {
+                reveal_board(&board, config.palette(), &viewport);
+            } else if precision {
+                erase_crosshair(&board, pointer, config.palette(), &viewport);
+            } else {
+                draw_cell(&board, pointer.position(), config.palette(), &viewport);
+            }
+            match new_state {
+                AppState::Running => rate.restart(),
+                AppState::TurboPaused => draw_turbo_paused(&turbo_log),
+                AppState::Scene(scene) => {
+                    // Switching scenes keeps the board from before the first one
+                    if !matches!(state, AppState::Scene(_)) {
+                        undo.save(&board);
+                    }
+                    // Scenes run without the buffer, whose cells are left from another board
+                    simulation.edge.clear();
+                    player = Some(Player::start(scene, &mut config));
+                }
+                AppState::Jump => draw_jump(&jump_input),
+                AppState::Caption => caption.draw(true, config.palette()),
+                AppState::Autosolve => {
+                    draw_autosolve(BUDGETS_S[autosolve_budget], autosolve_criterion)
+                }
+                AppState::Records => records.draw(),
+                AppState::Replay { frame } => {
+                    recording.draw(frame, config.palette());
+                    replay_next_ms = timing::millis() + replay::FRAME_DELAYS_MS[replay_speed];
+                }
+                AppState::HeatMap => draw_heat_map(&board),
+                AppState::Cone {
+                    origin,
+                    generations,
+                    actual,
+                } => draw_cone(&board, (origin, generations, actual), &config, &viewport),
+                AppState::Stats => {
+                    let current = last_outcome
+                        .as_mut()
+                        .is_some_and(|outcome| outcome.is_current(&board));
+                    draw_stats(
+                        &board,
+                        generation,
+                        last_outcome.as_ref().filter(|_| current),
+                        &LifeLike {
+                            rule: config.rule,
+                            boundary: config.boundary,
+                        },
+                        (&checkpoints, &rate, simulation.reference_stepper.is_some()),
+                        caption.as_str(),
+                    )
+                }
+                AppState::Diff => {
+                    if let [Some(a), Some(b)] = &snapshots {
+                        diff::draw_diff(&diff::diff_snapshots(a, b), config.theme());
+                    }
+                }
+                AppState::Versus { ref reference, .. } => {
+                    // Whichever board is kept, undoing gives back the one compared
+                    undo.save(&board);
+                    draw_versus(&board, reference, generation, config.theme())
+                }
+                AppState::Keypad { in_editor } => keypad::draw(in_editor, config.theme()),
+                AppState::Library => library.draw(),
+                AppState::RuleEditor => rule_editor.draw(&config.rule, config.theme()),
+                AppState::Import => {
+                    if let Some(staged) = &staging {
+                        staged.draw(config.theme());
+                    }
+                }
+                _ => {}
+            }
+            state = new_state;
+        }
+
+        toast.on_expiry(|| {
+            if !state.covers_board() {
+                redraw_area(&board, Toast::AREA, config.palette(), &viewport);
+            }
+        });
+
+        if pressed.key_down(key::LOG)
+            && keyboard_state.key_down(key::ALPHA)
+            && !state.covers_board()
+        {
+            show_frontier = !show_frontier;
+            // The next frame of a run repaints the board, with or without it
+            render.invalidate();
+            toast.show(
+                if show_frontier {
+                    "Frontier shown while running\0"
+                } else {
+                    "Frontier hidden\0"
+                },
+                1000,
+            );
+        } else if pressed.key_down(key::LOG)
+            && !keyboard_state.key_down(key::SHIFT)
+            && !state.covers_board()
+        {
+            show_graph = !show_graph;
+            if show_graph {
+                graph.draw(config.theme());
+            } else {
+                draw_board(&board, config.palette(), &viewport);
+            }
+        }
+
+        if let Some(area) = tooltip.take_area() {
+            if !state.covers_board() {
+                redraw_area(&board, area, config.palette(), &viewport);
+            }
+        }
+
+        // However the run started, the log starts with it, and is over once the run is
+        if !matches!(
+            state,
+            AppState::Running | AppState::TurboPaused | AppState::TurboReplay { .. }
+        ) {
+            turbo_logging = false;
+        }
+
+        // Every way in and out of the simulation goes through here, so no edit of the board
+        // can go unnoticed by the soup run, and every run ending is recorded
+        let simulating = matches!(state, AppState::Running | AppState::StepByStep);
+        if simulating != was_simulating {
+            if let Some(run) = &mut soup_run {
+                if simulating {
+                    run.resume(&board, SoupSettings::of(&config));
+                } else {
+                    run.pause(&board);
+                }
+            }
+            if simulating {
+                run_watch = Some(RunWatch::start(&board));
+                last_outcome = None;
+            } else {
+                last_outcome = run_watch
+                    .take()
+                    .map(|watch| watch.finish(&board, generation));
+            }
+            was_simulating = simulating;
+        }
+
+        match state {
+            AppState::Editor => {
+                let current = pointer.cell_mut(&mut board);
+                // With SHIFT or ALPHA held these keys are chords, like SHIFT + to zoom
+                let keys = input::plain(keyboard_state);
+                if keys.key_down(key::EXE) {
+                    *current = !*current;
+                } else if keys.key_down(key::PLUS) {
+                    *current = true;
+                } else if keys.key_down(key::MINUS) {
+                    *current = false;
+                }
+
+                if keyboard_state.key_down(key::SHIFT)
+                    && (pressed.key_down(key::PLUS) || pressed.key_down(key::MINUS))
+                {
+                    let level = ZOOM_LEVELS
+                        .iter()
+                        .position(|&size| size == viewport.cell_size)
+                        .unwrap_or(0);
+                    let level = if pressed.key_down(key::PLUS) {
+                        (level + 1).min(ZOOM_LEVELS.len() - 1)
+                    } else {
+                        level.saturating_sub(1)
+                    };
+                    if ZOOM_LEVELS[level] != viewport.cell_size {
+                        viewport.zoom_toward(pointer.position(), ZOOM_LEVELS[level]);
+                        draw_board(&board, config.palette(), &viewport);
+                    }
+                } else if keyboard_state.key_down(key::ALPHA)
+                    && (pressed.key_down(key::PLUS) || pressed.key_down(key::MINUS))
+                {
+                    let mut edit = edit::begin_edit(
+                        &mut board,
+                        &mut undo,
+                        &mut simulation.edge,
+                        config.palette(),
+                        &viewport,
+                    );
+                    *edit = if pressed.key_down(key::PLUS) {
+                        morphology::dilate(&edit, config.boundary)
+                    } else {
+                        morphology::erode(&edit, config.boundary)
+                    };
+                }
+
+                let settings_changed = if pressed.key_down(key::ANS) {
+                    if keyboard_state.key_down(key::SHIFT) {
+                        config.startup = config.startup.toggle();
+                        toast.show(
+                            match config.startup {
+                                StartupMode::Editor => "Opens in the editor\0",
+                                StartupMode::Run => "Opens running, resumed or a soup\0",
+                            },
+                            1000,
+                        );
+                    } else {
+                        config.update_mode = config.update_mode.next();
+                    }
+                    true
+                } else if pressed.key_down(key::SINE) {
+                    config.rule = config.rule.next_preset();
+                    true
+                } else if pressed.key_down(key::COSINE) && keyboard_state.key_down(key::ALPHA) {
+                    turbo = !turbo;
+                    toast.show(
+                        if turbo {
+                            "Turbo: runs drawn after, as a replay\0"
+                        } else {
+                            "Turbo off\0"
+                        },
+                        1500,
+                    );
+                    false
+                } else if pressed.key_down(key::COSINE) {
+                    if keyboard_state.key_down(key::SHIFT) {
+                        config.uncapped = !config.uncapped;
+                        toast.show(
+                            if config.uncapped {
+                                "Uncapped\0"
+                            } else {
+                                "Capped\0"
+                            },
+                            1000,
+                        );
+                    } else {
+                        config.speed = config.speed.next();
+                    }
+                    true
+                } else if pressed.key_down(key::TANGENT) && keyboard_state.key_down(key::ALPHA) {
+                    config.state_tint = !config.state_tint;
+                    toast.show(
+                        if config.state_tint {
+                            "Sides tinted running, stepping or paused\0"
+                        } else {
+                            "Sides not tinted\0"
+                        },
+                        1500,
+                    );
+                    true
+                } else if pressed.key_down(key::TANGENT) {
+                    if keyboard_state.key_down(key::SHIFT) {
+                        config.theme_index = (config.theme_index + 1) % THEMES.len() as u8;
+                        toast.show(config.theme().name, 1000);
+                        if show_graph {
+                            graph.draw(config.theme());
+                        }
+                    } else {
+                        config.palette_index = (config.palette_index + 1) % PALETTES.len() as u8;
+                        draw_board(&board, config.palette(), &viewport);
+                    }
+                    true
+                } else if pressed.key_down(key::EE) && keyboard_state.key_down(key::SHIFT) {
+                    let rng = &mut simulation.rng;
+                    // Drawn afterwards in the order it was filled, not repainted by the edit
+                    soup::spiral_fill(
+                        &mut edit::begin_hidden_edit(&mut board, &mut undo, &mut simulation.edge),
+                        |x, y| {
+                            // Square rings around the center, denser outward every 16 cells
+                            let distance =
+                                x.abs_diff(LINE_SIZE / 2).max(y.abs_diff(COLUMN_SIZE / 2));
+                            rng.random_bool((distance % 16) as u32, 16)
+                        },
+                    );
+                    soup::spiral().for_each(|cell| {
+                        draw_cell(&board, cell, config.palette(), &viewport);
+                    });
+                    false
+                } else if pressed.key_down(key::EE) {
+                    soup::stress_board(
+                        &mut edit::begin_edit(
+                            &mut board,
+                            &mut undo,
+                            &mut simulation.edge,
+                            config.palette(),
+                            &viewport,
+                        ),
+                        soup::STRESS_SEED,
+                    );
+                    false
+                } else if pressed.key_down(key::IMAGINARY) {
+                    if keyboard_state.key_down(key::SHIFT) {
+                        symmetry::symmetrize_board(
+                            &mut edit::begin_edit(
+                                &mut board,
+                                &mut undo,
+                                &mut simulation.edge,
+                                config.palette(),
+                                &viewport,
+                            ),
+                            symmetry,
+                        );
+                    } else {
+                        symmetry = symmetry.next();
+                        toast.show(symmetry.name(), 1000);
+                    }
+                    false
+                } else if keyboard_state.key_down(key::SHIFT) && pressed.key_down(key::SQRT) {
+                    let seed = simulation.rng.next_u32();
+                    autosolve::seed_soup(
+                        &mut edit::begin_edit(
+                            &mut board,
+                            &mut undo,
+                            &mut simulation.edge,
+                            config.palette(),
+                            &viewport,
+                        ),
+                        seed,
+                        config.rule.soup_density(),
+                    );
+                    generation = 0;
+                    soup_run = Some(SoupRun::new(seed, SoupSettings::of(&config), &board));
+                    let mut text: String<32> = String::new();
+                    write!(text, "Soup {:08X}\0", seed).unwrap();
+                    toast.show(&text, 1500);
+                    false
+                } else if pressed.key_down(key::SIX) {
+                    let mut text: String<40> = String::new();
+                    if keyboard_state.key_down(key::SHIFT) {
+                        flip_count = (flip_count + 1) % FLIP_COUNTS.len();
+                    } else if keyboard_state.key_down(key::ALPHA) {
+                        perturb_mode = perturb_mode.toggle();
+                    } else {
+                        let flipped = perturb::perturb(
+                            &mut edit::begin_edit(
+                                &mut board,
+                                &mut undo,
+                                &mut simulation.edge,
+                                config.palette(),
+                                &viewport,
+                            ),
+                            &mut simulation.rng,
+                            FLIP_COUNTS[flip_count],
+                            perturb_mode,
+                        );
+                        write!(text, "Flipped {} cells ", flipped.len()).unwrap();
+                    }
+                    if text.is_empty() {
+                        write!(text, "Perturb {} cells ", FLIP_COUNTS[flip_count]).unwrap();
+                    }
+                    text.push_str(perturb_mode.name()).unwrap();
+                    toast.show(&text, 1500);
+                    false
+                } else if pressed.key_down(key::SEVEN) || pressed.key_down(key::NINE) {
+                    let shift = keyboard_state.key_down(key::SHIFT);
+                    let source = match (pressed.key_down(key::SEVEN), shift) {
+                        (true, false) => MirrorSource::Left,
+                        (true, true) => MirrorSource::Right,
+                        (false, false) => MirrorSource::Top,
+                        (false, true) => MirrorSource::Bottom,
+                    };
+                    symmetry::mirror_half(
+                        &mut edit::begin_edit(
+                            &mut board,
+                            &mut undo,
+                            &mut simulation.edge,
+                            config.palette(),
+                            &viewport,
+                        ),
+                        source,
+                    );
+                    false
+                } else if pressed.key_down(key::ONE) || pressed.key_down(key::TWO) {
+                    let (slot, name) = if pressed.key_down(key::ONE) {
+                        (0, "A")
+                    } else {
+                        (1, "B")
+                    };
+                    let mut text: String<24> = String::new();
+                    if keyboard_state.key_down(key::SHIFT) {
+                        snapshots[slot] = Some(PackedBoard::pack(&board));
+                        write!(text, "Saved snapshot {}\0", name).unwrap();
+                    } else if let Some(snapshot) = &snapshots[slot] {
+                        snapshot.unpack(&mut edit::begin_edit(
+                            &mut board,
+                            &mut undo,
+                            &mut simulation.edge,
+                            config.palette(),
+                            &viewport,
+                        ));
+                        write!(text, "Restored snapshot {}\0", name).unwrap();
+                    } else {
+                        write!(text, "No snapshot {}\0", name).unwrap();
+                    }
+                    toast.show(&text, 1000);
+                    false
+                } else if keyboard_state.key_down(key::SHIFT) && pressed.key_down(key::LOG) {
+                    config.next_noise_level();
+                    let mut text: String<32> = String::new();
+                    match config.noise {
+                        0 => write!(text, "Noise off\0"),
+                        level => write!(text, "Noise: {} flips in 1000\0", level),
+                    }
+                    .unwrap();
+                    toast.show(&text, 1000);
+                    true
+                } else if pressed.key_down(key::LN) && !keyboard_state.key_down(key::ALPHA) {
+                    let mut text: String<32> = String::new();
+                    if keyboard_state.key_down(key::SHIFT) {
+                        config.next_checkpoint_interval();
+                        match config.checkpoint_every {
+                            0 => write!(text, "Checkpoints off\0"),
+                            every => write!(text, "Checkpoint every {}\0", every),
+                        }
+                        .unwrap();
+                        toast.show(&text, 1000);
+                        true
+                    } else {
+                        match checkpoints.newest() {
+                            Some((packed, saved_generation, saved_rng)) => {
+                                packed.unpack(&mut edit::begin_edit(
+                                    &mut board,
+                                    &mut undo,
+                                    &mut simulation.edge,
+                                    config.palette(),
+                                    &viewport,
+                                ));
+                                generation = saved_generation;
+                                simulation.rng = saved_rng;
+                                write!(text, "Resumed generation {}\0", generation).unwrap();
+                            }
+                            None => write!(text, "No checkpoint\0").unwrap(),
+                        }
+                        toast.show(&text, 1500);
+                        false
+                    }
+                } else if pressed.key_down(key::DIVISION) {
+                    if keyboard_state.key_down(key::SHIFT) {
+                        let mut edit = edit::begin_edit(
+                            &mut board,
+                            &mut undo,
+                            &mut simulation.edge,
+                            config.palette(),
+                            &viewport,
+                        );
+                        for &(dx, dy) in patterns::LWSS {
+                            if let Some(cell) = pointer.offset((dx as i32, dy as i32)) {
+                                *cell.cell_mut(&mut edit) = true;
+                            }
+                        }
+                    }
+                    let mut text: String<40> = String::new();
+                    match analysis::measure_spaceship_velocity(
+                        &board,
+                        pointer.position(),
+                        VELOCITY_GENERATIONS,
+                    ) {
+                        Some((dx, dy)) => write!(
+                            text,
+                            "Moved ({}, {}) in {} gen\0",
+                            dx, dy, VELOCITY_GENERATIONS
+                        ),
+                        None => write!(text, "Died in {} gen\0", VELOCITY_GENERATIONS),
+                    }
+                    .unwrap();
+                    toast.show(&text, 2000);
+                    false
+                } else if pressed.key_down(key::MULTIPLICATION)
+                    && keyboard_state.key_down(key::SHIFT)
+                {
+                    if simulation.mask.take().is_some() {
+                        toast.show("Confinement lifted\0", 2000);
+                    }
+                    false
+                } else if pressed.key_down(key::MULTIPLICATION)
+                    && keyboard_state.key_down(key::ALPHA)
+                {
+                    let before = analysis::population(&board);
+                    morphology::smooth_board(
+                        &mut edit::begin_edit(
+                            &mut board,
+                            &mut undo,
+                            &mut simulation.edge,
+                            config.palette(),
+                            &viewport,
+                        ),
+                        SMOOTH_PASSES,
+                        config.boundary,
+                    );
+                    let mut text: String<32> = String::new();
+                    write!(
+                        text,
+                        "Smoothed away {} cells\0",
+                        before - analysis::population(&board)
+                    )
+                    .unwrap();
+                    toast.show(&text, 1500);
+                    false
+                } else if pressed.key_down(key::MULTIPLICATION) {
+                    expand_border_cells(&mut edit::begin_edit(
+                        &mut board,
+                        &mut undo,
+                        &mut simulation.edge,
+                        config.palette(),
+                        &viewport,
+                    ));
+                    false
+                } else if pressed.key_down(key::BACKSPACE) {
+                    if undo.undo(&mut board) {
+                        simulation.edge.clear();
+                        draw_board(&board, config.palette(), &viewport);
+                    }
+                    false
+                } else if pressed.key_down(key::PI) && !keyboard_state.key_down(key::ALPHA) {
+                    if keyboard_state.key_down(key::SHIFT) {
+                        config.rim_tint = !config.rim_tint;
+                        toast.show(
+                            if config.rim_tint {
+                                "Rim of the board tinted\0"
+                            } else {
+                                "Rim not tinted\0"
+                            },
+                            1000,
+                        );
+                    } else {
+                        // Dead edges, dead edges behind a buffer, then wrapping
+                        match (config.boundary, config.edge_buffer) {
+                            (BoundaryMode::Dead, false) => {
+                                simulation.edge.clear();
+                                config.edge_buffer = true;
+                            }
+                            (BoundaryMode::Dead, true) => {
+                                config.boundary = BoundaryMode::Wrap;
+                                config.edge_buffer = false;
+                            }
+                            (BoundaryMode::Wrap, _) => config.boundary = BoundaryMode::Dead,
+                        }
+                    }
+                    // The rim is only tinted with dead edges
+                    draw_board(&board, config.palette(), &viewport);
+                    true
+                } else {
+                    false
+                };
+                if settings_changed {
+                    config.save();
+                }
+
+                if keyboard_state.key_down(key::SHIFT) && pressed.key_down(key::OK) {
+                    precision = !precision;
+                    if !precision {
+                        erase_crosshair(&board, pointer, config.palette(), &viewport);
+                    }
+                }
+
+                let previous = pointer;
+                if let Some(path) = &mut glide {
+                    // Any key skips to the end of the glide
+                    match path.next() {
+                        Some(cell) if !pressed.any_down() => pointer = cell,
+                        _ => {
+                            pointer = path.target();
+                            glide = None;
+                        }
+                    }
+                } else if let Some(target) = jump_target(&board, &keyboard_state, &pressed, pointer)
+                {
+                    glide = Some(Glide::new(pointer, target));
+                } else {
+                    move_pointer(&keyboard_state, &mut pointer, config.boundary);
+                }
+                let mut panned = false;
+                if status_shown && pointer != previous {
+                    let (from, to) = (previous.position(), pointer.position());
+                    match LastRunOutcome::dodge(from, to, &mut viewport) {
+                        Dodge::Clear => {}
+                        Dodge::Scrolled => panned = true,
+                        Dodge::Stopped => {
+                            pointer = previous;
+                            glide = None;
+                            toast.show("Under the run status until an edit\0", 2000);
+                        }
+                    }
+                }
+                if pointer != previous {
+                    if viewport.follow(pointer.position()) || panned {
+                        draw_board(&board, config.palette(), &viewport);
+                    } else if precision {
+                        erase_crosshair(&board, previous, config.palette(), &viewport);
+                    } else {
+                        draw_cell(&board, previous.position(), config.palette(), &viewport);
+                    }
+                }
+
+                if pressed.key_down(key::DOT) && keyboard_state.key_down(key::ALPHA) {
+                    viewport.grid = !viewport.grid;
+                    draw_board(&board, config.palette(), &viewport);
+                } else if pressed.key_down(key::DOT) {
+                    if precision {
+                        erase_crosshair(&board, pointer, config.palette(), &viewport);
+                    }
+                    selection_anchor = pointer;
+                    state = AppState::Select;
+                } else if pressed.key_down(key::EIGHT) {
+                    pattern_exe_at = None;
+                    showing_rle = false;
+                    draw_pattern_editor(
+                        (pointer, &[]),
+                        pointer,
+                        (config.palette(), config.theme()),
+                        &viewport,
+                    );
+                    state = AppState::PatternEditor {
+                        origin: pointer.position(),
+                        cells: Vec::new(),
+                    };
+                }
+
+                if matches!(state, AppState::Editor | AppState::Select) {
+                    draw_cursor(&board, pointer, config.palette(), &viewport);
+                }
+                if precision && matches!(state, AppState::Editor) {
+                    draw_crosshair(pointer, config.palette(), &viewport);
+                }
+
+                timing::msleep(50);
+            }
+            AppState::Select => {
+                let selected =
+                    CellRect::from_corners(selection_anchor.position(), pointer.position());
+                if pressed.key_down(key::EXE) {
+                    selection::toggle_region(
+                        &mut edit::begin_hidden_edit(&mut board, &mut undo, &mut simulation.edge),
+                        selected,
+                        config.palette(),
+                        &viewport,
+                    );
+                    state = AppState::Editor;
+                } else if pressed.key_down(key::MULTIPLICATION) {
+                    let confined = selection::create_rect_mask(selected);
+                    selection::apply_mask(
+                        &mut edit::begin_edit(
+                            &mut board,
+                            &mut undo,
+                            &mut simulation.edge,
+                            config.palette(),
+                            &viewport,
+                        ),
+                        &confined,
+                    );
+                    simulation.mask = Some(confined);
+                    toast.show("Confined to the selection\0", 2000);
+                    state = AppState::Editor;
+                } else if pressed.key_down(key::BACK) {
+                    selected
+                        .border_cells()
+                        .for_each(|cell| draw_cell(&board, cell, config.palette(), &viewport));
+                    state = AppState::Editor;
+                } else {
+                    if move_pointer(&keyboard_state, &mut pointer, config.boundary).is_some() {
+                        if viewport.follow(pointer.position()) {
+                            draw_board(&board, config.palette(), &viewport);
+                        } else {
+                            selected.border_cells().for_each(|cell| {
+                                draw_cell(&board, cell, config.palette(), &viewport)
+                            });
+                        }
+                    }
+                    CellRect::from_corners(selection_anchor.position(), pointer.position())
+                        .draw_outline(&viewport, config.palette().cursor);
+                    draw_cursor(&board, pointer, config.palette(), &viewport);
+                    timing::msleep(50);
+                }
+            }
+            AppState::Running => {
+                if turbo && !turbo_logging {
+                    turbo_log.start(&board, generation);
+                    draw_turbo_hud(&turbo_log, None, simulation.reference_stepper.is_some());
+                    turbo_logging = true;
+                }
+                // Uncapped runs scan the keyboard during long generations too
+                let cell_budget = if config.uncapped || turbo {
+                    STEP_CELL_BUDGET
+                } else {
+                    usize::MAX
+                };
+                // Set again by another rule if it gets stuck too
+                let stuck_before = simulation.reference_stepper;
+                // Neither is changed until the last part of a generation
+                let rewind = Rewind::before(&simulation, &checkpoints);
+                let automaton = LifeLike {
+                    rule: config.rule,
+                    boundary: config.boundary,
+                };
+                let mut changes = match run_in_parts(
+                    &mut board,
+                    &config,
+                    &mut simulation,
+                    (&mut in_progress, automaton),
+                    cell_budget,
+                ) {
+                    Some(changes) => changes,
+                    None => continue,
+                };
+                if simulation.reference_stepper.is_some()
+                    && simulation.reference_stepper != stuck_before
+                {
+                    let mut text: String<48> = String::new();
+                    write!(text, "Gen {} stuck, stepping at once\0", generation + 1).unwrap();
+                    toast.show(&text, 3000);
+                }
+                generation += 1;
+                let noisy = apply_noise(
+                    &mut board,
+                    config.noise as u32,
+                    &mut simulation,
+                    &mut changes,
+                );
+                if noisy {
+                    render.invalidate();
+                }
+                if config.checkpoint_every != 0
+                    && generation.is_multiple_of(config.checkpoint_every as u32)
+                {
+                    checkpoints.save(&board, generation, simulation.rng);
+                }
+                graph.push(changes.born.len() as u32, changes.died.len() as u32);
+                // Turbo runs are logged instead of drawn. Faster than the screen, generations
+                // between two frames aren't drawn at all.
+                if turbo {
+                    turbo_log.push(&board);
+                    render.invalidate();
+                } else if frame_skip.frame_due() {
+                    // Capped, the pacer's deadlines absorb the wait for the screen
+                    if !config.uncapped {
+                        display::wait_for_vblank();
+                    }
+                    if show_frontier {
+                        draw_frontier(
+                            &board,
+                            config.boundary,
+                            (config.palette(), config.theme()),
+                            &viewport,
+                        );
+                        render.invalidate();
+                    } else {
+                        render.flush(&board, &changes, config.palette(), &viewport);
+                    }
+                    if show_graph {
+                        graph.draw(config.theme());
+                    }
+                } else {
+                    render.invalidate();
+                }
+                if let Some(beaten) = soup_run
+                    .as_mut()
+                    .and_then(|run| run.step(&board, &changes, &mut records))
+                {
+                    show_record(&mut toast, &beaten);
+                }
+                if let Some(watch) = &mut run_watch {
+                    watch.step(&board);
+                }
+                // A cell flipped right after changing is in the changes twice, they can't be
+                // stepped back
+                last_changes = (!noisy).then_some((changes, rewind));
+                let measured = rate.tick();
+                match config.frame_period_us() {
+                    // Not paced either, the HUD tells the rate instead of a toast
+                    _ if turbo => {
+                        if let Some(per_second) = measured {
+                            draw_turbo_hud(
+                                &turbo_log,
+                                Some(per_second),
+                                simulation.reference_stepper.is_some(),
+                            );
+                        }
+                    }
+                    Some(period) => pacer.wait(period),
+                    None => {
+                        if let Some(per_second) = measured {
+                            let mut text: String<64> = String::new();
+                            write!(
+                                text,
+                                "{} gen/s, {} rects/frame",
+                                Tenths(per_second),
+                                render.rects
+                            )
+                            .unwrap();
+                            if simulation.reference_stepper.is_some() {
+                                text.push_str(", fallback stepper").unwrap();
+                            }
+                            text.push('\0').unwrap();
+                            toast.show(&text, 1500);
+                        }
+                    }
+                }
             }
             AppState::StepByStep => {
-                if keyboard_state.key_down(key::EXE) {
-                    run_once(&mut board);
+                let keys = input::plain(pressed);
+                let stride = if keyboard_state.key_down(key::EXE) {
+                    1
+                } else if keys.key_down(key::MULTIPLICATION) {
+                    SHORT_STRIDE
+                } else if keys.key_down(key::POWER) {
+                    LONG_STRIDE
+                } else {
+                    0
+                };
+                if stride != 0 {
+                    // Strides can be undone as a whole, the steps of one can't
+                    stride_start = (stride > 1).then(|| {
+                        let rewind = Rewind::before(&simulation, &checkpoints);
+                        StrideStart::before(&board, generation, rewind)
+                    });
+                    // A board already repeating doesn't stop strides, one starting to does
+                    let was_repeating = run_watch.as_ref().and_then(RunWatch::period).is_some();
+                    let mut entered_period = None;
+                    let mut stepped = 0;
+                    let mut last = None;
+                    while stepped < stride && entered_period.is_none() {
+                        let rewind = Rewind::before(&simulation, &checkpoints);
+                        let mut changes = run_once(&mut board, &config, &mut simulation);
+                        generation += 1;
+                        stepped += 1;
+                        let noisy = apply_noise(
+                            &mut board,
+                            config.noise as u32,
+                            &mut simulation,
+                            &mut changes,
+                        );
+                        if noisy {
+                            render.invalidate();
+                        }
+                        if config.checkpoint_every != 0
+                            && generation.is_multiple_of(config.checkpoint_every as u32)
+                        {
+                            checkpoints.save(&board, generation, simulation.rng);
+                        }
+                        graph.push(changes.born.len() as u32, changes.died.len() as u32);
+                        if let Some(beaten) = soup_run
+                            .as_mut()
+                            .and_then(|run| run.step(&board, &changes, &mut records))
+                        {
+                            show_record(&mut toast, &beaten);
+                        }
+                        if let Some(watch) = &mut run_watch {
+                            watch.step(&board);
+                            entered_period = watch.period().filter(|_| !was_repeating);
+                        }
+                        last = Some((changes, rewind, noisy));
+                    }
+                    // The board is drawn once, after the whole stride
+                    if let Some((changes, rewind, noisy)) = last {
+                        if stepped > 1 {
+                            render.invalidate();
+                            render.catch_up(&board, config.palette(), &viewport);
+                        } else {
+                            render.flush(&board, &changes, config.palette(), &viewport);
+                        }
+                        last_changes = (stride == 1 && !noisy).then_some((changes, rewind));
+                    }
+                    if show_graph {
+                        graph.draw(config.theme());
+                    }
+                    if let (true, Some(period)) = (stride > 1, entered_period) {
+                        let mut text: String<40> = String::new();
+                        write!(text, "Period {} from gen {}\0", period, generation).unwrap();
+                        toast.show(&text, 2000);
+                    }
+                    timing::msleep(50);
+                } else if pressed.key_down(key::BACKSPACE) {
+                    match last_changes.take() {
+                        Some((changes, rewind)) => {
+                            // Going back isn't running the soup anymore
+                            soup_run = None;
+                            render.catch_up(&board, config.palette(), &viewport);
+                            step_backward(
+                                &mut board,
+                                (&changes, rewind),
+                                (&mut simulation, &mut checkpoints),
+                                config.palette(),
+                                &viewport,
+                            );
+                            generation = generation.saturating_sub(1);
+                            // The generations followed so far are ahead of the board now
+                            run_watch = Some(RunWatch::start(&board));
+                        }
+                        None => match stride_start.take() {
+                            Some(start) => {
+                                generation =
+                                    start.undo(&mut board, &mut simulation, &mut checkpoints);
+                                soup_run = None;
+                                render.invalidate();
+                                render.catch_up(&board, config.palette(), &viewport);
+                                run_watch = Some(RunWatch::start(&board));
+                            }
+                            _ => toast.show("Only the last step or stride can be undone\0", 2000),
+                        },
+                    }
+                }
+            }
+            AppState::Stats => {
+                if pressed.key_down(key::EXE) {
+                    draw_stat(6, "Evolution", "analyzing...  ");
+                    let automaton = LifeLike {
+                        rule: config.rule,
+                        boundary: config.boundary,
+                    };
+                    let mut spinner = Spinner::new(Point::new(SPINNER_X, 10 + 6 * 22));
+                    let analysis = analysis::analyze_until(
+                        &board,
+                        &automaton,
+                        ANALYSIS_MAX_GENERATIONS,
+                        || {
+                            spinner.tick();
+                            true
+                        },
+                    );
+                    // Periods too long to be noticed by the analysis are searched again
+                    let long_period =
+                        if analysis.detected_period.is_none() && analysis.drift.is_none() {
+                            period::compute_period(&board, &automaton, ANALYSIS_MAX_GENERATIONS)
+                        } else {
+                            None
+                        };
+                    spinner.clear();
+                    draw_analysis(
+                        &analysis,
+                        long_period,
+                        analysis::life_expectancy(&board, &automaton, LIFESPAN_GENERATIONS),
+                    );
+                }
+            }
+            AppState::Caption => {
+                if pressed.key_down(key::EXE) || pressed.key_down(key::BACK) {
+                    if pressed.key_down(key::BACK) {
+                        caption.clear();
+                    }
+                    // Shown without the underscore from now on, if it wasn't left empty
+                    redraw_area(&board, caption.area(), config.palette(), &viewport);
+                    state = AppState::Editor;
+                } else if pressed.key_down(key::UP) || pressed.key_down(key::DOWN) {
+                    redraw_area(&board, caption.area(), config.palette(), &viewport);
+                    caption.top = pressed.key_down(key::UP);
+                    caption.draw(true, config.palette());
+                } else if caption.push_key(&pressed, &keyboard_state) {
+                    caption.draw(true, config.palette());
+                }
+            }
+            AppState::Jump => {
+                let step = jump_ramp.step(
+                    timing::millis(),
+                    &keyboard_state,
+                    &pressed,
+                    (key::LEFT, key::RIGHT),
+                );
+                if step != 0 {
+                    let value = jump_input.value_u32().unwrap_or(0);
+                    jump_input.set_u32(ramp::adjust(value, step, (0, JUMP_MAX_GENERATIONS)));
+                    draw_jump(&jump_input);
+                } else if jump_input.push_key(&pressed) || pressed.key_down(key::BACKSPACE) {
+                    draw_jump(&jump_input);
+                } else if pressed.key_down(key::EXE) || pressed.key_down(key::BACK) {
+                    if let (true, Some(target)) =
+                        (pressed.key_down(key::EXE), jump_input.value_u32())
+                    {
+                        undo.save(&board);
+                        // Run in parts between frames when recording, the whole jump at
+                        // once otherwise
+                        let part = if record_replay {
+                            recording.clear();
+                            recording.push(&board);
+                            Recording::frame_every(target)
+                        } else {
+                            target
+                        };
+                        let mut spinner = Spinner::new(Point::new(SPINNER_X, 10));
+                        let mut done = 0;
+                        while done < target {
+                            let length = part.min(target - done);
+                            // The board isn't drawn meanwhile, so show the progress and
+                            // allow stopping long jumps
+                            let ran = evolve_n_with_progress(
+                                &mut board,
+                                &config,
+                                &mut simulation,
+                                length,
+                                JUMP_PROGRESS_EVERY.min(length),
+                                |ran| {
+                                    draw_progress(done + ran, target);
+                                    spinner.tick();
+                                    !keyboard::scan().key_down(key::BACK)
+                                },
+                            );
+                            done += ran;
+                            if record_replay {
+                                recording.push(&board);
+                            }
+                            if ran < length {
+                                break;
+                            }
+                        }
+                        generation += done;
+                    } else if pressed.key_down(key::BACK) {
+                        // Only a jump run keeps the number typed or adjusted
+                        jump_input = jump_offered;
+                    }
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                }
+            }
+            AppState::Autosolve => {
+                if pressed.key_down(key::UP) || pressed.key_down(key::DOWN) {
+                    autosolve_budget = if pressed.key_down(key::UP) {
+                        (autosolve_budget + 1) % BUDGETS_S.len()
+                    } else {
+                        (autosolve_budget + BUDGETS_S.len() - 1) % BUDGETS_S.len()
+                    };
+                    draw_autosolve(BUDGETS_S[autosolve_budget], autosolve_criterion);
+                } else if pressed.key_down(key::LEFT) || pressed.key_down(key::RIGHT) {
+                    autosolve_criterion = autosolve_criterion.toggle();
+                    draw_autosolve(BUDGETS_S[autosolve_budget], autosolve_criterion);
+                } else if pressed.key_down(key::EXE) {
+                    let mut seeds = Rng::new(simulation.rng.next_u32());
+                    let deadline_ms = timing::millis() + BUDGETS_S[autosolve_budget] * 1000;
+                    let mut report = PeriodicUpdate::new(JUMP_PROGRESS_EVERY);
+                    draw_stat(3, "Searching", "BACK to stop");
+                    let mut spinner = Spinner::new(Point::new(SPINNER_X, 10 + 3 * 22));
+                    let found = autosolve::search(
+                        (&config, &mut simulation),
+                        autosolve_criterion,
+                        deadline_ms,
+                        &mut seeds,
+                        |tried| {
+                            spinner.tick();
+                            if report.tick() {
+                                let left_s = deadline_ms.saturating_sub(timing::millis()) / 1000;
+                                draw_stat(4, "Soups tried", tried);
+                                draw_stat(5, "Time left", format_args!("{} s  ", left_s));
+                            }
+                            !keyboard::scan().key_down(key::BACK)
+                        },
+                    );
+                    // The soups ran through the edge buffer, none of them is on the board
+                    simulation.edge.clear();
+                    // BACK may still be down after stopping, it's not a new press
+                    last_keyboard_state = input::resolve(keyboard::scan());
+
+                    if let Some(found) = found {
+                        // Replayed from the start, so the soup can be watched evolving
+                        autosolve::seed_soup(
+                            &mut edit::begin_hidden_edit(
+                                &mut board,
+                                &mut undo,
+                                &mut simulation.edge,
+                            ),
+                            found.seed,
+                            config.rule.soup_density(),
+                        );
+                        simulation.rng = autosolve::soup_rng(found.seed);
+                        generation = 0;
+                        soup_run =
+                            Some(SoupRun::new(found.seed, SoupSettings::of(&config), &board));
+                        reveal_board(&board, config.palette(), &viewport);
+                        let mut text: String<48> = String::new();
+                        match found.analysis.generations_to_stabilize {
+                            Some(settled) => {
+                                write!(text, "Best of {}: stable at {}", found.tried, settled)
+                            }
+                            None => write!(
+                                text,
+                                "Best of {}: unstable at {}",
+                                found.tried,
+                                autosolve::MAX_GENERATIONS
+                            ),
+                        }
+                        .unwrap();
+                        write!(text, ", peak {}\0", found.analysis.peak_population).unwrap();
+                        toast.show(&text, 5000);
+                        rate.restart();
+                        state = AppState::Running;
+                    } else {
+                        reveal_board(&board, config.palette(), &viewport);
+                        toast.show("Search stopped\0", 2000);
+                        state = AppState::Editor;
+                    }
+                } else if pressed.key_down(key::BACK) {
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                }
+            }
+            AppState::Cone {
+                origin,
+                ref mut generations,
+                ref mut actual,
+            } => {
+                let step = cone_ramp.step(
+                    timing::millis(),
+                    &keyboard_state,
+                    &pressed,
+                    (key::LEFT, key::RIGHT),
+                );
+                if pressed.key_down(key::BACK) {
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                } else if step != 0 || pressed.key_down(key::EXE) {
+                    *generations = ramp::adjust(*generations, step, (1, cone::MAX_GENERATIONS));
+                    *actual ^= pressed.key_down(key::EXE);
+                    draw_cone(&board, (origin, *generations, *actual), &config, &viewport);
+                }
+            }
+            AppState::TurboPaused => {
+                if pressed.key_down(key::EXE) {
+                    let (first, _) = turbo_log.range();
+                    turbo_log.draw(first, config.palette(), &viewport);
+                    turbo_next_ms = timing::millis() + turbo::PLAYBACK_DELAYS_MS[turbo_speed];
+                    state = AppState::TurboReplay {
+                        generation: first,
+                        playing: true,
+                    };
+                } else if pressed.key_down(key::OK) {
+                    draw_board(&board, config.palette(), &viewport);
+                    draw_turbo_hud(&turbo_log, None, simulation.reference_stepper.is_some());
+                    rate.restart();
+                    state = AppState::Running;
+                } else if pressed.key_down(key::BACK) {
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                }
+            }
+            AppState::TurboReplay {
+                ref mut generation,
+                ref mut playing,
+            } => {
+                let (first, last) = turbo_log.range();
+                let step = turbo_ramp.step(
+                    timing::millis(),
+                    &keyboard_state,
+                    &pressed,
+                    (key::LEFT, key::RIGHT),
+                );
+                if pressed.key_down(key::UP) {
+                    turbo_speed = (turbo_speed + 1).min(turbo::PLAYBACK_DELAYS_MS.len() - 1);
+                } else if pressed.key_down(key::DOWN) {
+                    turbo_speed = turbo_speed.saturating_sub(1);
+                }
+                let now = timing::millis();
+                if pressed.key_down(key::BACK) {
+                    draw_turbo_paused(&turbo_log);
+                    state = AppState::TurboPaused;
+                } else {
+                    let next = if pressed.key_down(key::EXE) {
+                        *playing = !*playing;
+                        turbo_next_ms = now + turbo::PLAYBACK_DELAYS_MS[turbo_speed];
+                        // Playing again from the end starts over
+                        (*playing && *generation == last).then_some(first)
+                    } else if step != 0 {
+                        *playing = false;
+                        Some(ramp::adjust(*generation, step, (first, last)))
+                    } else if *playing && now >= turbo_next_ms {
+                        turbo_next_ms = now + turbo::PLAYBACK_DELAYS_MS[turbo_speed];
+                        *playing = *generation + 1 < last;
+                        Some((*generation + 1).min(last))
+                    } else {
+                        None
+                    };
+                    if let Some(next) = next {
+                        *generation = next;
+                        turbo_log.draw(next, config.palette(), &viewport);
+                    }
+                }
+            }
+            AppState::Replay { ref mut frame } => {
+                if pressed.key_down(key::BACK) {
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                } else {
+                    if pressed.key_down(key::LEFT) {
+                        replay_speed = replay_speed.saturating_sub(1);
+                    } else if pressed.key_down(key::RIGHT) {
+                        replay_speed = (replay_speed + 1).min(replay::FRAME_DELAYS_MS.len() - 1);
+                    }
+                    let now = timing::millis();
+                    if now >= replay_next_ms {
+                        *frame = (*frame + 1) % recording.len();
+                        recording.draw(*frame, config.palette());
+                        replay_next_ms = now + replay::FRAME_DELAYS_MS[replay_speed];
+                    }
+                }
+            }
+            AppState::Versus { .. }
+                if pressed.key_down(key::EXE) || pressed.key_down(key::BACK) =>
+            {
+                // The board of the rule is already on the board, EXE keeps Conway's instead.
+                // Not an edit, the undo is still the board from before both.
+                if let (true, AppState::Versus { reference, .. }) =
+                    (pressed.key_down(key::EXE), &state)
+                {
+                    reference.unpack(&mut board);
+                }
+                // Either board evolved without the buffer
+                simulation.edge.clear();
+                reveal_board(&board, config.palette(), &viewport);
+                state = AppState::Editor;
+            }
+            AppState::Versus {
+                ref mut reference,
+                ref mut playing,
+            } => {
+                if pressed.key_down(key::OK) {
+                    *playing = !*playing;
+                }
+                if *playing {
+                    run_once_sync(
+                        &mut board,
+                        &LifeLike {
+                            rule: config.rule,
+                            boundary: config.boundary,
+                        },
+                    );
+                    let mut conway = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+                    reference.unpack(&mut conway);
+                    run_once_sync(
+                        &mut conway,
+                        &LifeLike {
+                            rule: Rule::CONWAY,
+                            boundary: config.boundary,
+                        },
+                    );
+                    *reference = PackedBoard::pack(&conway);
+                    generation += 1;
+                    draw_versus(&board, reference, generation, config.theme());
+                }
+                timing::msleep(50);
+            }
+            AppState::Keypad { ref mut in_editor } => {
+                if pressed.key_down(key::LEFT) || pressed.key_down(key::RIGHT) {
+                    *in_editor = !*in_editor;
+                    keypad::draw(*in_editor, config.theme());
+                } else if pressed.key_down(key::BACK) {
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                }
+            }
+            AppState::Diff | AppState::HeatMap | AppState::Records => {
+                if pressed.key_down(key::BACK) {
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                }
+            }
+            AppState::Library => {
+                if pressed.key_down(key::UP) {
+                    library.select_previous();
+                    library.draw();
+                } else if pressed.key_down(key::DOWN) {
+                    library.select_next();
+                    library.draw();
+                } else if pressed.key_down(key::EXE) || pressed.key_down(key::BACK) {
+                    if pressed.key_down(key::EXE) {
+                        place_pattern(
+                            &mut edit::begin_hidden_edit(
+                                &mut board,
+                                &mut undo,
+                                &mut simulation.edge,
+                            ),
+                            library.selected(),
+                            pointer.position(),
+                        );
+                    }
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                }
+            }
+            AppState::RuleEditor => {
+                let rule = config.rule;
+                if rule_editor.update(&pressed, &mut config.rule) {
+                    if config.rule != rule {
+                        config.save();
+                    }
+                    rule_editor.draw(&config.rule, config.theme());
+                } else if pressed.key_down(key::BACK) {
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                }
+            }
+            AppState::Scene(_) => {
+                let step = match &mut player {
+                    Some(player) if !pressed.key_down(key::BACK) => {
+                        player.step(&mut board, &mut config, pressed.key_down(key::EXE))
+                    }
+                    _ => Step::Done,
+                };
+                match step {
+                    Step::Generation => {
+                        let changes = run_once(&mut board, &config, &mut simulation);
+                        generation += 1;
+                        render.flush(&board, &changes, config.palette(), &viewport);
+                        graph.push(changes.born.len() as u32, changes.died.len() as u32);
+                        if show_graph {
+                            graph.draw(config.theme());
+                        }
+                        pacer.wait(config.speed.period_us());
+                    }
+                    Step::Skip(generations) => {
+                        evolve_n(&mut board, &config, &mut simulation, generations);
+                        generation += generations;
+                        draw_board(&board, config.palette(), &viewport);
+                    }
+                    Step::Redraw => draw_board(&board, config.palette(), &viewport),
+                    Step::Caption(text, duration_ms) => toast.show(text, duration_ms),
+                    Step::Idle => {}
+                    Step::Done => {
+                        if let Some(player) = player.take() {
+                            player.stop(&mut config);
+                        }
+                        toast.hide();
+                        draw_board(&board, config.palette(), &viewport);
+                        state = AppState::Editor;
+                    }
+                }
+            }
+            AppState::PatternEditor {
+                origin,
+                ref mut cells,
+            } => {
+                let origin = Cursor::clamped((origin.x as i32, origin.y as i32));
+                let (from, to) = (origin.position(), pointer.position());
+                let relative = (to.x as i16 - from.x as i16, to.y as i16 - from.y as i16);
+                if showing_rle {
+                    if pressed.key_down(key::BACK) {
+                        showing_rle = false;
+                        draw_pattern_editor(
+                            (origin, cells),
+                            pointer,
+                            (config.palette(), config.theme()),
+                            &viewport,
+                        );
+                    }
+                } else if pressed.key_down(key::BACK) {
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                } else if pressed.key_down(key::EXE) {
+                    let now = timing::millis();
+                    if pattern_exe_at.is_some_and(|at| now - at < DOUBLE_PRESS_MS) {
+                        pattern_exe_at = None;
+                        let mut rle: String<1024> = String::new();
+                        if patterns::encode(cells, config.rule, caption.as_str(), &mut rle).is_ok()
+                        {
+                            draw_rle(&rle);
+                            showing_rle = true;
+                        } else {
+                            toast.show("Pattern too long to encode\0", 2000);
+                        }
+                    } else {
+                        pattern_exe_at = Some(now);
+                        if !cells.contains(&relative) && cells.push(relative).is_err() {
+                            toast.show("The pattern is full\0", 2000);
+                        }
+                        draw_pattern_editor(
+                            (origin, cells),
+                            pointer,
+                            (config.palette(), config.theme()),
+                            &viewport,
+                        );
+                    }
+                } else if pressed.key_down(key::MINUS) {
+                    let distance = |&(dx, dy): &(i16, i16)| {
+                        (dx as i32 - relative.0 as i32).pow(2)
+                            + (dy as i32 - relative.1 as i32).pow(2)
+                    };
+                    let nearest = cells
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, cell)| distance(cell))
+                        .map(|(i, _)| i);
+                    if let Some(i) = nearest {
+                        cells.swap_remove(i);
+                        draw_pattern_editor(
+                            (origin, cells),
+                            pointer,
+                            (config.palette(), config.theme()),
+                            &viewport,
+                        );
+                    }
+                } else {
+                    if move_pointer(&keyboard_state, &mut pointer, config.boundary).is_some() {
+                        viewport.follow(pointer.position());
+                        draw_pattern_editor(
+                            (origin, cells),
+                            pointer,
+                            (config.palette(), config.theme()),
+                            &viewport,
+                        );
+                    }
                     timing::msleep(50);
                 }
             }
+            AppState::Import => {
+                if pressed.key_down(key::LEFT)
+                    || pressed.key_down(key::RIGHT)
+                    || pressed.key_down(key::UP)
+                    || pressed.key_down(key::DOWN)
+                {
+                    if let Some(staged) = &mut staging {
+                        if pressed.key_down(key::UP) {
+                            staged.rotate();
+                        } else if pressed.key_down(key::DOWN) {
+                            staged.flip();
+                        } else {
+                            staged.toggle_mode();
+                        }
+                        staged.draw(config.theme());
+                    }
+                } else if pressed.key_down(key::EXE) || pressed.key_down(key::BACK) {
+                    match staging.take() {
+                        Some(staged) if pressed.key_down(key::EXE) => {
+                            staged.apply(&mut edit::begin_hidden_edit(
+                                &mut board,
+                                &mut undo,
+                                &mut simulation.edge,
+                            ));
+                            if let Some(rule) = staged.rule {
+                                config.rule = rule;
+                                config.save();
+                            }
+                        }
+                        _ => {}
+                    }
+                    reveal_board(&board, config.palette(), &viewport);
+                    state = AppState::Editor;
+                }
+            }
+        }
+
+        // Drawn over whatever the state drew, and for the state it may have switched to
+        let mode = match state {
+            AppState::Diff => Some(RenderMode::Diff),
+            AppState::Versus { .. } => Some(RenderMode::Versus),
+            AppState::HeatMap => Some(RenderMode::HeatMap),
+            AppState::Cone { actual, .. } => Some(RenderMode::Cone { actual }),
+            AppState::Running if show_frontier && !turbo => Some(RenderMode::Frontier),
+            _ => None,
+        };
+        // A cone switching to the actual one is still the same screen
+        if mode.map(RenderMode::bit) != render_mode.map(RenderMode::bit) {
+            show_legend = false;
+            legend_until_ms = 0;
+            if let Some(mode) = mode.filter(|mode| legends_introduced & mode.bit() == 0) {
+                legends_introduced |= mode.bit();
+                legend_until_ms = timing::millis() + LEGEND_INTRO_MS;
+            }
+        } else if mode.is_some() && pressed.key_down(key::DOT) {
+            show_legend = !show_legend && timing::millis() >= legend_until_ms;
+            legend_until_ms = 0;
+        }
+        render_mode = mode;
+        match mode {
+            Some(mode) if show_legend || timing::millis() < legend_until_ms => {
+                legend_area = Some(legend::draw(mode, config.palette(), config.theme()));
+            }
+            _ => {
+                if let Some(area) = legend_area.take() {
+                    match state {
+                        AppState::Diff => {
+                            if let [Some(a), Some(b)] = &snapshots {
+                                diff::draw_diff(&diff::diff_snapshots(a, b), config.theme());
+                            }
+                        }
+                        AppState::Versus { ref reference, .. } => {
+                            draw_versus(&board, reference, generation, config.theme())
+                        }
+                        AppState::HeatMap => draw_heat_map(&board),
+                        AppState::Cone {
+                            origin,
+                            generations,
+                            actual,
+                        } => draw_cone(&board, (origin, generations, actual), &config, &viewport),
+                        // Left for another screen drawn over it already
+                        _ if state.covers_board() => {}
+                        _ => {
+                            redraw_area(&board, area, config.palette(), &viewport);
+                            // The frontier comes back with the next frame
+                            render.invalidate();
+                        }
+                    }
+                }
+            }
+        }
+
+        // Drawn last, over whatever the state drew, and for the state it may have switched to
+        if keyboard_state.key_down(key::SHIFT) && !state.covers_board() {
+            hints_area = Some(hints::draw(matches!(state, AppState::Editor)));
+        } else if let Some(area) = hints_area.take() {
+            if !state.covers_board() {
+                redraw_area(&board, area, config.palette(), &viewport);
+            }
+        }
+
+        // Explains ALPHA next to the pointer, it's redrawn every frame while held
+        if keyboard_state.key_down(key::ALPHA)
+            && !keyboard_state.key_down(key::SHIFT)
+            && matches!(state, AppState::Editor)
+        {
+            let position = pointer.position();
+            if let Some(cell) = viewport.cell_rect(position) {
+                let mut text: String<48> = String::new();
+                if keyboard_state.key_down(key::PI) {
+                    // Why cells near the edges don't evolve like the others
+                    let neighborhood = config.rule.neighborhood;
+                    let outside = rim::neighbors_outside(position, neighborhood);
+                    match (config.boundary, config.edge_buffer) {
+                        (BoundaryMode::Wrap, _) => {
+                            write!(text, "{} neighbors on the other side\0", outside)
+                        }
+                        (BoundaryMode::Dead, true) => {
+                            write!(text, "{} neighbors in the edge buffer\0", outside)
+                        }
+                        (BoundaryMode::Dead, false) => {
+                            write!(
+                                text,
+                                "{} of {} neighbors off the board, dead\0",
+                                outside,
+                                neighborhood.size()
+                            )
+                        }
+                    }
+                } else {
+                    // Board coordinates, the same whatever part of it is in view
+                    write!(
+                        text,
+                        "({}, {}) ALPHA + arrow: go to that edge\0",
+                        position.x, position.y
+                    )
+                }
+                .unwrap();
+                tooltip.draw(
+                    &text,
+                    Point::new(cell.x + cell.width, cell.y + cell.height),
+                    config.palette(),
+                );
+            }
+        }
+
+        // How the last run ended, over the board until it's edited. Only keys change the
+        // board or draw over the line, so it's checked and drawn again on frames with some.
+        let stale = !status_shown || keyboard_state.any_down() || glide.is_some();
+        let status = matches!(state, AppState::Editor)
+            && if stale {
+                last_outcome
+                    .as_mut()
+                    .is_some_and(|outcome| outcome.is_current(&board))
+            } else {
+                status_shown
+            };
+        if status && stale {
+            if let Some(outcome) = &last_outcome {
+                outcome.draw_status(config.palette());
+            }
+        } else if status_shown && !state.covers_board() {
+            redraw_area(
+                &board,
+                LastRunOutcome::status_area(),
+                config.palette(),
+                &viewport,
+            );
+        }
+        status_shown = status;
+
+        // The sides of the screen, not redrawn with the cells under them while running
+        let tint = state_tint(&state, config.theme()).filter(|_| config.state_tint);
+        if let Some(tint) = tint {
+            for strip in STATE_STRIPS {
+                display::push_rect_uniform(strip, tint);
+            }
+        } else if strips_shown && !state.covers_board() {
+            for strip in STATE_STRIPS {
+                redraw_area(&board, strip, config.palette(), &viewport);
+            }
+        }
+        strips_shown = tint.is_some();
+
+        // Over the status line too, it's there to be photographed
+        if caption.is_shown() && !state.covers_board() {
+            caption.draw(false, config.palette());
+        }
+        if record_replay && !state.covers_board() {
+            draw_recording_indicator();
+        }
+
+        // Runs wait for the screen only on the frames they draw, and the editor sleeps anyway
+        if !matches!(state, AppState::Running | AppState::Editor) {
+            display::wait_for_vblank();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulation(mask: Option<Board<bool>>) -> Simulation {
+        Simulation {
+            edge: EdgeBuffer::new(),
+            rng: Rng::new(0x1234_5678),
+            mask,
+            reference_stepper: None,
+        }
+    }
+
+    #[test]
+    fn noise_flips_are_part_of_the_changes() {
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        let mut simulation = simulation(None);
+        for _ in 0..20 {
+            let before = board;
+            let mut changes = Changes::new();
+            assert!(apply_noise(&mut board, 100, &mut simulation, &mut changes));
+            for x in 0..LINE_SIZE as usize {
+                for y in 0..COLUMN_SIZE as usize {
+                    let cell = (x as u8, y as u8);
+                    let flipped = before[x][y] != board[x][y];
+                    let listed = if board[x][y] {
+                        changes.born.contains(&cell)
+                    } else {
+                        changes.died.contains(&cell)
+                    };
+                    assert_eq!(flipped, listed, "{:?}", cell);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn noise_stays_inside_the_mask() {
+        let rect = CellRect::from_corners(LogicalPos::new(10, 10), LogicalPos::new(20, 15));
+        let mask = selection::create_rect_mask(rect);
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        let mut simulation = simulation(Some(mask));
+        for _ in 0..20 {
+            apply_noise(&mut board, 500, &mut simulation, &mut Changes::new());
         }
+        for x in 0..LINE_SIZE as usize {
+            for y in 0..COLUMN_SIZE as usize {
+                assert!(mask[x][y] || !board[x][y], "({}, {})", x, y);
+            }
+        }
+        assert!(analysis::population(&board) > 0);
+    }
+
+    #[test]
+    fn no_noise_draws_nothing() {
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        let mut simulation = simulation(None);
+        let mut changes = Changes::new();
+        assert!(!apply_noise(&mut board, 0, &mut simulation, &mut changes));
+        assert_eq!(changes.len(), 0);
+        assert_eq!(simulation.rng.state(), Rng::new(0x1234_5678).state());
+    }
+
+    #[test]
+    fn undone_strides_replay_the_same() {
+        let config = AppConfig {
+            edge_buffer: true,
+            noise: 5,
+            ..AppConfig::default()
+        };
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        autosolve::seed_soup(&mut board, 0x57_21DE, 35);
+        let mut simulation = simulation(None);
+        let mut checkpoints = Checkpoints::new();
+        let stride = |board: &mut Board<bool>, simulation: &mut Simulation| {
+            for _ in 0..SHORT_STRIDE {
+                let mut changes = run_once(board, &config, simulation);
+                apply_noise(board, config.noise as u32, simulation, &mut changes);
+            }
+        };
+
+        let before = board;
+        let rewind = Rewind::before(&simulation, &checkpoints);
+        let start = StrideStart::before(&board, 7, rewind);
+        stride(&mut board, &mut simulation);
+        let after = board;
+        assert!(after != before);
+        let rng_after = simulation.rng.state();
+
+        assert_eq!(start.undo(&mut board, &mut simulation, &mut checkpoints), 7);
+        assert!(board == before);
+        assert_eq!(simulation.rng.state(), Rng::new(0x1234_5678).state());
+        // The edge buffer is back too, or the stride would go another way
+        stride(&mut board, &mut simulation);
+        assert!(board == after);
+        assert_eq!(simulation.rng.state(), rng_after);
+    }
+
+    /// Every cell takes the opposite of its state, never quiescent so each part crawls
+    struct Invert;
+
+    impl Automaton for Invert {
+        fn next_state(&self, board: &Board<bool>, x: usize, y: usize) -> bool {
+            !board[x][y]
+        }
+    }
+
+    #[test]
+    fn runaway_generations_fall_back_to_the_reference_stepper() {
+        let mut config = AppConfig::default();
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        autosolve::seed_soup(&mut board, 0xD06, 30);
+        let mut expected = board;
+        run_once_sync(&mut expected, &Invert);
+        let mut simulation = simulation(None);
+        let mut in_progress = None;
+
+        // One cell per part, the generation needs thousands of them
+        let mut parts = 0;
+        let changes = loop {
+            let step = (&mut in_progress, Invert);
+            match run_in_parts(&mut board, &config, &mut simulation, step, 1) {
+                Some(changes) => break changes,
+                None => parts += 1,
+            }
+            assert!(parts <= WATCHDOG_ADVANCES, "the watchdog never fired");
+        };
+        assert_eq!(parts, WATCHDOG_ADVANCES);
+        assert!(simulation.reference_stepper.is_some());
+        assert!(in_progress.is_none());
+        // The generation was dropped and run again at once, by the same automaton
+        assert!(board == expected);
+        assert_eq!(changes.len(), BOARD_SIZE);
+
+        // The next generations are run at once, whatever the budget
+        let step = (&mut in_progress, Invert);
+        assert!(run_in_parts(&mut board, &config, &mut simulation, step, 1).is_some());
+        assert!(in_progress.is_none());
+        run_once_sync(&mut expected, &Invert);
+        assert!(board == expected);
+
+        // Until the rule changes, its generations are split again
+        config.rule = config.rule.next_preset();
+        let step = (&mut in_progress, Invert);
+        assert!(run_in_parts(&mut board, &config, &mut simulation, step, 1).is_none());
+        assert!(simulation.reference_stepper.is_none());
+        assert!(in_progress.is_some());
+        assert!(board == expected);
+    }
+
+    #[test]
+    fn whole_generations_stay_under_the_watchdog() {
+        let config = AppConfig::default();
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        let mut simulation = simulation(None);
+        let mut in_progress = None;
+        // Without quiescence every cell is visited, the slowest a generation gets
+        for _ in 0..3 {
+            let before = board;
+            let changes = loop {
+                let step = (&mut in_progress, Invert);
+                let parts =
+                    run_in_parts(&mut board, &config, &mut simulation, step, STEP_CELL_BUDGET);
+                if let Some(changes) = parts {
+                    break changes;
+                }
+            };
+            assert_eq!(changes.len(), BOARD_SIZE);
+            assert!(board
+                .iter()
+                .flatten()
+                .zip(before.iter().flatten())
+                .all(|(a, b)| a != b));
+        }
+        assert!(simulation.reference_stepper.is_none());
+    }
+
+    #[test]
+    fn asynchronous_updates_see_the_cells_already_updated() {
+        // A horizontal blinker
+        let mut start = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        (10..13).for_each(|x| start[x][10] = true);
+        let run = |update_mode, seed| {
+            let config = AppConfig {
+                update_mode,
+                ..AppConfig::default()
+            };
+            let mut board = start;
+            let mut simulation = simulation(None);
+            simulation.rng = Rng::new(seed);
+            let changes = run_unmasked(&mut board, &config, &mut simulation);
+            (board, changes.born.len(), changes.died.len())
+        };
+
+        // Synchronous, every cell sees the blinker as it was and it turns vertical
+        let (synchronous, born, died) = run(UpdateMode::Synchronous, 1);
+        let mut vertical = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        (9..12).for_each(|y| vertical[11][y] = true);
+        assert!(synchronous == vertical);
+        assert_eq!((born, died), (2, 2));
+
+        // Row by row, (11, 9) is born first and (12, 9) sees it, so it's born too. The
+        // middle of the blinker then has 4 neighbors and dies, its ends 2 and survive.
+        let (row_major, born, died) = run(UpdateMode::AsyncRowMajor, 1);
+        let mut expected = start;
+        expected[11][9] = true;
+        expected[12][9] = true;
+        expected[11][10] = false;
+        assert!(row_major == expected);
+        assert_eq!((born, died), (2, 1));
 
-        display::wait_for_vblank();
+        // At random, the order depends on the seed alone
+        let (random, ..) = run(UpdateMode::AsyncRandom, 7);
+        assert!(random == run(UpdateMode::AsyncRandom, 7).0);
+        assert!((0..8).any(|seed| run(UpdateMode::AsyncRandom, seed).0 != synchronous));
     }
 }
@@ -0,0 +1,119 @@
+use crate::neighbors::wrap;
+use crate::rule::BoundaryMode;
+use crate::Board;
+
+/// Offsets of the 4 orthogonal neighbors of a cell
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// States of the orthogonal neighbors of a cell, across the edges on a torus, those
+/// outside the board being dead otherwise
+fn orthogonal_neighbors(
+    board: &Board<bool>,
+    (x, y): (usize, usize),
+    boundary: BoundaryMode,
+) -> impl Iterator<Item = bool> + '_ {
+    ORTHOGONAL.iter().map(move |&(dx, dy)| {
+        wrap((x as isize + dx, y as isize + dy), boundary).is_some_and(|(x, y)| board[x][y])
+    })
+}
+
+/// Shrink the alive regions by a cell: a cell stays alive only if its 4 orthogonal
+/// neighbors are alive too
+///
+/// Followed by a [`dilate`], it removes the specks and bumps too thin to survive it, and
+/// after one it fills the holes and gaps too narrow to survive the dilation.
+pub fn erode(board: &Board<bool>, boundary: BoundaryMode) -> Board<bool> {
+    let mut eroded = *board;
+    for (x, column) in eroded.iter_mut().enumerate() {
+        for (y, cell) in column.iter_mut().enumerate() {
+            *cell &= orthogonal_neighbors(board, (x, y), boundary).all(|alive| alive);
+        }
+    }
+    eroded
+}
+
+/// Grow the alive regions by a cell: a cell comes alive if any of its 4 orthogonal
+/// neighbors is alive
+pub fn dilate(board: &Board<bool>, boundary: BoundaryMode) -> Board<bool> {
+    let mut dilated = *board;
+    for (x, column) in dilated.iter_mut().enumerate() {
+        for (y, cell) in column.iter_mut().enumerate() {
+            *cell |= orthogonal_neighbors(board, (x, y), boundary).any(|alive| alive);
+        }
+    }
+    dilated
+}
+
+/// Remove the isolated cells and the parts of the alive regions too thin for their size
+///
+/// The board is eroded `passes` times then dilated as many times, so what's left are the
+/// regions wide enough for a diamond of `passes` cells around its center, restored to their
+/// shape. A single pass removes isolated cells and lines up to 2 cells wide.
+pub fn smooth_board(board: &mut Board<bool>, passes: u8, boundary: BoundaryMode) {
+    for _ in 0..passes {
+        *board = erode(board, boundary);
+    }
+    for _ in 0..passes {
+        *board = dilate(board, boundary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{COLUMN_SIZE, LINE_SIZE};
+
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    const LAST: (usize, usize) = (LINE_SIZE as usize - 1, COLUMN_SIZE as usize - 1);
+
+    fn alive(board: &Board<bool>) -> usize {
+        board.iter().flatten().filter(|&&alive| alive).count()
+    }
+
+    #[test]
+    fn dilating_a_corner_reaches_the_other_corners_on_a_torus() {
+        let mut board = EMPTY;
+        board[0][0] = true;
+        let dead = dilate(&board, BoundaryMode::Dead);
+        assert_eq!(alive(&dead), 3);
+        let torus = dilate(&board, BoundaryMode::Wrap);
+        assert_eq!(alive(&torus), 5);
+        assert!(torus[LAST.0][0] && torus[0][LAST.1] && torus[1][0] && torus[0][1]);
+    }
+
+    #[test]
+    fn regions_across_the_edges_erode_as_one_on_a_torus() {
+        // A 3 × 3 square split by the corner of the board
+        let mut board = EMPTY;
+        for &x in &[LAST.0, 0, 1] {
+            for &y in &[LAST.1, 0, 1] {
+                board[x][y] = true;
+            }
+        }
+        let torus = erode(&board, BoundaryMode::Wrap);
+        assert_eq!(alive(&torus), 1);
+        assert!(torus[0][0]);
+        // With dead edges, the cells along them have a dead neighbor
+        assert_eq!(alive(&erode(&board, BoundaryMode::Dead)), 0);
+
+        let mut smoothed = board;
+        smooth_board(&mut smoothed, 1, BoundaryMode::Wrap);
+        assert!(smoothed == dilate(&torus, BoundaryMode::Wrap));
+        assert_eq!(alive(&smoothed), 5);
+    }
+
+    #[test]
+    fn both_topologies_agree_away_from_the_edges() {
+        let mut board = EMPTY;
+        // Columns of uneven heights
+        for (x, column) in board.iter_mut().enumerate().take(30).skip(20) {
+            column[10..10 + x % 7]
+                .iter_mut()
+                .for_each(|cell| *cell = true);
+        }
+        for boundary in [BoundaryMode::Dead, BoundaryMode::Wrap] {
+            assert!(erode(&board, boundary) == erode(&board, BoundaryMode::Dead));
+            assert!(dilate(&board, boundary) == dilate(&board, BoundaryMode::Dead));
+        }
+    }
+}
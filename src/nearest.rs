@@ -0,0 +1,65 @@
+use crate::viewport::LogicalPos;
+use crate::{Board, COLUMN_SIZE, LINE_SIZE};
+use heapless::binary_heap::{BinaryHeap, Min};
+
+/// Cells waiting in the search at once, enough for the widest diamond fitting on the board
+const FRONTIER: usize = 2 * (LINE_SIZE as usize + COLUMN_SIZE as usize);
+
+/// Positions by increasing distance, the closest coming out first
+///
+/// Positions are stored as bytes to keep the queue small, which the board fits in.
+pub struct DistanceQueue<const N: usize> {
+    heap: BinaryHeap<(u32, (u8, u8)), Min, N>,
+}
+
+impl<const N: usize> DistanceQueue<N> {
+    pub const fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Queue a position at `distance`, returns false if the queue is full
+    pub fn push(&mut self, distance: u32, cell: LogicalPos) -> bool {
+        self.heap
+            .push((distance, (cell.x as u8, cell.y as u8)))
+            .is_ok()
+    }
+
+    /// The closest position queued, the top left one among equally close ones
+    pub fn pop(&mut self) -> Option<(u32, LogicalPos)> {
+        let (distance, (x, y)) = self.heap.pop()?;
+        Some((distance, LogicalPos::new(x as u16, y as u16)))
+    }
+}
+
+/// The alive cell closest to `from` by Manhattan distance, other than `from` itself
+///
+/// Cells are visited from `from` outward, so the search stops as soon as one is found
+/// instead of going through the whole board. The board edges aren't crossed.
+pub fn nearest_live_cell(board: &Board<bool>, from: LogicalPos) -> Option<LogicalPos> {
+    let mut queued = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    let mut queue: DistanceQueue<FRONTIER> = DistanceQueue::new();
+    queued[from.x as usize][from.y as usize] = true;
+    queue.push(0, from);
+
+    while let Some((distance, cell)) = queue.pop() {
+        if distance > 0 && board[cell.x as usize][cell.y as usize] {
+            return Some(cell);
+        }
+        let neighbors = [
+            (cell.x.wrapping_sub(1), cell.y),
+            (cell.x + 1, cell.y),
+            (cell.x, cell.y.wrapping_sub(1)),
+            (cell.x, cell.y + 1),
+        ];
+        for &(x, y) in neighbors.iter() {
+            if x < LINE_SIZE && y < COLUMN_SIZE && !queued[x as usize][y as usize] {
+                queued[x as usize][y as usize] = true;
+                // The frontier is a diamond around `from`, it can't outgrow the queue
+                queue.push(distance + 1, LogicalPos::new(x, y));
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,150 @@
+use crate::rule::{BoundaryMode, Neighborhood};
+use crate::viewport::LogicalPos;
+use crate::{Board, COLUMN_SIZE, LINE_SIZE};
+
+/// The cell at coordinates that may be off the board by one, `None` if it's outside
+///
+/// On a torus, the cells off an edge are the ones along the opposite edge.
+pub fn wrap((x, y): (isize, isize), boundary: BoundaryMode) -> Option<(usize, usize)> {
+    let (width, height) = (LINE_SIZE as isize, COLUMN_SIZE as isize);
+    match boundary {
+        BoundaryMode::Wrap => Some((x.rem_euclid(width) as usize, y.rem_euclid(height) as usize)),
+        BoundaryMode::Dead if (0..width).contains(&x) && (0..height).contains(&y) => {
+            Some((x as usize, y as usize))
+        }
+        BoundaryMode::Dead => None,
+    }
+}
+
+/// Neighbors of a cell, in the order of [`Neighborhood::offsets`]
+///
+/// With dead edges the ones off the board are left out, on a torus they're the cells across
+/// the edges.
+pub fn neighbors(
+    cell: LogicalPos,
+    neighborhood: Neighborhood,
+    boundary: BoundaryMode,
+) -> impl Iterator<Item = LogicalPos> {
+    let (x, y) = (cell.x as isize, cell.y as isize);
+    neighborhood.offsets().iter().filter_map(move |(dx, dy)| {
+        wrap((x + dx, y + dy), boundary).map(|(x, y)| LogicalPos::new(x as u16, y as u16))
+    })
+}
+
+/// Alive neighbors of a cell, those off the board being dead unless it wraps around
+///
+/// Away from the edges, the cells are read as is without going through [`wrap`].
+pub fn neighbor_count(
+    board: &Board<bool>,
+    cell: LogicalPos,
+    neighborhood: Neighborhood,
+    boundary: BoundaryMode,
+) -> u8 {
+    let (x, y) = (cell.x as usize, cell.y as usize);
+    if (1..LINE_SIZE as usize - 1).contains(&x) && (1..COLUMN_SIZE as usize - 1).contains(&y) {
+        return neighborhood
+            .offsets()
+            .iter()
+            .map(|&(dx, dy)| board[x.wrapping_add_signed(dx)][y.wrapping_add_signed(dy)] as u8)
+            .sum();
+    }
+    neighbors(cell, neighborhood, boundary)
+        .map(|neighbor| board[neighbor.x as usize][neighbor.y as usize] as u8)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use crate::rule::{LifeLike, Rule};
+    use crate::run_once_sync;
+    use std::vec::Vec;
+
+    const WIDTH: usize = LINE_SIZE as usize;
+    const HEIGHT: usize = COLUMN_SIZE as usize;
+
+    /// Neighbors of `(x, y)` by going around the 3×3 square, without [`wrap`]
+    fn brute_neighbors(
+        (x, y): (usize, usize),
+        neighborhood: Neighborhood,
+        boundary: BoundaryMode,
+    ) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        for dy in [-1, 0, 1] {
+            for dx in [-1, 0, 1] {
+                let diagonal = dx != 0 && dy != 0;
+                if (dx, dy) == (0, 0) || (neighborhood == Neighborhood::Diagonal && !diagonal) {
+                    continue;
+                }
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                let inside = nx >= 0 && ny >= 0 && nx < WIDTH as isize && ny < HEIGHT as isize;
+                if inside {
+                    found.push((nx as usize, ny as usize));
+                } else if boundary == BoundaryMode::Wrap {
+                    found.push((
+                        (nx + WIDTH as isize) as usize % WIDTH,
+                        (ny + HEIGHT as isize) as usize % HEIGHT,
+                    ));
+                }
+            }
+        }
+        found
+    }
+
+    fn random_board(rng: &mut Rng, density: u32) -> Board<bool> {
+        let mut board = [[false; HEIGHT]; WIDTH];
+        board
+            .iter_mut()
+            .flatten()
+            .for_each(|cell| *cell = rng.below(100) < density);
+        board
+    }
+
+    #[test]
+    fn neighbors_are_the_cells_around() {
+        for neighborhood in [Neighborhood::Moore, Neighborhood::Diagonal] {
+            for boundary in [BoundaryMode::Dead, BoundaryMode::Wrap] {
+                for x in 0..WIDTH {
+                    for y in 0..HEIGHT {
+                        let cell = LogicalPos::new(x as u16, y as u16);
+                        let mut found: Vec<_> = neighbors(cell, neighborhood, boundary)
+                            .map(|n| (n.x as usize, n.y as usize))
+                            .collect();
+                        let mut expected = brute_neighbors((x, y), neighborhood, boundary);
+                        found.sort_unstable();
+                        expected.sort_unstable();
+                        assert_eq!(found, expected, "({}, {})", x, y);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neighbor_counts_and_the_stepper_agree_with_brute_force() {
+        let mut rng = Rng::new(0x4E1_6B0);
+        let rules = [Rule::CONWAY, Rule::diagonal(&[1], &[1, 2])];
+        for density in [5, 30, 50, 80, 100] {
+            let board = random_board(&mut rng, density);
+            for rule in rules {
+                for boundary in [BoundaryMode::Dead, BoundaryMode::Wrap] {
+                    let mut next = board;
+                    run_once_sync(&mut next, &LifeLike { rule, boundary });
+                    for x in 0..WIDTH {
+                        for y in 0..HEIGHT {
+                            let expected = brute_neighbors((x, y), rule.neighborhood, boundary)
+                                .iter()
+                                .filter(|&&(nx, ny)| board[nx][ny])
+                                .count() as u8;
+                            let cell = LogicalPos::new(x as u16, y as u16);
+                            let count = neighbor_count(&board, cell, rule.neighborhood, boundary);
+                            assert_eq!(count, expected, "({}, {})", x, y);
+                            assert_eq!(next[x][y], rule.next_state(board[x][y], expected));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use crate::analysis::population;
+use crate::eadk::{display, Point, Rect, SCREEN_WIDTH};
+use crate::palette::ColorPalette;
+use crate::period::{checksum, History};
+use crate::viewport::{LogicalPos, Viewport};
+use crate::Board;
+use core::fmt::{self, Display, Formatter, Write};
+use heapless::String;
+
+/// Space between the border of the status line and its text
+const PADDING: u16 = 2;
+
+/// Why a run stopped
+#[derive(Clone, Copy, PartialEq)]
+pub enum StopReason {
+    /// Left by hand while the board was still changing
+    Stopped,
+    /// No cell left alive
+    Extinct,
+    /// The board stopped changing
+    Stable,
+    /// The board repeats with a period greater than 1
+    Cycling,
+}
+
+impl Display for StopReason {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            StopReason::Stopped => "stopped",
+            StopReason::Extinct => "extinct",
+            StopReason::Stable => "stable",
+            StopReason::Cycling => "cycling",
+        })
+    }
+}
+
+/// What became of a cursor move while the status line is shown
+#[derive(Clone, Copy, PartialEq)]
+pub enum Dodge {
+    /// It didn't go under the line, or was already there
+    Clear,
+    /// The view scrolled up, for the cursor to be just below the line
+    Scrolled,
+    /// The view couldn't scroll that far, the cursor stays where it was
+    Stopped,
+}
+
+/// How the last run ended, shown until the board is edited
+pub struct LastRunOutcome {
+    pub reason: StopReason,
+    pub generation: u32,
+    pub population: u32,
+    /// Period the board repeats with, if it was noticed
+    pub period: Option<u32>,
+    /// Checksum of the board the run ended on, `None` once it was edited
+    board: Option<u32>,
+}
+
+impl LastRunOutcome {
+    /// Whether the board is still the one the run ended on
+    ///
+    /// Once it was edited the outcome is out of date for good, even if the edit is undone.
+    pub fn is_current(&mut self, board: &Board<bool>) -> bool {
+        if self.board.is_some_and(|hash| hash != checksum(board)) {
+            self.board = None;
+        }
+        self.board.is_some()
+    }
+
+    /// Part of the screen covered by the status line
+    pub fn status_area() -> Rect {
+        let (_, glyph_height) = display::glyph_size(false);
+        Rect::new(0, 0, SCREEN_WIDTH, glyph_height + 2 * PADDING)
+    }
+
+    /// Rows in view the status line covers, even partly
+    pub fn covered_rows(viewport: &Viewport) -> u16 {
+        Self::status_area().height.div_ceil(viewport.cell_size)
+    }
+
+    /// Keep a cursor moving from `previous` to `next` out of the rows under the status line
+    ///
+    /// Those rows still run, but the cursor can't be seen there. One already under the line
+    /// when it was shown can still move out of it.
+    pub fn dodge(previous: LogicalPos, next: LogicalPos, viewport: &mut Viewport) -> Dodge {
+        let covered = Self::covered_rows(viewport);
+        if next.y >= viewport.origin.y + covered {
+            Dodge::Clear
+        } else if next.y >= covered {
+            viewport.origin.y = next.y - covered;
+            Dodge::Scrolled
+        } else if previous.y >= viewport.origin.y + covered {
+            Dodge::Stopped
+        } else {
+            Dodge::Clear
+        }
+    }
+
+    /// Draw the outcome in a line over the top of the board
+    pub fn draw_status(&self, palette: &ColorPalette) {
+        let mut text: String<48> = String::new();
+        write!(text, "{}\0", self).unwrap();
+        display::push_rect_uniform(Self::status_area(), palette.status_bg);
+        display::draw_string(
+            &text,
+            Point::new(PADDING, PADDING),
+            false,
+            palette.live_cell,
+            palette.status_bg,
+        );
+    }
+}
+
+impl Display for LastRunOutcome {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match (self.reason, self.period) {
+            (StopReason::Extinct, _) => return write!(f, "Extinct at gen {}", self.generation),
+            (StopReason::Cycling, Some(period)) => write!(f, "Period {}", period)?,
+            (StopReason::Stable, _) => f.write_str("Stable")?,
+            _ => f.write_str("Stopped")?,
+        }
+        write!(f, " at gen {}, {} cells", self.generation, self.population)
+    }
+}
+
+/// A run followed from its start, to tell how it ended
+pub struct RunWatch {
+    history: History,
+    /// Period of the last generation, if it repeats an earlier one
+    period: Option<u32>,
+}
+
+impl RunWatch {
+    pub fn start(board: &Board<bool>) -> Self {
+        Self {
+            history: History::new(board),
+            period: None,
+        }
+    }
+
+    /// Follow the next generation of the run
+    pub fn step(&mut self, board: &Board<bool>) {
+        self.period = self.history.push(board);
+    }
+
+    /// Period the board repeats with, once it was noticed
+    pub fn period(&self) -> Option<u32> {
+        self.period
+    }
+
+    /// How the run ended on `board`, at `generation`
+    pub fn finish(&self, board: &Board<bool>, generation: u32) -> LastRunOutcome {
+        let population = population(board);
+        let reason = match self.period {
+            _ if population == 0 => StopReason::Extinct,
+            Some(1) => StopReason::Stable,
+            Some(_) => StopReason::Cycling,
+            None => StopReason::Stopped,
+        };
+        LastRunOutcome {
+            reason,
+            generation,
+            population,
+            period: self.period,
+            board: Some(checksum(board)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cursor::Cursor;
+    use crate::rng::Rng;
+    use crate::rule::BoundaryMode;
+    use crate::viewport::ZOOM_LEVELS;
+    use crate::{COLUMN_SIZE, LINE_SIZE};
+
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+
+    fn with_cells(cells: &[(usize, usize)]) -> Board<bool> {
+        let mut board = EMPTY;
+        for &(x, y) in cells {
+            board[x][y] = true;
+        }
+        board
+    }
+
+    #[test]
+    fn runs_end_for_the_reason_the_board_shows() {
+        let block = with_cells(&[(1, 1), (1, 2), (2, 1), (2, 2)]);
+        let mut watch = RunWatch::start(&block);
+        assert!(watch.finish(&block, 0).reason == StopReason::Stopped);
+        watch.step(&block);
+        let outcome = watch.finish(&block, 1);
+        assert!(outcome.reason == StopReason::Stable);
+        assert_eq!((outcome.population, outcome.period), (4, Some(1)));
+
+        let vertical = with_cells(&[(5, 4), (5, 5), (5, 6)]);
+        let horizontal = with_cells(&[(4, 5), (5, 5), (6, 5)]);
+        let mut watch = RunWatch::start(&vertical);
+        watch.step(&horizontal);
+        watch.step(&vertical);
+        assert!(watch.finish(&vertical, 2).reason == StopReason::Cycling);
+
+        let mut watch = RunWatch::start(&vertical);
+        watch.step(&EMPTY);
+        assert!(watch.finish(&EMPTY, 1).reason == StopReason::Extinct);
+    }
+
+    #[test]
+    fn an_edit_outdates_the_outcome_for_good() {
+        let mut board = with_cells(&[(3, 3)]);
+        let mut outcome = RunWatch::start(&board).finish(&board, 0);
+        assert!(outcome.is_current(&board));
+        board[3][3] = false;
+        assert!(!outcome.is_current(&board));
+        board[3][3] = true;
+        assert!(!outcome.is_current(&board));
+    }
+
+    fn zoomed(cell_size: u16, origin: (u16, u16)) -> Viewport {
+        Viewport {
+            origin: LogicalPos::new(origin.0, origin.1),
+            cell_size,
+            grid: false,
+        }
+    }
+
+    #[test]
+    fn the_line_covers_whole_rows() {
+        for &cell_size in ZOOM_LEVELS.iter() {
+            let rows = LastRunOutcome::covered_rows(&zoomed(cell_size, (0, 0)));
+            let height = LastRunOutcome::status_area().height;
+            assert!(rows * cell_size >= height);
+            assert!((rows - 1) * cell_size < height);
+        }
+    }
+
+    #[test]
+    fn the_cursor_stops_below_the_line_of_the_whole_board() {
+        let mut viewport = Viewport::FULL;
+        let covered = LastRunOutcome::covered_rows(&viewport);
+        let at = |y| LogicalPos::new(20, y);
+        assert!(LastRunOutcome::dodge(at(covered + 1), at(covered), &mut viewport) == Dodge::Clear);
+        assert!(
+            LastRunOutcome::dodge(at(covered), at(covered - 1), &mut viewport) == Dodge::Stopped
+        );
+        // Left under the line when it was shown, it moves freely until it's out
+        assert!(LastRunOutcome::dodge(at(1), at(0), &mut viewport) == Dodge::Clear);
+        assert!(LastRunOutcome::dodge(at(0), at(1), &mut viewport) == Dodge::Clear);
+        assert!(viewport.origin == LogicalPos::new(0, 0));
+    }
+
+    #[test]
+    fn the_view_scrolls_to_keep_the_cursor_below_the_line() {
+        let mut viewport = zoomed(16, (10, 20));
+        let covered = LastRunOutcome::covered_rows(&viewport);
+        let mut cursor = LogicalPos::new(15, 20 + covered);
+        while cursor.y > covered {
+            let next = LogicalPos::new(cursor.x, cursor.y - 1);
+            assert!(LastRunOutcome::dodge(cursor, next, &mut viewport) == Dodge::Scrolled);
+            assert!(viewport.origin == LogicalPos::new(10, next.y - covered));
+            cursor = next;
+        }
+        let next = LogicalPos::new(cursor.x, cursor.y - 1);
+        assert!(LastRunOutcome::dodge(cursor, next, &mut viewport) == Dodge::Stopped);
+        assert!(viewport.origin == LogicalPos::new(10, 0));
+    }
+
+    #[test]
+    fn moves_never_end_under_the_line_while_it_is_shown() {
+        let mut rng = Rng::new(0x0D6E);
+        for _ in 0..200 {
+            let cell_size = ZOOM_LEVELS[rng.below(ZOOM_LEVELS.len() as u32) as usize];
+            let mut viewport = zoomed(cell_size, (0, 0));
+            let mut cursor = Cursor::CENTER;
+            viewport.follow(cursor.position());
+            let mut shown = false;
+            for _ in 0..300 {
+                // Runs ending and edits toggle the line while the cursor is anywhere
+                if rng.random_bool(1, 20) {
+                    shown = !shown;
+                }
+                let reach = if rng.random_bool(1, 10) { 30 } else { 1 };
+                let step = (
+                    rng.below(2 * reach + 1) as i32 - reach as i32,
+                    rng.below(2 * reach + 1) as i32 - reach as i32,
+                );
+                let previous = cursor;
+                cursor = cursor.moved(step, BoundaryMode::Dead);
+                let under = |cursor: Cursor, viewport: &Viewport| {
+                    let covered = LastRunOutcome::covered_rows(viewport);
+                    cursor.position().y < viewport.origin.y + covered
+                };
+                let was_under = under(previous, &viewport);
+                if shown && cursor != previous {
+                    let dodge = LastRunOutcome::dodge(
+                        previous.position(),
+                        cursor.position(),
+                        &mut viewport,
+                    );
+                    if dodge == Dodge::Stopped {
+                        cursor = previous;
+                    }
+                }
+                viewport.follow(cursor.position());
+                if shown && !was_under {
+                    assert!(!under(cursor, &viewport));
+                }
+                let (_, rows) = viewport.size();
+                assert!(viewport.origin.y + rows <= COLUMN_SIZE);
+                assert!(viewport.view_pos(cursor.position()).is_some());
+            }
+        }
+    }
+}
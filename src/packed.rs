@@ -0,0 +1,110 @@
+use crate::{Board, BOARD_SIZE, COLUMN_SIZE, LINE_SIZE};
+
+const WORDS: usize = BOARD_SIZE.div_ceil(32);
+
+/// A board stored as one bit per cell, in row-major order
+#[derive(Clone, Copy, PartialEq)]
+pub struct PackedBoard {
+    words: [u32; WORDS],
+}
+
+impl PackedBoard {
+    pub const EMPTY: Self = Self { words: [0; WORDS] };
+    /// Size of the serialized board
+    pub const SIZE: usize = WORDS * 4;
+
+    pub fn pack(board: &Board<bool>) -> Self {
+        let mut packed = Self::EMPTY;
+        for (x, column) in board.iter().enumerate() {
+            for (y, &alive) in column.iter().enumerate() {
+                if alive {
+                    let i = y * LINE_SIZE as usize + x;
+                    packed.words[i / 32] |= 1 << (i % 32);
+                }
+            }
+        }
+        packed
+    }
+
+    pub fn unpack(&self, board: &mut Board<bool>) {
+        for (x, column) in board.iter_mut().enumerate() {
+            for (y, cell) in column.iter_mut().enumerate() {
+                *cell = self.get(x as u16, y as u16);
+            }
+        }
+    }
+
+    pub fn get(&self, x: u16, y: u16) -> bool {
+        let i = y as usize * LINE_SIZE as usize + x as usize;
+        self.words[i / 32] >> (i % 32) & 1 != 0
+    }
+
+    /// FNV-1a hash of the cells, continuing from `hash`
+    pub fn checksum(&self, hash: u32) -> u32 {
+        self.words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .fold(hash, fnv1a)
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(self.words.iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Self {
+        let mut packed = Self::EMPTY;
+        for (word, chunk) in packed.words.iter_mut().zip(bytes.chunks_exact(4)) {
+            *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        packed
+    }
+
+    /// Indices of the cells that differ from `other`, row by row
+    pub fn differences<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = u16> + 'a {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .enumerate()
+            .map(|(w, (&a, &b))| (w, a ^ b))
+            .filter(|&(_, word)| word != 0)
+            .flat_map(|(w, word)| {
+                (0..32)
+                    .filter(move |bit| word >> bit & 1 != 0)
+                    .map(move |bit| (w * 32 + bit) as u16)
+            })
+    }
+
+    /// Positions of the alive cells, row by row
+    ///
+    /// Empty words are skipped at once, so sparse boards are iterated quickly.
+    pub fn live_cells(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .filter(|(_, &word)| word != 0)
+            .flat_map(|(w, &word)| {
+                (0..32)
+                    .filter(move |bit| word >> bit & 1 != 0)
+                    .map(move |bit| w * 32 + bit)
+            })
+            .map(|i| {
+                (
+                    (i % LINE_SIZE as usize) as u16,
+                    (i / LINE_SIZE as usize) as u16,
+                )
+            })
+            .filter(|&(_, y)| y < COLUMN_SIZE)
+    }
+}
+
+/// FNV-1a offset basis, to start a hash with
+pub const FNV_OFFSET: u32 = 0x811C_9DC5;
+
+/// One step of FNV-1a
+pub fn fnv1a(hash: u32, byte: u8) -> u32 {
+    (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+}
@@ -0,0 +1,176 @@
+use crate::eadk::Color;
+use crate::rim;
+use crate::viewport::LogicalPos;
+
+/// Colors used to draw the board
+#[derive(Clone, Copy)]
+pub struct ColorPalette {
+    pub live_cell: Color,
+    pub dead_cell: Color,
+    /// Dead cells on the rim of the board, a shade away from the others
+    pub rim_cell: Color,
+    /// Cursor and selection outline
+    pub cursor: Color,
+    /// Background of the tooltips over the board
+    pub status_bg: Color,
+    /// Lines between the cells, when they're shown
+    pub grid: Color,
+}
+
+impl ColorPalette {
+    /// The same colors, with the rim drawn as any dead cell
+    const fn without_rim(self) -> Self {
+        Self {
+            rim_cell: self.dead_cell,
+            ..self
+        }
+    }
+
+    /// Color of a cell of the board
+    pub fn cell(&self, alive: bool, cell: LogicalPos) -> Color {
+        if alive {
+            self.live_cell
+        } else if rim::contains(cell) {
+            self.rim_cell
+        } else {
+            self.dead_cell
+        }
+    }
+}
+
+/// Palettes cycled through in the editor
+pub const PALETTES: [ColorPalette; 4] = [
+    // Classic, black on white
+    ColorPalette {
+        live_cell: Color::BLACK,
+        dead_cell: Color::WHITE,
+        rim_cell: Color::from_rgb888(236, 236, 236),
+        cursor: Color::RED,
+        status_bg: Color::from_rgb888(220, 220, 220),
+        grid: Color::from_rgb888(200, 200, 200),
+    },
+    // Dark, white on black
+    ColorPalette {
+        live_cell: Color::WHITE,
+        dead_cell: Color::BLACK,
+        rim_cell: Color::from_rgb888(28, 28, 28),
+        cursor: Color::RED,
+        status_bg: Color::from_rgb888(60, 60, 60),
+        grid: Color::from_rgb888(56, 56, 56),
+    },
+    // Phosphor, green on dark green
+    ColorPalette {
+        live_cell: Color::GREEN,
+        dead_cell: Color::from_rgb888(0, 40, 0),
+        rim_cell: Color::from_rgb888(0, 60, 0),
+        cursor: Color::from_rgb888(255, 200, 0),
+        status_bg: Color::from_rgb888(0, 90, 0),
+        grid: Color::from_rgb888(0, 80, 0),
+    },
+    // Amber terminal, in ANSI colors
+    ColorPalette {
+        live_cell: Color::from_ansi(214),
+        dead_cell: Color::from_ansi(234),
+        rim_cell: Color::from_ansi(235),
+        cursor: Color::from_ansi(45),
+        status_bg: Color::from_ansi(237),
+        grid: Color::from_ansi(237),
+    },
+];
+
+/// [`PALETTES`] without the rim tint
+pub const UNTINTED_PALETTES: [ColorPalette; 4] = [
+    PALETTES[0].without_rim(),
+    PALETTES[1].without_rim(),
+    PALETTES[2].without_rim(),
+    PALETTES[3].without_rim(),
+];
+
+// Semantic colors are only reachable through a theme, so switching it changes them all
+const GREEN: Color = Color::from_rgb888(0, 200, 0);
+const RED: Color = Color::from_rgb888(230, 0, 0);
+const AMBER: Color = Color::from_rgb888(255, 190, 0);
+// From the Okabe-Ito palette, told apart with any color vision
+const BLUE: Color = Color::from_rgb888(0, 114, 178);
+const ORANGE: Color = Color::from_rgb888(230, 159, 0);
+const VERMILLION: Color = Color::from_rgb888(213, 94, 0);
+const SKY_BLUE: Color = Color::from_rgb888(86, 180, 233);
+const REDDISH_PURPLE: Color = Color::from_rgb888(204, 121, 167);
+
+/// Colors carrying a meaning, outside of the board
+pub struct Theme {
+    /// Nul terminated, to be drawn as is
+    pub name: &'static str,
+    pub born: Color,
+    pub died: Color,
+    pub success: Color,
+    pub warning: Color,
+    /// Cells only alive in the first of two compared snapshots
+    pub only_a: Color,
+    /// Cells only alive in the second of two compared snapshots
+    pub only_b: Color,
+    /// Strips along the sides of the screen telling the state, when they're shown
+    pub running: Color,
+    pub stepping: Color,
+    pub paused: Color,
+    /// Tint of the cells on the frontier between alive and dead regions
+    pub frontier: Color,
+    /// What has the focus on a screen, or marks a cell of it
+    pub highlight: Color,
+}
+
+/// Themes cycled through in the editor
+pub const THEMES: [Theme; 2] = [
+    Theme {
+        name: "Theme: standard\0",
+        born: GREEN,
+        died: RED,
+        success: GREEN,
+        warning: RED,
+        only_a: RED,
+        only_b: GREEN,
+        running: Color::from_rgb888(140, 220, 140),
+        stepping: Color::from_rgb888(140, 180, 240),
+        paused: AMBER,
+        frontier: GREEN,
+        highlight: Color::from_rgb888(0, 0, 255),
+    },
+    Theme {
+        name: "Theme: color-blind\0",
+        born: BLUE,
+        died: ORANGE,
+        success: BLUE,
+        warning: VERMILLION,
+        only_a: ORANGE,
+        only_b: BLUE,
+        running: SKY_BLUE,
+        stepping: REDDISH_PURPLE,
+        paused: ORANGE,
+        frontier: SKY_BLUE,
+        highlight: BLUE,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::OsStr, fs, path::Path};
+
+    #[test]
+    fn raw_semantic_colors_stay_in_the_palettes() {
+        let sources = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        for entry in fs::read_dir(sources).unwrap() {
+            let path = entry.unwrap().path();
+            // Where they're defined, and where the palettes and themes pick them
+            if path.extension() != Some(OsStr::new("rs"))
+                || path.ends_with("eadk.rs")
+                || path.ends_with("palette.rs")
+            {
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            for raw in ["Color::RED", "Color::GREEN", "Color::BLUE"] {
+                assert!(!source.contains(raw), "{} in {}", raw, path.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use crate::rule::Rule;
+use core::fmt;
+
+/// A named pattern, packed while compiling from the RLE format
+///
+/// Cells are one bit each in row-major order, like in a [`BitGrid`](crate::bitgrid::BitGrid),
+/// so the library patterns are never parsed on the calculator.
+pub struct Pattern {
+    /// Nul terminated, to be drawn as is
+    pub name: &'static str,
+    pub width: u16,
+    pub height: u16,
+    cells: &'static [u32],
+    /// The RLE it was packed from, to check the packing
+    #[cfg(test)]
+    rle: &'static str,
+}
+
+impl Pattern {
+    /// Whether a cell is alive, cells outside of the pattern are dead
+    pub fn get(&self, x: u16, y: u16) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        let i = y as usize * self.width as usize + x as usize;
+        self.cells[i / 32] >> (i % 32) & 1 != 0
+    }
+}
+
+/// A library pattern, from its name and its cells in RLE
+///
+/// The RLE is decoded by [`pack`] in constants, so a malformed one fails the build.
+macro_rules! pattern {
+    ($name:expr, $rle:expr) => {{
+        const SIZE: (u16, u16) = pack::<0>($rle, 0).0;
+        const CELLS: [u32; (SIZE.0 as usize * SIZE.1 as usize).div_ceil(32)] = pack($rle, SIZE.0).1;
+        Pattern {
+            name: $name,
+            width: SIZE.0,
+            height: SIZE.1,
+            cells: &CELLS,
+            #[cfg(test)]
+            rle: $rle,
+        }
+    }};
+}
+
+/// Patterns of the library
+pub const PATTERNS: [Pattern; 11] = [
+    pattern!("Glider\0", "bob$2bo$3o!"),
+    pattern!("Blinker\0", "3o!"),
+    pattern!("Toad\0", "b3o$3o!"),
+    pattern!("Beacon\0", "2o$2o$2b2o$2b2o!"),
+    pattern!("Pulsar\0", "2b3o3b3o2b2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2b2$2b3o3b3o2b$o4bobo4bo$o4bobo4bo$o4bobo4bo2$2b3o3b3o!"),
+    pattern!("Pentadecathlon\0", "2bo4bo2b$2ob4ob2o$2bo4bo2b!"),
+    pattern!("Lightweight ship\0", "bo2bo$o4b$o3bo$4o!"),
+    pattern!("R-pentomino\0", "b2o$2o$bo!"),
+    pattern!("Acorn\0", "bo5b$3bo3b$2o2b3o!"),
+    pattern!("Diehard\0", "6bob$2o6b$bo3b3o!"),
+    pattern!("Glider gun\0", "24bo11b$22bobo11b$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o14b$2o8bo3bob2o4bobo11b$10bo5bo7bo11b$11bo3bo20b$12b2o22b!"),
+];
+
+/// Oscillators of period 2 and 3, from the library
+pub const BLINKER: &Pattern = &PATTERNS[1];
+pub const TOAD: &Pattern = &PATTERNS[2];
+pub const BEACON: &Pattern = &PATTERNS[3];
+pub const PULSAR: &Pattern = &PATTERNS[4];
+
+/// Gosper's glider gun, from the library
+pub const GLIDER_GUN: &Pattern = &PATTERNS[10];
+
+/// Lightweight spaceship, as the positions of its cells, travelling left at c/2
+///
+/// ```text
+/// .O..O
+/// O....
+/// O...O
+/// OOOO.
+/// ```
+pub const LWSS: &[(i16, i16)] = &[
+    (1, 0),
+    (4, 0),
+    (0, 1),
+    (0, 2),
+    (4, 2),
+    (0, 3),
+    (1, 3),
+    (2, 3),
+    (3, 3),
+];
+
+/// Error while decoding a RLE pattern, at a position in the text (starting from 1)
+#[derive(Clone, Copy)]
+pub struct RleError {
+    pub line: u16,
+    pub column: u16,
+}
+
+/// Header line of a RLE pattern, like `x = 3, y = 3, rule = B3/S23`
+pub struct Header {
+    pub width: u16,
+    pub height: u16,
+    pub rule: Option<Rule>,
+}
+
+/// Parse the header of a RLE pattern, `None` if it has none or it's malformed
+pub fn header(rle: &str) -> Option<Header> {
+    let line = rle
+        .lines()
+        .map(str::trim_start)
+        .find(|line| !line.starts_with('#'))?;
+    if !line.starts_with('x') {
+        return None;
+    }
+
+    let mut header = Header {
+        width: 0,
+        height: 0,
+        rule: None,
+    };
+    for field in line.split(',') {
+        let (key, value) = field.split_once('=')?;
+        match key.trim() {
+            "x" => header.width = value.trim().parse().ok()?,
+            "y" => header.height = value.trim().parse().ok()?,
+            "rule" => header.rule = Rule::parse(value),
+            _ => {}
+        }
+    }
+    Some(header)
+}
+
+/// Decode a RLE pattern
+///
+/// `set_alive` is called with the position of every alive cell, relative to the top left corner.
+/// Comment (`#`) and header (`x = ...`) lines are skipped.
+/// Returns the width and height of the pattern.
+///
+/// # Example
+///
+/// ```
+/// use patterns::decode;
+///
+/// let (width, height) = decode("bob$2bo$3o!", |x, y| todo!()).ok().unwrap(); // (3, 3)
+/// ```
+pub fn decode(rle: &str, mut set_alive: impl FnMut(u16, u16)) -> Result<(u16, u16), RleError> {
+    let (mut x, mut y) = (0u16, 0u16);
+    let (mut width, mut height) = (0u16, 0u16);
+    let mut run: Option<u16> = None;
+
+    for (line_index, line) in rle.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') || trimmed.starts_with('x') {
+            continue;
+        }
+
+        for (column_index, c) in line.chars().enumerate() {
+            let error = RleError {
+                line: line_index as u16 + 1,
+                column: column_index as u16 + 1,
+            };
+            let count = run.unwrap_or(1);
+
+            match c {
+                '0'..='9' => {
+                    run = Some(
+                        run.unwrap_or(0)
+                            .checked_mul(10)
+                            .and_then(|r| r.checked_add(c as u16 - '0' as u16))
+                            .ok_or(error)?,
+                    );
+                    continue;
+                }
+                'b' | '.' => x = x.checked_add(count).ok_or(error)?,
+                'o' | 'A' => {
+                    for _ in 0..count {
+                        set_alive(x, y);
+                        x = x.checked_add(1).ok_or(error)?;
+                    }
+                    width = width.max(x);
+                    height = height.max(y + 1);
+                }
+                '$' => {
+                    y = y.checked_add(count).ok_or(error)?;
+                    x = 0;
+                }
+                '!' => return Ok((width, height)),
+                ' ' | '\t' | '\r' => {}
+                _ => return Err(error),
+            }
+            run = None;
+        }
+    }
+
+    Ok((width, height))
+}
+
+/// Decode a RLE pattern while compiling, into its size and its cells packed in `WORDS` words
+///
+/// The same as [`decode`] for the library patterns, which have neither comment nor header
+/// lines. Rows are `width` cells apart, so it's run a first time with no words to size the
+/// pattern. Anything it can't decode stops the build.
+const fn pack<const WORDS: usize>(rle: &str, width: u16) -> ((u16, u16), [u32; WORDS]) {
+    let rle = rle.as_bytes();
+    let mut cells = [0; WORDS];
+    let (mut x, mut y) = (0u16, 0u16);
+    let mut size = (0u16, 0u16);
+    let mut run = 0u16;
+
+    let mut i = 0;
+    while i < rle.len() {
+        let count = if run == 0 { 1 } else { run };
+        match rle[i] {
+            c @ b'0'..=b'9' => {
+                run = run * 10 + (c - b'0') as u16;
+                i += 1;
+                continue;
+            }
+            b'b' | b'.' => x += count,
+            b'o' | b'A' => {
+                let mut n = 0;
+                while n < count {
+                    if WORDS > 0 {
+                        let cell = y as usize * width as usize + x as usize;
+                        cells[cell / 32] |= 1 << (cell % 32);
+                    }
+                    x += 1;
+                    n += 1;
+                }
+                if x > size.0 {
+                    size.0 = x;
+                }
+                size.1 = y + 1;
+            }
+            b'$' => {
+                y += count;
+                x = 0;
+            }
+            b'!' => return (size, cells),
+            b' ' | b'\t' | b'\r' | b'\n' => {}
+            _ => panic!("library pattern with a character that isn't RLE"),
+        }
+        run = 0;
+        i += 1;
+    }
+    (size, cells)
+}
+
+/// Encode cells as a RLE pattern, with its header
+///
+/// Cells are relative to any origin, the pattern starts at the top left one. A `comment`
+/// that isn't empty goes in a `#C` line before the header.
+pub fn encode(
+    cells: &[(i16, i16)],
+    rule: Rule,
+    comment: &str,
+    out: &mut impl fmt::Write,
+) -> fmt::Result {
+    if !comment.is_empty() {
+        writeln!(out, "#C {}", comment)?;
+    }
+    let bounds = |coordinate: fn(&(i16, i16)) -> i16| {
+        let values = cells.iter().map(coordinate).map(i32::from);
+        (
+            values.clone().min().unwrap_or(0),
+            values.max().unwrap_or(-1),
+        )
+    };
+    let ((min_x, max_x), (min_y, max_y)) = (bounds(|cell| cell.0), bounds(|cell| cell.1));
+    writeln!(
+        out,
+        "x = {}, y = {}, rule = {}",
+        max_x - min_x + 1,
+        max_y - min_y + 1,
+        rule
+    )?;
+
+    // Empty lines are written along with the next line with alive cells
+    let mut lines_ended = 0;
+    for y in min_y..=max_y {
+        let alive = |x: i32| cells.contains(&(x as i16, y as i16));
+        if !(min_x..=max_x).any(alive) {
+            lines_ended += 1;
+            continue;
+        }
+        if y > min_y {
+            write_run(out, lines_ended + 1, '$')?;
+        }
+        lines_ended = 0;
+
+        let mut x = min_x;
+        while x <= max_x {
+            let state = alive(x);
+            let start = x;
+            while x <= max_x && alive(x) == state {
+                x += 1;
+            }
+            // Dead cells at the end of a line are left out
+            if state || x <= max_x {
+                write_run(out, x - start, if state { 'o' } else { 'b' })?;
+            }
+        }
+    }
+    out.write_char('!')
+}
+
+/// Write a run of a RLE pattern, its count being left out when it's 1
+fn write_run(out: &mut impl fmt::Write, count: i32, tag: char) -> fmt::Result {
+    if count > 1 {
+        write!(out, "{}", count)?;
+    }
+    out.write_char(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_patterns_are_the_ones_named() {
+        for (pattern, name) in [
+            (BLINKER, "Blinker\0"),
+            (TOAD, "Toad\0"),
+            (BEACON, "Beacon\0"),
+            (PULSAR, "Pulsar\0"),
+            (GLIDER_GUN, "Glider gun\0"),
+        ] {
+            assert_eq!(pattern.name, name);
+        }
+    }
+
+    #[test]
+    fn packed_patterns_are_the_decoded_ones() {
+        for pattern in &PATTERNS {
+            let mut alive = std::vec::Vec::new();
+            let size = decode(pattern.rle, |x, y| alive.push((x, y))).ok().unwrap();
+            assert_eq!((pattern.width, pattern.height), size, "{}", pattern.name);
+            for y in 0..pattern.height + 1 {
+                for x in 0..pattern.width + 1 {
+                    let expected = alive.contains(&(x, y));
+                    assert_eq!(
+                        pattern.get(x, y),
+                        expected,
+                        "{} ({}, {})",
+                        pattern.name,
+                        x,
+                        y
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::packed::{PackedBoard, FNV_OFFSET};
+use crate::rule::Automaton;
+use crate::{run_once_sync, Board};
+use heapless::Vec;
+
+/// Generations remembered to notice a board repeating, the longest period noticed
+const HISTORY: usize = 32;
+/// Generations remembered by [`compute_period`], the longest period it finds
+const LONG_HISTORY: usize = 256;
+
+/// Hash of a board, the same for equal boards
+pub fn checksum(board: &Board<bool>) -> u32 {
+    PackedBoard::pack(board).checksum(FNV_OFFSET)
+}
+
+/// Hashes of the last generations of a board, to notice when it repeats
+pub struct History {
+    /// Hashes by generation modulo the history size
+    hashes: [u32; HISTORY],
+    generation: u32,
+}
+
+impl History {
+    /// Start from `board`, at generation 0
+    pub fn new(board: &Board<bool>) -> Self {
+        let mut hashes = [0; HISTORY];
+        hashes[0] = checksum(board);
+        Self {
+            hashes,
+            generation: 0,
+        }
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Remember the next generation, returns its period if it repeats a remembered one
+    pub fn push(&mut self, board: &Board<bool>) -> Option<u32> {
+        self.generation += 1;
+        let hash = checksum(board);
+        let remembered = HISTORY.min(self.generation as usize) as u32;
+        let period = (1..=remembered)
+            .find(|&period| self.hashes[((self.generation - period) as usize) % HISTORY] == hash);
+        self.hashes[self.generation as usize % HISTORY] = hash;
+        period
+    }
+}
+
+/// Period a copy of the board ends up repeating with, if it does within `max_generations`
+///
+/// Each generation is hashed and compared to the hashes of the last ones, so longer periods
+/// are found than while running, but a hash collision could report a wrong period.
+pub fn compute_period(
+    board: &Board<bool>,
+    automaton: &impl Automaton,
+    max_generations: u32,
+) -> Option<u32> {
+    let mut copy = *board;
+    // Hashes by generation modulo their number, once there are that many
+    let mut hashes: Vec<u32, LONG_HISTORY> = Vec::new();
+    for generation in 0..=max_generations as usize {
+        let hash = checksum(&copy);
+        let period = hashes
+            .iter()
+            .enumerate()
+            .filter(|&(_, &earlier)| earlier == hash)
+            .map(|(i, _)| ((generation + LONG_HISTORY - i - 1) % LONG_HISTORY + 1) as u32)
+            .min();
+        if period.is_some() {
+            return period;
+        }
+        if hashes.is_full() {
+            hashes[generation % LONG_HISTORY] = hash;
+        } else {
+            hashes.push(hash).ok();
+        }
+        run_once_sync(&mut copy, automaton);
+    }
+    None
+}
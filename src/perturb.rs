@@ -0,0 +1,75 @@
+use crate::analysis::neighborhood_density;
+use crate::rng::Rng;
+use crate::{Board, BOARD_SIZE, COLUMN_SIZE};
+use heapless::Vec;
+
+/// Most cells flipped by a perturbation
+pub const MAX_FLIPS: usize = 32;
+/// Numbers of cells flipped, cycled through in the editor
+pub const FLIP_COUNTS: [u8; 6] = [1, 2, 4, 8, 16, 32];
+
+/// Where the flipped cells are picked
+#[derive(Clone, Copy)]
+pub enum PerturbMode {
+    /// Anywhere on the board
+    Anywhere,
+    /// At most 2 cells away from an alive cell
+    NearLife,
+}
+
+impl PerturbMode {
+    pub fn toggle(self) -> Self {
+        match self {
+            PerturbMode::Anywhere => PerturbMode::NearLife,
+            PerturbMode::NearLife => PerturbMode::Anywhere,
+        }
+    }
+
+    /// Nul terminated name, to be drawn as is
+    pub fn name(self) -> &'static str {
+        match self {
+            PerturbMode::Anywhere => "anywhere\0",
+            PerturbMode::NearLife => "near life\0",
+        }
+    }
+}
+
+/// Flip `count` distinct cells picked uniformly among the candidates of the mode
+///
+/// Returns the flipped cells, fewer than `count` if there aren't enough candidates.
+pub fn perturb(
+    board: &mut Board<bool>,
+    rng: &mut Rng,
+    count: u8,
+    mode: PerturbMode,
+) -> Vec<(u16, u16), MAX_FLIPS> {
+    // Candidates by index in column-major order, as the board is stored
+    let mut candidates = [true; BOARD_SIZE];
+    if let PerturbMode::NearLife = mode {
+        let density = neighborhood_density(board);
+        for (candidate, &cells) in candidates.iter_mut().zip(density.iter().flatten()) {
+            *candidate = cells > 0;
+        }
+    }
+    let mut left = candidates.iter().filter(|&&candidate| candidate).count() as u32;
+
+    let mut flipped = Vec::new();
+    while flipped.len() < (count as usize).min(MAX_FLIPS) && left > 0 {
+        // The n-th candidate still available, so the same cell is never flipped twice
+        let n = rng.below(left) as usize;
+        let i = candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, &candidate)| candidate)
+            .nth(n)
+            .map_or(0, |(i, _)| i);
+        candidates[i] = false;
+        left -= 1;
+
+        let (x, y) = (i / COLUMN_SIZE as usize, i % COLUMN_SIZE as usize);
+        board[x][y] = !board[x][y];
+        // At most MAX_FLIPS are pushed
+        let _ = flipped.push((x as u16, y as u16));
+    }
+    flipped
+}
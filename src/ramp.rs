@@ -0,0 +1,159 @@
+use crate::eadk::State;
+
+/// Time a key is held before it repeats, in milliseconds
+const REPEAT_DELAY_MS: u64 = 400;
+/// Time between two repeats of a held key, in milliseconds
+const REPEAT_EVERY_MS: u64 = 80;
+/// Repeats at a step size before going to the next one
+const REPEATS_PER_STEP: u64 = 8;
+/// Step sizes, growing the longer a key is held
+const STEPS: [i32; 3] = [1, 5, 25];
+
+/// Adjustment of a value with two opposite keys, in steps growing while one is held
+pub struct Ramp {
+    /// When the key was pressed, and how many times it repeated since
+    held: Option<(u64, u64)>,
+}
+
+impl Ramp {
+    pub const fn new() -> Self {
+        Self { held: None }
+    }
+
+    /// How much to adjust the value by on the frame at `now`, 0 if it isn't time to
+    ///
+    /// A press is a step of 1 at once. Held, the key repeats after a delay, with steps
+    /// of 1, then 5, then 25.
+    pub fn step(
+        &mut self,
+        now: u64,
+        keyboard_state: &State,
+        pressed: &State,
+        (less, more): (u32, u32),
+    ) -> i32 {
+        let direction = if keyboard_state.key_down(more) {
+            1
+        } else if keyboard_state.key_down(less) {
+            -1
+        } else {
+            self.held = None;
+            return 0;
+        };
+        if pressed.key_down(more) || pressed.key_down(less) {
+            self.held = Some((now, 0));
+            return direction;
+        }
+
+        let (since, repeats) = match &mut self.held {
+            Some(held) => held,
+            None => return 0,
+        };
+        let held_ms = now.saturating_sub(*since);
+        if held_ms < REPEAT_DELAY_MS {
+            return 0;
+        }
+        // At most one step a frame, slow frames don't make the value jump ahead
+        let due = 1 + (held_ms - REPEAT_DELAY_MS) / REPEAT_EVERY_MS;
+        if due <= *repeats {
+            return 0;
+        }
+        *repeats = due;
+        let size = STEPS[((due - 1) / REPEATS_PER_STEP).min(STEPS.len() as u64 - 1) as usize];
+        direction * size
+    }
+}
+
+/// Adjust a value by a step, clamped to `min..=max`
+///
+/// Nothing adjusted with a ramp is cyclic, so values stop at the bounds instead of wrapping.
+pub fn adjust(value: u32, step: i32, (min, max): (u32, u32)) -> u32 {
+    (value as i64 + step as i64).clamp(min as i64, max as i64) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eadk::key;
+    use std::vec::Vec;
+
+    const KEYS: (u32, u32) = (key::LEFT, key::RIGHT);
+
+    /// Steps given when `held` is pressed at 0 and held, on frames at each of `frames`
+    fn hold(held: u32, frames: &[u64]) -> Vec<i32> {
+        let mut ramp = Ramp::new();
+        let down = State::NONE.with(held);
+        let mut steps = Vec::new();
+        steps.push(ramp.step(0, &down, &down, KEYS));
+        for &now in frames {
+            steps.push(ramp.step(now, &down, &State::NONE, KEYS));
+        }
+        steps
+    }
+
+    #[test]
+    fn a_press_is_one_step_at_once() {
+        assert_eq!(hold(key::RIGHT, &[]), [1]);
+        assert_eq!(hold(key::LEFT, &[]), [-1]);
+    }
+
+    #[test]
+    fn held_keys_repeat_after_a_delay() {
+        let frames: Vec<u64> = (1..REPEAT_DELAY_MS).step_by(20).collect();
+        assert!(hold(key::RIGHT, &frames)[1..].iter().all(|&step| step == 0));
+        assert_eq!(hold(key::RIGHT, &[REPEAT_DELAY_MS]), [1, 1]);
+        // Not again until the next repeat is due
+        let again = REPEAT_DELAY_MS + REPEAT_EVERY_MS;
+        assert_eq!(
+            hold(key::RIGHT, &[REPEAT_DELAY_MS, again - 1, again]),
+            [1, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn steps_grow_while_held() {
+        let repeats = REPEATS_PER_STEP * STEPS.len() as u64 + 4;
+        let frames: Vec<u64> = (0..repeats)
+            .map(|repeat| REPEAT_DELAY_MS + repeat * REPEAT_EVERY_MS)
+            .collect();
+        let steps = hold(key::LEFT, &frames);
+        let mut expected = std::vec![-1];
+        for repeat in 0..repeats {
+            let size = STEPS[((repeat / REPEATS_PER_STEP) as usize).min(STEPS.len() - 1)];
+            expected.push(-size);
+        }
+        assert_eq!(steps, expected);
+    }
+
+    #[test]
+    fn slow_frames_give_one_step() {
+        // Long enough for a dozen repeats, but only one frame
+        let late = REPEAT_DELAY_MS + 12 * REPEAT_EVERY_MS;
+        assert_eq!(hold(key::RIGHT, &[late, late]), [1, STEPS[1], 0]);
+    }
+
+    #[test]
+    fn releasing_starts_over() {
+        let mut ramp = Ramp::new();
+        let down = State::NONE.with(key::RIGHT);
+        assert_eq!(ramp.step(0, &down, &down, KEYS), 1);
+        assert_eq!(ramp.step(10_000, &State::NONE, &State::NONE, KEYS), 0);
+        // Down again without a press seen, it waits for one
+        assert_eq!(ramp.step(10_001, &down, &State::NONE, KEYS), 0);
+        assert_eq!(ramp.step(20_000, &down, &State::NONE, KEYS), 0);
+        assert_eq!(ramp.step(20_001, &down, &down, KEYS), 1);
+        assert_eq!(ramp.step(20_002, &down, &State::NONE, KEYS), 0);
+    }
+
+    #[test]
+    fn values_stop_at_the_bounds() {
+        let bounds = (1, 100_000);
+        assert_eq!(adjust(50, 25, bounds), 75);
+        assert_eq!(adjust(99_990, 25, bounds), 100_000);
+        assert_eq!(adjust(100_000, 1, bounds), 100_000);
+        assert_eq!(adjust(3, -5, bounds), 1);
+        assert_eq!(adjust(1, -25, bounds), 1);
+        assert_eq!(adjust(0, 0, (0, u32::MAX)), 0);
+        assert_eq!(adjust(u32::MAX, 25, (0, u32::MAX)), u32::MAX);
+        assert_eq!(adjust(0, -1, (0, u32::MAX)), 0);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::eadk::timing;
+use core::fmt;
+
+/// Keeps generations a fixed period apart
+///
+/// Deadlines follow each other by exactly the period, so the time spent computing and
+/// drawing a generation, and the sub-millisecond parts of the period, are accounted for.
+pub struct FramePacer {
+    deadline_us: u64,
+}
+
+impl FramePacer {
+    pub const fn new() -> Self {
+        Self { deadline_us: 0 }
+    }
+
+    /// Wait until `period_us` after the previous deadline
+    ///
+    /// The time left is slept with [`timing::msleep`] then [`timing::usleep`] for the
+    /// sub-millisecond remainder, and spinning on [`timing::millis`] is only a fallback
+    /// for sleeps returning early. A generation overrunning its period isn't waited for.
+    pub fn wait(&mut self, period_us: u32) {
+        let now_us = timing::millis() * 1000;
+        // After an overrun, start again from now instead of rushing to catch up
+        self.deadline_us = (self.deadline_us + period_us as u64).max(now_us);
+        let remaining_us = (self.deadline_us - now_us) as u32;
+
+        timing::msleep(remaining_us / 1000);
+        timing::usleep(remaining_us % 1000);
+        while timing::millis() < self.deadline_us / 1000 {}
+    }
+}
+
+/// Time between two refreshes of the screen, in microseconds
+const FRAME_US: u64 = 16_667;
+
+/// Draws generations at most once per refresh of the screen
+///
+/// Generations computed faster than the screen refreshes would never be seen, so only
+/// the last one before each refresh is drawn.
+pub struct FrameSkip {
+    last_frame_us: u64,
+}
+
+impl FrameSkip {
+    pub const fn new() -> Self {
+        Self { last_frame_us: 0 }
+    }
+
+    /// Whether the screen refreshed since the last drawn generation, so this one is drawn
+    pub fn frame_due(&mut self) -> bool {
+        let now_us = timing::millis() * 1000;
+        if now_us < self.last_frame_us + FRAME_US {
+            return false;
+        }
+        self.last_frame_us = now_us;
+        true
+    }
+}
+
+/// Generations per second, measured over one second windows
+pub struct GenRate {
+    window_start: u64,
+    generations: u32,
+    /// Last measured rate, in tenths of generation per second
+    pub last: u32,
+}
+
+impl GenRate {
+    pub const fn new() -> Self {
+        Self {
+            window_start: 0,
+            generations: 0,
+            last: 0,
+        }
+    }
+
+    /// Start a new window, when the simulation starts running
+    pub fn restart(&mut self) {
+        self.window_start = timing::millis();
+        self.generations = 0;
+    }
+
+    /// Count a generation, returns the rate once a window is over, in tenths of generation/s
+    pub fn tick(&mut self) -> Option<u32> {
+        let now = timing::millis();
+        self.generations += 1;
+        let elapsed = now - self.window_start;
+        if elapsed < 1000 {
+            return None;
+        }
+
+        // Generations per 10 seconds, for a decimal without floats
+        let rate = (self.generations as u64 * 10_000 / elapsed) as u32;
+        self.window_start = now;
+        self.generations = 0;
+        self.last = rate;
+        Some(rate)
+    }
+}
+
+/// Tenths of a unit, written with one decimal
+pub struct Tenths(pub u32);
+
+impl fmt::Display for Tenths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.0 / 10, self.0 % 10)
+    }
+}
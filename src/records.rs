@@ -0,0 +1,235 @@
+use crate::analysis::population;
+use crate::config::AppConfig;
+use crate::eadk::{display, Color, Point, Rect, SCREEN_HEIGHT};
+use crate::period::{checksum, History};
+use crate::rule::{BoundaryMode, Rule};
+use crate::{Board, Changes, UpdateMode};
+use core::fmt::Write;
+use heapless::String;
+
+const PRESETS: usize = Rule::PRESETS.len();
+
+/// Best value reached by a soup, and the seed of that soup
+#[derive(Clone, Copy)]
+pub struct Record {
+    pub value: u32,
+    pub seed: u32,
+}
+
+/// Records of random soups for each rule preset, kept for the session
+pub struct Records {
+    /// Most generations before becoming static or periodic
+    settling: [Option<Record>; PRESETS],
+    /// Most cells alive at once
+    peak: [Option<Record>; PRESETS],
+}
+
+/// Settings a soup has to run with from start to end to count
+#[derive(Clone, Copy, PartialEq)]
+pub struct SoupSettings {
+    rule: Rule,
+    boundary: BoundaryMode,
+    edge_buffer: bool,
+    update_mode: UpdateMode,
+    noise: u8,
+}
+
+impl SoupSettings {
+    pub fn of(config: &AppConfig) -> Self {
+        Self {
+            rule: config.rule,
+            boundary: config.boundary,
+            edge_buffer: config.edge_buffer,
+            update_mode: config.update_mode,
+            noise: config.noise,
+        }
+    }
+}
+
+/// Records beaten by a soup once it settled
+pub struct Beaten {
+    pub settling: Option<u32>,
+    pub peak: Option<u32>,
+}
+
+impl Records {
+    pub const fn new() -> Self {
+        Self {
+            settling: [None; PRESETS],
+            peak: [None; PRESETS],
+        }
+    }
+
+    /// Keep the values of a settled soup where they beat the records of its rule
+    fn submit(&mut self, preset: usize, seed: u32, (settling, peak): (u32, u32)) -> Beaten {
+        let beat = |record: &mut Option<Record>, value: u32| {
+            if record.is_some_and(|record| record.value >= value) {
+                return None;
+            }
+            *record = Some(Record { value, seed });
+            Some(value)
+        };
+        Beaten {
+            settling: beat(&mut self.settling[preset], settling),
+            peak: beat(&mut self.peak[preset], peak),
+        }
+    }
+
+    pub fn draw(&self) {
+        display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+        display::draw_string(
+            "Records of random soups\0",
+            Point::new(10, 10),
+            true,
+            Color::BLACK,
+            Color::WHITE,
+        );
+
+        let (_, glyph_height) = display::glyph_size(false);
+        for (preset, rule) in Rule::PRESETS.iter().enumerate() {
+            let mut name: String<16> = String::new();
+            write!(name, "{}", rule).unwrap();
+            let lines = [
+                (name.as_str(), "longest", self.settling[preset], "gen"),
+                ("", "crowded", self.peak[preset], "cells"),
+            ];
+            for (line_index, &(name, label, record, unit)) in lines.iter().enumerate() {
+                let mut line: String<64> = String::new();
+                write!(line, "{:<13}{} ", name, label).unwrap();
+                match record {
+                    Some(record) => {
+                        write!(line, "{} {}, seed {:08X}", record.value, unit, record.seed)
+                    }
+                    None => write!(line, "-"),
+                }
+                .unwrap();
+                line.push('\0').unwrap();
+                let row = (2 * preset + line_index) as u16;
+                display::draw_string(
+                    &line,
+                    Point::new(10, 40 + row * glyph_height + preset as u16 * 6),
+                    false,
+                    Color::BLACK,
+                    Color::WHITE,
+                );
+            }
+        }
+
+        display::draw_string(
+            "BACK: back to the board\0",
+            Point::new(10, SCREEN_HEIGHT - 20),
+            false,
+            Color::BLACK,
+            Color::WHITE,
+        );
+    }
+}
+
+/// A soup drawn from a seed being run, followed to compare it with the records
+///
+/// A soup edited by hand can't set records anymore: the board is compared when the
+/// simulation is resumed to when it was left, so no edit can slip in unnoticed. Neither can
+/// a soup run with noise, its generations don't follow from the seed and the rule alone.
+pub struct SoupRun {
+    seed: u32,
+    settings: SoupSettings,
+    population: u32,
+    peak: u32,
+    history: History,
+    /// Checksum of the board when the simulation was left
+    left_at: Option<u32>,
+    /// Settled or disqualified, so there is nothing left to follow
+    over: bool,
+}
+
+impl SoupRun {
+    /// Follow a soup just drawn on the board
+    pub fn new(seed: u32, settings: SoupSettings, board: &Board<bool>) -> Self {
+        let population = population(board);
+        Self {
+            seed,
+            settings,
+            population,
+            peak: population,
+            history: History::new(board),
+            left_at: Some(checksum(board)),
+            over: settings.noise != 0,
+        }
+    }
+
+    /// The simulation was left, the board may be edited from now on
+    pub fn pause(&mut self, board: &Board<bool>) {
+        self.left_at = Some(checksum(board));
+    }
+
+    /// Back to the simulation, the soup is disqualified if anything changed meanwhile
+    pub fn resume(&mut self, board: &Board<bool>, settings: SoupSettings) {
+        if self.left_at.take() != Some(checksum(board))
+            || settings != self.settings
+            || settings.noise != 0
+        {
+            self.over = true;
+        }
+    }
+
+    /// Follow a synchronous generation, returns the records beaten if the soup just settled
+    pub fn step(
+        &mut self,
+        board: &Board<bool>,
+        changes: &Changes,
+        records: &mut Records,
+    ) -> Option<Beaten> {
+        if self.over {
+            return None;
+        }
+        self.population = self.population + changes.born.len() as u32 - changes.died.len() as u32;
+        self.peak = self.peak.max(self.population);
+
+        let period = self.history.push(board);
+        let settled_at = self.history.generation() - period?;
+        self.over = true;
+        let preset = Rule::PRESETS
+            .iter()
+            .position(|&rule| rule == self.settings.rule)?;
+        Some(records.submit(preset, self.seed, (settled_at, self.peak)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{COLUMN_SIZE, LINE_SIZE};
+
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+
+    /// Follow an empty board until it settles or for a few generations
+    fn settle(run: &mut SoupRun, records: &mut Records) -> Option<Beaten> {
+        (0..4).find_map(|_| run.step(&EMPTY, &Changes::new(), records))
+    }
+
+    #[test]
+    fn soups_without_noise_set_records() {
+        let mut records = Records::new();
+        let mut run = SoupRun::new(7, SoupSettings::of(&AppConfig::default()), &EMPTY);
+        assert!(settle(&mut run, &mut records).is_some());
+        assert!(records.settling[0].is_some());
+    }
+
+    #[test]
+    fn soups_with_noise_set_no_record() {
+        let config = AppConfig {
+            noise: 1,
+            ..AppConfig::default()
+        };
+        let mut records = Records::new();
+        let mut run = SoupRun::new(7, SoupSettings::of(&config), &EMPTY);
+        assert!(settle(&mut run, &mut records).is_none());
+
+        // Nor when noise is turned on while the simulation was left
+        let mut run = SoupRun::new(7, SoupSettings::of(&AppConfig::default()), &EMPTY);
+        run.pause(&EMPTY);
+        run.resume(&EMPTY, SoupSettings::of(&config));
+        assert!(settle(&mut run, &mut records).is_none());
+        assert!(records.settling.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,266 @@
+use crate::eadk::timing::{self, PeriodicUpdate};
+use crate::eadk::{display, Color};
+use crate::palette::ColorPalette;
+use crate::viewport::{LogicalPos, Viewport};
+use crate::{draw_board, Board, Changes};
+
+/// Frames in a row with few enough changes before going back to per cell drawing
+const CALM_FRAMES: u8 = 8;
+
+/// Changed cells of a column of the same color, one above the other
+struct Run {
+    x: u16,
+    top: u16,
+    bottom: u16,
+    color: Color,
+}
+
+/// Changed cells merged into runs, in the order they're listed
+///
+/// Changes are listed column by column, so cells stacked in a column follow each other and
+/// only the last run has to be looked at to merge them.
+struct Runs<'a, I> {
+    cells: I,
+    board: &'a Board<bool>,
+    palette: &'a ColorPalette,
+    /// With a grid, each cell is drawn inside its lines and not merged
+    merge: bool,
+    pending: Option<Run>,
+}
+
+impl<'a, I: Iterator<Item = &'a (u8, u8)>> Iterator for Runs<'a, I> {
+    type Item = Run;
+
+    fn next(&mut self) -> Option<Run> {
+        for &(x, y) in self.cells.by_ref() {
+            let (x, y) = (x as u16, y as u16);
+            // Dead cells on the rim may be tinted, they don't join the other dead cells
+            let color = self
+                .palette
+                .cell(self.board[x as usize][y as usize], LogicalPos::new(x, y));
+            match &mut self.pending {
+                Some(run)
+                    if self.merge && run.x == x && run.bottom + 1 == y && run.color == color =>
+                {
+                    run.bottom = y
+                }
+                _ => {
+                    let next = Run {
+                        x,
+                        top: y,
+                        bottom: y,
+                        color,
+                    };
+                    if let Some(done) = self.pending.replace(next) {
+                        return Some(done);
+                    }
+                }
+            }
+        }
+        self.pending.take()
+    }
+}
+
+/// Time limit on drawing the changes of a generation
+///
+/// When a generation changes too many cells to draw them one by one in time, drawing is
+/// abandoned and the next frames repaint the whole board at once instead, so input keeps
+/// being scanned. Per cell drawing comes back once the changes have been small enough
+/// for a few frames in a row, so it doesn't switch back and forth every frame.
+pub struct RenderBudget {
+    budget_ms: u64,
+    /// Cells drawn before running out of time, the last time it happened
+    capacity: usize,
+    /// The screen doesn't match the board anymore
+    dirty: bool,
+    batched: bool,
+    calm_frames: u8,
+    /// Rects pushed to the screen by the last flush, a repaint pushing one per line of pixels
+    pub rects: u32,
+}
+
+impl RenderBudget {
+    pub const fn new(budget_ms: u64) -> Self {
+        Self {
+            budget_ms,
+            capacity: 0,
+            dirty: false,
+            batched: false,
+            calm_frames: 0,
+            rects: 0,
+        }
+    }
+
+    /// Draw the cells changed by a generation
+    pub fn flush(
+        &mut self,
+        board: &Board<bool>,
+        changes: &Changes,
+        palette: &ColorPalette,
+        viewport: &Viewport,
+    ) {
+        if self.batched || self.dirty {
+            draw_board(board, palette, viewport);
+            self.dirty = false;
+            self.rects = (viewport.size().1 * viewport.cell_size) as u32;
+
+            if changes.len() < self.capacity / 2 {
+                self.calm_frames += 1;
+                if self.calm_frames >= CALM_FRAMES {
+                    self.batched = false;
+                    self.calm_frames = 0;
+                }
+            } else {
+                self.calm_frames = 0;
+            }
+            return;
+        }
+
+        let start = timing::millis();
+        let runs = Runs {
+            cells: changes.born.iter().chain(changes.died.iter()),
+            board,
+            palette,
+            merge: !viewport.grid,
+            pending: None,
+        };
+        // Checking the time costs a call too, so only do it every few rects
+        let mut check_time = PeriodicUpdate::new(32);
+        let mut drawn = 0;
+        self.rects = 0;
+        for run in runs {
+            if check_time.tick() && timing::millis() - start > self.budget_ms {
+                self.capacity = drawn;
+                self.dirty = true;
+                self.batched = true;
+                return;
+            }
+            drawn += (run.bottom - run.top + 1) as usize;
+            self.draw_run(run, viewport);
+        }
+    }
+
+    fn draw_run(&mut self, run: Run, viewport: &Viewport) {
+        if let Some(rect) = viewport.column_rect(run.x, (run.top, run.bottom)) {
+            display::push_rect_uniform(viewport.inside_grid(rect), run.color);
+            self.rects += 1;
+        }
+    }
+
+    /// The screen doesn't match the board anymore, the next flush repaints all of it
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Repaint the board if drawing was abandoned, before leaving the simulation
+    pub fn catch_up(&mut self, board: &Board<bool>, palette: &ColorPalette, viewport: &Viewport) {
+        if self.dirty {
+            draw_board(board, palette, viewport);
+            self.dirty = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette::PALETTES;
+    use crate::{Changes, COLUMN_SIZE, LINE_SIZE};
+    use std::vec::Vec;
+
+    /// Runs of the changes, as their column, top, bottom and whether they're alive
+    fn runs(board: &Board<bool>, changes: &Changes, merge: bool) -> Vec<(u16, u16, u16, bool)> {
+        let palette = &PALETTES[0];
+        Runs {
+            cells: changes.born.iter().chain(changes.died.iter()),
+            board,
+            palette,
+            merge,
+            pending: None,
+        }
+        .map(|run| (run.x, run.top, run.bottom, run.color == palette.live_cell))
+        .collect()
+    }
+
+    /// The changes bringing `born` to life and killing `died`, listed column by column
+    fn changes(
+        board: &mut Board<bool>,
+        born: &[(usize, usize)],
+        died: &[(usize, usize)],
+    ) -> Changes {
+        let mut changes = Changes::new();
+        let mut cells: Vec<_> = born
+            .iter()
+            .map(|&cell| (cell, true))
+            .chain(died.iter().map(|&cell| (cell, false)))
+            .collect();
+        cells.sort();
+        for ((x, y), alive) in cells {
+            board[x][y] = alive;
+            changes.push((x, y), alive);
+        }
+        changes
+    }
+
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+
+    #[test]
+    fn stacked_cells_are_one_run() {
+        let mut board = EMPTY;
+        let column: Vec<_> = (5..10).map(|y| (10, y)).collect();
+        let changes = changes(&mut board, &column, &[]);
+        assert_eq!(runs(&board, &changes, true), [(10, 5, 9, true)]);
+        assert_eq!(runs(&board, &changes, false).len(), 5);
+    }
+
+    #[test]
+    fn runs_break_on_gaps_columns_and_colors() {
+        let mut board = EMPTY;
+        let changes = changes(
+            &mut board,
+            &[(10, 5), (10, 6), (10, 8), (11, 9), (12, 20), (12, 22)],
+            &[(12, 21), (13, 4), (13, 5)],
+        );
+        assert_eq!(
+            runs(&board, &changes, true),
+            [
+                (10, 5, 6, true),
+                (10, 8, 8, true),
+                (11, 9, 9, true),
+                (12, 20, 20, true),
+                (12, 22, 22, true),
+                (12, 21, 21, false),
+                (13, 4, 5, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn rim_cells_dont_join_the_others() {
+        let mut board = [[true; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        let died: Vec<_> = (0..4).map(|y| (30, y)).collect();
+        let changes = changes(&mut board, &[], &died);
+        assert_eq!(
+            runs(&board, &changes, true),
+            [(30, 0, 1, false), (30, 2, 3, false)]
+        );
+    }
+
+    #[test]
+    fn runs_are_cut_to_the_view() {
+        let mut zoomed = Viewport::FULL;
+        zoomed.zoom_toward(LogicalPos::new(40, 30), 8);
+        let (_, rows) = zoomed.size();
+        let bottom = zoomed.origin.y + rows - 1;
+
+        let rect = zoomed.column_rect(25, (0, COLUMN_SIZE - 1)).unwrap();
+        assert_eq!(
+            (rect.x, rect.y, rect.width, rect.height),
+            (40, 0, 8, rows * 8)
+        );
+        let rect = zoomed.column_rect(25, (bottom - 1, bottom + 5)).unwrap();
+        assert_eq!((rect.y, rect.height), ((rows - 2) * 8, 16));
+        assert!(zoomed.column_rect(25, (0, zoomed.origin.y - 1)).is_none());
+        assert!(zoomed.column_rect(10, (0, COLUMN_SIZE - 1)).is_none());
+    }
+}
@@ -0,0 +1,58 @@
+use crate::eadk::{display, Color, Point};
+use crate::packed::PackedBoard;
+use crate::palette::ColorPalette;
+use crate::viewport::Viewport;
+use crate::{draw_board, Board, COLUMN_SIZE, LINE_SIZE};
+use core::fmt::Write;
+use heapless::{String, Vec};
+
+/// Boards a recording keeps
+const FRAMES: usize = 32;
+/// Time each frame of a replay is shown, in milliseconds, from the slowest speed
+pub const FRAME_DELAYS_MS: [u64; 4] = [800, 400, 200, 100];
+
+/// Boards taken at even intervals during a jump, to play it back
+pub struct Recording {
+    frames: Vec<PackedBoard, FRAMES>,
+}
+
+impl Recording {
+    pub const fn new() -> Self {
+        Self { frames: Vec::new() }
+    }
+
+    /// Generations between two frames of a jump of `generations`, so they all fit
+    ///
+    /// The board before the jump and the one after it are recorded too.
+    pub fn frame_every(generations: u32) -> u32 {
+        generations.div_ceil(FRAMES as u32 - 2).max(1)
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Add a frame, dropped if the recording is full
+    pub fn push(&mut self, board: &Board<bool>) {
+        self.frames.push(PackedBoard::pack(board)).ok();
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Draw a frame over the whole screen, with its number in the corner
+    pub fn draw(&self, frame: usize, palette: &ColorPalette) {
+        let mut board: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        self.frames[frame].unpack(&mut board);
+        draw_board(&board, palette, &Viewport::FULL);
+
+        let mut text: String<16> = String::new();
+        write!(text, "{}/{}\0", frame + 1, self.len()).unwrap();
+        display::draw_string(&text, Point::new(2, 2), false, Color::WHITE, Color::BLACK);
+    }
+}
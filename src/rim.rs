@@ -0,0 +1,20 @@
+use crate::neighbors::neighbors;
+use crate::rule::{BoundaryMode, Neighborhood};
+use crate::viewport::LogicalPos;
+use crate::{COLUMN_SIZE, LINE_SIZE};
+
+/// Rows and columns along each edge making the rim of the board
+const RIM_WIDTH: u16 = 2;
+
+/// Whether a cell is on the rim, where dead edges make the neighborhood smaller
+pub fn contains(cell: LogicalPos) -> bool {
+    cell.x < RIM_WIDTH
+        || cell.y < RIM_WIDTH
+        || cell.x >= LINE_SIZE - RIM_WIDTH
+        || cell.y >= COLUMN_SIZE - RIM_WIDTH
+}
+
+/// Neighbors of a cell falling outside the board
+pub fn neighbors_outside(cell: LogicalPos, neighborhood: Neighborhood) -> u8 {
+    neighborhood.size() - neighbors(cell, neighborhood, BoundaryMode::Dead).count() as u8
+}
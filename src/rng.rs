@@ -0,0 +1,54 @@
+/// Seedable pseudo random number generator
+///
+/// A xorshift32 generator, so a run can be reproduced from its seed,
+/// unlike [`eadk::random`](crate::eadk::random).
+///
+/// # Example
+///
+/// ```
+/// use rng::Rng;
+///
+/// let mut rng = Rng::new(42);
+/// let r: u32 = rng.below(10); // [0; 10[
+/// ```
+#[derive(Clone, Copy)]
+pub struct Rng {
+    state: u32,
+}
+
+impl Rng {
+    /// Create a generator from a seed
+    ///
+    /// Xorshift can't leave the zero state, so a zero seed is replaced by a fixed constant.
+    #[must_use]
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { 0x9E37_79B9 } else { seed },
+        }
+    }
+
+    /// Current state, giving back the same generator with [`Rng::new`]
+    pub fn state(&self) -> u32 {
+        self.state
+    }
+
+    /// Get the next random u32
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Get a random number in [0; n[
+    pub fn below(&mut self, n: u32) -> u32 {
+        self.next_u32() % n
+    }
+
+    /// True with a chance of `numerator` in `denominator`
+    pub fn random_bool(&mut self, numerator: u32, denominator: u32) -> bool {
+        self.below(denominator) < numerator
+    }
+}
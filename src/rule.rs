@@ -0,0 +1,376 @@
+use crate::neighbors::neighbor_count;
+use crate::viewport::LogicalPos;
+use crate::Board;
+use core::fmt;
+
+/// Cells counted as the neighbors of a cell
+#[derive(Clone, Copy, PartialEq)]
+pub enum Neighborhood {
+    /// The 8 cells around it
+    Moore,
+    /// Only the 4 cells touching its corners, as in diagonal Life
+    Diagonal,
+}
+
+impl Neighborhood {
+    /// Offsets of the neighbors from the cell
+    pub fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Moore => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+            Neighborhood::Diagonal => &[(-1, -1), (1, -1), (-1, 1), (1, 1)],
+        }
+    }
+
+    /// Most alive neighbors a cell can have
+    pub fn size(self) -> u8 {
+        self.offsets().len() as u8
+    }
+}
+
+/// Life-like rule, in birth/survival notation
+///
+/// Bit n of `birth` is set when a dead cell with n alive neighbors comes alive,
+/// bit n of `survive` when an alive cell with n alive neighbors stays alive.
+///
+/// # Example
+///
+/// ```
+/// use rule::Rule;
+///
+/// let highlife = Rule::new(&[3, 6], &[2, 3]); // B36/S23
+/// let diagonal = Rule::diagonal(&[1], &[1, 2]); // B1/S12D
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub struct Rule {
+    pub birth: u16,
+    pub survive: u16,
+    pub neighborhood: Neighborhood,
+}
+
+impl Rule {
+    /// Conway's Game Of Life, B3/S23
+    pub const CONWAY: Self = Self::new(&[3], &[2, 3]);
+    /// B36/S23
+    pub const HIGHLIFE: Self = Self::new(&[3, 6], &[2, 3]);
+    /// B2/S
+    pub const SEEDS: Self = Self::new(&[2], &[]);
+    /// B3678/S34678
+    pub const DAY_AND_NIGHT: Self = Self::new(&[3, 6, 7, 8], &[3, 4, 6, 7, 8]);
+    /// B1357/S1357
+    pub const REPLICATOR: Self = Self::new(&[1, 3, 5, 7], &[1, 3, 5, 7]);
+
+    /// Rules cycled through in the editor
+    pub const PRESETS: [Self; 5] = [
+        Self::CONWAY,
+        Self::HIGHLIFE,
+        Self::SEEDS,
+        Self::DAY_AND_NIGHT,
+        Self::REPLICATOR,
+    ];
+
+    /// Create a rule from the neighbor counts giving birth and survival
+    #[must_use]
+    pub const fn new(birth: &[u8], survive: &[u8]) -> Self {
+        Self {
+            birth: mask(birth),
+            survive: mask(survive),
+            neighborhood: Neighborhood::Moore,
+        }
+    }
+
+    /// Create a rule counting only the diagonal neighbors, from 0 to 4
+    #[must_use]
+    pub const fn diagonal(birth: &[u8], survive: &[u8]) -> Self {
+        Self {
+            birth: mask(birth),
+            survive: mask(survive),
+            neighborhood: Neighborhood::Diagonal,
+        }
+    }
+
+    /// State of a cell on the next generation
+    #[must_use]
+    pub fn next_state(&self, alive: bool, neighbors: u8) -> bool {
+        let mask = if alive { self.survive } else { self.birth };
+        mask.wrapping_shr(neighbors as u32) & 1 != 0
+    }
+
+    /// Parse a rule in the B3/S23 or the 23/3 notation, ending with D for diagonal rules
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (text, neighborhood) = match strip_letter_suffix(text, 'D') {
+            Some(text) => (text, Neighborhood::Diagonal),
+            None => (text, Neighborhood::Moore),
+        };
+        let counts = |text: &str| {
+            text.chars().try_fold(0u16, |mask, c| {
+                Some(
+                    mask | 1
+                        << c.to_digit(10)
+                            .filter(|&n| n <= neighborhood.size() as u32)?,
+                )
+            })
+        };
+        let (first, second) = text.split_once('/')?;
+        let (birth, survive) = if let Some(birth) = strip_letter(first, 'B') {
+            (birth, strip_letter(second, 'S')?)
+        } else if let Some(survive) = strip_letter(first, 'S') {
+            (strip_letter(second, 'B')?, survive)
+        } else {
+            (second, first)
+        };
+        Some(Self {
+            birth: counts(birth)?,
+            survive: counts(survive)?,
+            neighborhood,
+        })
+    }
+
+    /// Toggle whether a dead cell with `neighbors` alive neighbors comes alive
+    pub fn toggle_birth(&mut self, neighbors: u8) {
+        self.birth ^= 1 << neighbors;
+    }
+
+    /// Toggle whether an alive cell with `neighbors` alive neighbors stays alive
+    pub fn toggle_survive(&mut self, neighbors: u8) {
+        self.survive ^= 1 << neighbors;
+    }
+
+    /// Switch between counting all 8 neighbors and only the diagonal ones
+    ///
+    /// Counts a diagonal neighborhood can't reach are dropped.
+    pub fn toggle_neighborhood(&mut self) {
+        self.neighborhood = match self.neighborhood {
+            Neighborhood::Moore => Neighborhood::Diagonal,
+            Neighborhood::Diagonal => Neighborhood::Moore,
+        };
+        let reachable = (1 << (self.neighborhood.size() + 1)) - 1;
+        self.birth &= reachable;
+        self.survive &= reachable;
+    }
+
+    /// Percentage of alive cells in a random soup worth running under this rule
+    ///
+    /// The fewer neighbors it takes to be born, the sparser a soup has to be not to fill
+    /// the board at once.
+    #[must_use]
+    pub fn soup_density(&self) -> u32 {
+        // Birth on 0 neighbors fills the board whatever the density
+        match (self.birth & !1).trailing_zeros() {
+            1 => 10,
+            2 => 20,
+            _ => 35,
+        }
+    }
+
+    /// The preset following this rule, or Conway's if it isn't a preset
+    #[must_use]
+    pub fn next_preset(self) -> Self {
+        Self::PRESETS
+            .iter()
+            .position(|&rule| rule == self)
+            .map_or(Self::CONWAY, |i| {
+                Self::PRESETS[(i + 1) % Self::PRESETS.len()]
+            })
+    }
+}
+
+impl fmt::Display for Rule {
+    /// Write the rule in the B3/S23 notation, followed by D for diagonal rules
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("B")?;
+        for n in (0..=8).filter(|n| self.birth >> n & 1 != 0) {
+            write!(f, "{}", n)?;
+        }
+        f.write_str("/S")?;
+        for n in (0..=8).filter(|n| self.survive >> n & 1 != 0) {
+            write!(f, "{}", n)?;
+        }
+        if let Neighborhood::Diagonal = self.neighborhood {
+            f.write_str("D")?;
+        }
+        Ok(())
+    }
+}
+
+/// Strip a letter from the start of a text, in either case
+fn strip_letter(text: &str, letter: char) -> Option<&str> {
+    text.strip_prefix(letter)
+        .or_else(|| text.strip_prefix(letter.to_ascii_lowercase()))
+}
+
+/// Strip a letter from the end of a text, in either case
+fn strip_letter_suffix(text: &str, letter: char) -> Option<&str> {
+    text.strip_suffix(letter)
+        .or_else(|| text.strip_suffix(letter.to_ascii_lowercase()))
+}
+
+const fn mask(counts: &[u8]) -> u16 {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < counts.len() {
+        mask |= 1 << counts[i];
+        i += 1;
+    }
+    mask
+}
+
+/// What lies beyond the board edges
+#[derive(Clone, Copy, PartialEq)]
+pub enum BoundaryMode {
+    /// Cells outside the board are always dead
+    Dead,
+    /// The board wraps around like a torus
+    Wrap,
+}
+
+/// A cellular automaton on the board, deciding the next state of each cell
+///
+/// # Example
+///
+/// ```
+/// use rule::Automaton;
+///
+/// /// Every cell copies its left neighbor
+/// struct ShiftRight;
+///
+/// impl Automaton for ShiftRight {
+///     fn next_state(&self, board: &Board<bool>, x: usize, y: usize) -> bool {
+///         x > 0 && board[x - 1][y]
+///     }
+/// }
+/// ```
+pub trait Automaton {
+    /// State of the cell at (`x`, `y`) on the next generation
+    fn next_state(&self, board: &Board<bool>, x: usize, y: usize) -> bool;
+
+    /// Whether a dead cell without alive neighbors always stays dead
+    ///
+    /// Only the neighborhoods of alive cells have to be updated then.
+    fn quiescent(&self) -> bool {
+        false
+    }
+}
+
+impl<A: Automaton> Automaton for &A {
+    fn next_state(&self, board: &Board<bool>, x: usize, y: usize) -> bool {
+        (*self).next_state(board, x, y)
+    }
+
+    fn quiescent(&self) -> bool {
+        (*self).quiescent()
+    }
+}
+
+/// Life-like automaton, counting the alive neighbors of each cell in the rule's neighborhood
+#[derive(Clone, Copy, PartialEq)]
+pub struct LifeLike {
+    pub rule: Rule,
+    pub boundary: BoundaryMode,
+}
+
+impl Automaton for LifeLike {
+    fn next_state(&self, board: &Board<bool>, x: usize, y: usize) -> bool {
+        let cell = LogicalPos::new(x as u16, y as u16);
+        let neighbors = neighbor_count(board, cell, self.rule.neighborhood, self.boundary);
+        self.rule.next_state(board[x][y], neighbors)
+    }
+
+    fn quiescent(&self) -> bool {
+        // With B0, cells far from any alive cell can be born too
+        self.rule.birth & 1 == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::analyze_until;
+    use crate::step::{Progress, StepContext};
+    use crate::{run_once_sync, COLUMN_SIZE, LINE_SIZE};
+
+    const WIDTH: usize = LINE_SIZE as usize;
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; WIDTH];
+
+    /// Elementary automaton, with the history of its row scrolling down the board
+    ///
+    /// The top row is the current generation, each cell of it following the Wolfram `rule`
+    /// from itself and its 2 neighbors, wrapping around. The other rows copy the one above.
+    struct Elementary(u8);
+
+    impl Automaton for Elementary {
+        fn next_state(&self, board: &Board<bool>, x: usize, y: usize) -> bool {
+            if y > 0 {
+                return board[x][y - 1];
+            }
+            let left = board[(x + WIDTH - 1) % WIDTH][0] as u8;
+            let right = board[(x + 1) % WIDTH][0] as u8;
+            let pattern = left << 2 | (board[x][0] as u8) << 1 | right;
+            self.0 >> pattern & 1 != 0
+        }
+
+        fn quiescent(&self) -> bool {
+            // A row of dead cells stays dead, and the rows below only see the ones above
+            self.0 & 1 == 0
+        }
+    }
+
+    #[test]
+    fn rule_90_draws_a_sierpinski_triangle() {
+        let mut board = EMPTY;
+        board[40][0] = true;
+        for _ in 0..31 {
+            run_once_sync(&mut board, &Elementary(90));
+        }
+        // Row `y` is generation `31 - y`, where the cell `i` away from the first one is
+        // alive when C(t, (t + i) / 2) is odd
+        for (x, column) in board.iter().enumerate() {
+            for (y, &alive) in column.iter().enumerate().take(32) {
+                let (t, i) = (31 - y as i32, x as i32 - 40);
+                let odd = (t + i) % 2 == 0 && i.abs() <= t && ((t + i) / 2) & t == (t + i) / 2;
+                assert_eq!(alive, odd, "({}, {})", x, y);
+            }
+        }
+        assert!(board
+            .iter()
+            .all(|column| column[32..].iter().all(|&alive| !alive)));
+    }
+
+    #[test]
+    fn other_automata_can_be_stepped_in_parts_and_analyzed() {
+        // Rule 30 is chaotic, rule 1 isn't quiescent
+        for rule in [30, 1] {
+            let mut at_once = EMPTY;
+            at_once[10][0] = true;
+            at_once[11][0] = true;
+            let mut in_parts = at_once;
+            for _ in 0..20 {
+                run_once_sync(&mut at_once, &Elementary(rule));
+                let mut step = StepContext::begin(&in_parts, Elementary(rule));
+                while let Progress::InProgress = step.advance(50) {}
+                step.commit(&mut in_parts);
+                assert!(in_parts == at_once, "rule {}", rule);
+            }
+        }
+
+        // Rule 4 keeps isolated cells and kills the others, the history fills the board
+        // and the board stops changing
+        let mut board = EMPTY;
+        board[5][0] = true;
+        board[6][0] = true;
+        board[20][0] = true;
+        let analysis = analyze_until(&board, &Elementary(4), 100, || true);
+        assert_eq!(analysis.final_population, COLUMN_SIZE as u32);
+        assert_eq!(analysis.detected_period, Some(1));
+        assert_eq!(analysis.generations_to_stabilize, Some(COLUMN_SIZE as u32));
+    }
+}
@@ -0,0 +1,254 @@
+use crate::eadk::{display, key, Color, Point, Rect, State};
+use crate::palette::Theme;
+use crate::rng::Rng;
+use crate::rule::{BoundaryMode, LifeLike, Neighborhood, Rule};
+use crate::{run_once_sync, Board, COLUMN_SIZE, LINE_SIZE};
+use core::fmt::Write;
+use heapless::String;
+
+const TOGGLE_SIZE: u16 = 22;
+const TOGGLES_X: u16 = 90;
+const BIRTH_Y: u16 = 36;
+const SURVIVE_Y: u16 = 66;
+
+/// Soup the preview steps, always the same so changes to the rule can be compared
+const PREVIEW_SEED: u32 = 0xB352;
+const PREVIEW_WIDTH: u16 = 24;
+const PREVIEW_HEIGHT: u16 = 16;
+const PREVIEW_SCALE: u16 = 5;
+const PREVIEW_Y: u16 = 146;
+
+/// Editor of the birth and survival neighbor counts of the rule
+pub struct RuleEditor {
+    survive_row: bool,
+    neighbors: u8,
+}
+
+impl RuleEditor {
+    pub const fn new() -> Self {
+        Self {
+            survive_row: false,
+            neighbors: 0,
+        }
+    }
+
+    /// Move the selection with the arrows and flip the selected count with EXE or OK
+    ///
+    /// × switches to counting only the diagonal neighbors, and back.
+    /// Returns whether the screen has to be redrawn.
+    pub fn update(&mut self, pressed: &State, rule: &mut Rule) -> bool {
+        let counts = rule.neighborhood.size() + 1;
+        if pressed.key_down(key::LEFT) {
+            self.neighbors = (self.neighbors + counts - 1) % counts;
+        } else if pressed.key_down(key::RIGHT) {
+            self.neighbors = (self.neighbors + 1) % counts;
+        } else if pressed.key_down(key::MULTIPLICATION) {
+            rule.toggle_neighborhood();
+            self.neighbors = self.neighbors.min(rule.neighborhood.size());
+        } else if pressed.key_down(key::UP) || pressed.key_down(key::DOWN) {
+            self.survive_row = !self.survive_row;
+        } else if pressed.key_down(key::EXE) || pressed.key_down(key::OK) {
+            if self.survive_row {
+                rule.toggle_survive(self.neighbors);
+            } else {
+                rule.toggle_birth(self.neighbors);
+            }
+        } else {
+            return false;
+        }
+        true
+    }
+
+    pub fn draw(&self, rule: &Rule, theme: &Theme) {
+        display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+        draw_text("Rule editor (BACK to leave)\0", Point::new(4, 4));
+
+        draw_text("Birth\0", Point::new(4, BIRTH_Y + 2));
+        draw_text("Survive\0", Point::new(4, SURVIVE_Y + 2));
+        for neighbors in 0..=rule.neighborhood.size() {
+            self.draw_toggle(neighbors, false, rule.birth, theme);
+            self.draw_toggle(neighbors, true, rule.survive, theme);
+        }
+
+        let mut notation: String<40> = String::new();
+        let other = match rule.neighborhood {
+            Neighborhood::Moore => "diagonal",
+            Neighborhood::Diagonal => "all 8",
+        };
+        write!(notation, "{}  ×: {}\0", rule, other).unwrap();
+        draw_text(&notation, Point::new(4, 100));
+
+        draw_preview(rule);
+    }
+
+    fn draw_toggle(&self, neighbors: u8, survive_row: bool, mask: u16, theme: &Theme) {
+        let origin = Point::new(
+            TOGGLES_X + neighbors as u16 * (TOGGLE_SIZE + 2),
+            if survive_row { SURVIVE_Y } else { BIRTH_Y },
+        );
+        let on = mask >> neighbors & 1 != 0;
+        let selected = survive_row == self.survive_row && neighbors == self.neighbors;
+
+        display::push_rect_uniform(
+            Rect::new(origin.x, origin.y, TOGGLE_SIZE, TOGGLE_SIZE),
+            if selected {
+                theme.highlight
+            } else {
+                Color::BLACK
+            },
+        );
+        let (text, background) = if on {
+            (Color::WHITE, Color::BLACK)
+        } else {
+            (Color::BLACK, Color::WHITE)
+        };
+        display::push_rect_uniform(
+            Rect::new(origin.x + 2, origin.y + 2, TOGGLE_SIZE - 4, TOGGLE_SIZE - 4),
+            background,
+        );
+        display::draw_char(
+            b'0' + neighbors,
+            Point::new(origin.x + 6, origin.y + 4),
+            false,
+            text,
+            background,
+        );
+    }
+}
+
+fn draw_text(text: &str, pos: Point) {
+    display::draw_string(text, pos, true, Color::BLACK, Color::WHITE);
+}
+
+/// Show a test soup and its next generation under the rule
+fn draw_preview(rule: &Rule) {
+    let automaton = LifeLike {
+        rule: *rule,
+        boundary: BoundaryMode::Dead,
+    };
+
+    // Soup in the middle of the preview window, itself in the middle of the board
+    let window = (
+        (LINE_SIZE - PREVIEW_WIDTH) / 2,
+        (COLUMN_SIZE - PREVIEW_HEIGHT) / 2,
+    );
+    let mut board: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    let mut rng = Rng::new(PREVIEW_SEED);
+    for x in window.0 + 7..window.0 + PREVIEW_WIDTH - 7 {
+        for y in window.1 + 4..window.1 + PREVIEW_HEIGHT - 4 {
+            board[x as usize][y as usize] = rng.below(2) == 0;
+        }
+    }
+
+    draw_text("Now\0", Point::new(20, PREVIEW_Y - 22));
+    draw_window(&board, window, Point::new(20, PREVIEW_Y));
+    run_once_sync(&mut board, &automaton);
+    draw_text("Next\0", Point::new(180, PREVIEW_Y - 22));
+    draw_window(&board, window, Point::new(180, PREVIEW_Y));
+}
+
+fn draw_window(board: &Board<bool>, window: (u16, u16), origin: Point) {
+    display::push_rect_uniform(
+        Rect::new(
+            origin.x - 1,
+            origin.y - 1,
+            PREVIEW_WIDTH * PREVIEW_SCALE + 2,
+            PREVIEW_HEIGHT * PREVIEW_SCALE + 2,
+        ),
+        Color::BLACK,
+    );
+    for x in 0..PREVIEW_WIDTH {
+        for y in 0..PREVIEW_HEIGHT {
+            let alive = board[(window.0 + x) as usize][(window.1 + y) as usize];
+            display::push_rect_uniform(
+                Rect::new(
+                    origin.x + x * PREVIEW_SCALE,
+                    origin.y + y * PREVIEW_SCALE,
+                    PREVIEW_SCALE,
+                    PREVIEW_SCALE,
+                ),
+                if alive { Color::BLACK } else { Color::WHITE },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Press `keys` one after the other, returning whether each asked for a redraw
+    fn press(editor: &mut RuleEditor, rule: &mut Rule, keys: &[u32]) -> bool {
+        keys.iter()
+            .all(|&k| editor.update(&State::NONE.with(k), rule))
+    }
+
+    #[test]
+    fn toggles_flip_the_selected_count_of_their_row() {
+        let mut editor = RuleEditor::new();
+        let mut rule = Rule::CONWAY;
+        let right = [key::RIGHT; 6];
+        assert!(press(&mut editor, &mut rule, &right));
+        assert!(press(&mut editor, &mut rule, &[key::EXE]));
+        assert!(rule == Rule::HIGHLIFE);
+        assert_eq!(rule.birth, 1 << 3 | 1 << 6);
+
+        // Down to the survival row, then back to 2 neighbors
+        assert!(press(
+            &mut editor,
+            &mut rule,
+            &[key::DOWN, key::LEFT, key::LEFT]
+        ));
+        assert!(press(
+            &mut editor,
+            &mut rule,
+            &[key::LEFT, key::LEFT, key::OK]
+        ));
+        assert_eq!(rule.survive, 1 << 3);
+        assert_eq!(rule.birth, 1 << 3 | 1 << 6);
+        assert!(press(&mut editor, &mut rule, &[key::EXE]));
+        assert!(rule == Rule::HIGHLIFE);
+
+        // Up and down both switch rows
+        assert!(press(&mut editor, &mut rule, &[key::UP, key::EXE]));
+        assert_eq!(rule.birth, 1 << 2 | 1 << 3 | 1 << 6);
+        assert_eq!(rule.survive, 1 << 2 | 1 << 3);
+    }
+
+    #[test]
+    fn the_selection_wraps_around_the_counts_of_the_neighborhood() {
+        let mut editor = RuleEditor::new();
+        let mut rule = Rule::new(&[], &[]);
+        // Left of 0 is 8 with all 8 neighbors
+        assert!(press(&mut editor, &mut rule, &[key::LEFT, key::EXE]));
+        assert_eq!(rule.birth, 1 << 8);
+        assert!(press(&mut editor, &mut rule, &[key::RIGHT, key::EXE]));
+        assert_eq!(rule.birth, 1 << 8 | 1);
+
+        // Only 4 diagonal neighbors, the selection and the counts past 4 are cut down
+        assert!(press(
+            &mut editor,
+            &mut rule,
+            &[key::LEFT, key::MULTIPLICATION]
+        ));
+        assert!(rule.neighborhood == Neighborhood::Diagonal);
+        assert_eq!(rule.birth, 1);
+        assert!(press(&mut editor, &mut rule, &[key::EXE]));
+        assert_eq!(rule.birth, 1 << 4 | 1);
+        assert!(press(&mut editor, &mut rule, &[key::RIGHT, key::EXE]));
+        assert_eq!(rule.birth, 1 << 4);
+    }
+
+    #[test]
+    fn other_keys_change_nothing() {
+        let mut editor = RuleEditor::new();
+        let mut rule = Rule::CONWAY;
+        for k in [key::BACK, key::ZERO, key::SHIFT] {
+            assert!(!editor.update(&State::NONE.with(k), &mut rule));
+        }
+        assert!(!editor.update(&State::NONE, &mut rule));
+        assert!(rule == Rule::CONWAY);
+        assert!(press(&mut editor, &mut rule, &[key::EXE]));
+        assert_eq!(rule.birth, 1 | 1 << 3);
+    }
+}
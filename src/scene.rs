@@ -0,0 +1,262 @@
+use crate::config::{AppConfig, SimSpeed};
+use crate::patterns::{Pattern, BEACON, BLINKER, GLIDER_GUN, PULSAR, TOAD};
+use crate::rule::{BoundaryMode, Rule};
+use crate::viewport::LogicalPos;
+use crate::{place_pattern, Board, UpdateMode, COLUMN_SIZE, LINE_SIZE};
+
+/// One instruction of a scene
+pub enum Command {
+    /// Place a pattern with its top left corner on a cell
+    LoadPattern(&'static Pattern, LogicalPos),
+    SetRule(Rule),
+    SetSpeed(SimSpeed),
+    RunGens(u32),
+    /// Run generations at once, without drawing them
+    SkipGens(u32),
+    /// Show a nul terminated caption for some milliseconds, while the next commands go on
+    ShowText(&'static str, u64),
+    /// Wait for EXE to be pressed
+    WaitKey,
+    ClearBoard,
+}
+
+/// A scripted sequence of commands, played on the board
+pub struct Scene {
+    pub commands: &'static [Command],
+}
+
+/// Gosper's glider gun, firing for a while
+pub const GLIDER_GUN_DEMO: Scene = Scene {
+    commands: &[
+        Command::ClearBoard,
+        Command::SetRule(Rule::CONWAY),
+        Command::SetSpeed(SimSpeed::Fast),
+        Command::LoadPattern(GLIDER_GUN, LogicalPos::new(2, 2)),
+        Command::ShowText("Gosper's glider gun\0", 2000),
+        Command::RunGens(120),
+        Command::ShowText("A new glider every 30 generations\0", 3000),
+        Command::RunGens(240),
+        Command::SkipGens(600),
+        Command::ShowText("600 generations later, the gliders pile up\0", 3000),
+        Command::RunGens(60),
+        Command::ShowText("EXE: back to the editor\0", 60_000),
+        Command::WaitKey,
+    ],
+};
+
+/// Oscillators of the library one after the other, with their period
+pub const OSCILLATORS: Scene = Scene {
+    commands: &[
+        Command::SetRule(Rule::CONWAY),
+        Command::SetSpeed(SimSpeed::Slow),
+        Command::ClearBoard,
+        Command::LoadPattern(BLINKER, LogicalPos::new(38, 29)),
+        Command::ShowText("Blinker, period 2\0", 2400),
+        Command::RunGens(12),
+        Command::ClearBoard,
+        Command::LoadPattern(TOAD, LogicalPos::new(38, 29)),
+        Command::ShowText("Toad, period 2\0", 2400),
+        Command::RunGens(12),
+        Command::ClearBoard,
+        Command::LoadPattern(BEACON, LogicalPos::new(38, 28)),
+        Command::ShowText("Beacon, period 2\0", 2400),
+        Command::RunGens(12),
+        Command::ClearBoard,
+        Command::LoadPattern(PULSAR, LogicalPos::new(34, 24)),
+        Command::ShowText("Pulsar, period 3\0", 3600),
+        Command::RunGens(18),
+        Command::ShowText("EXE: back to the editor\0", 60_000),
+        Command::WaitKey,
+    ],
+};
+
+/// What the main loop has to do for the scene to go on
+pub enum Step {
+    /// Run a generation and draw its changes
+    Generation,
+    /// Run generations without drawing them, then redraw the board
+    Skip(u32),
+    /// The board was changed, redraw it
+    Redraw,
+    /// Show a nul terminated caption for some milliseconds
+    Caption(&'static str, u64),
+    /// Nothing to do this time
+    Idle,
+    /// The scene is over
+    Done,
+}
+
+/// Plays a scene, a command at a time so the main loop keeps scanning the keyboard
+pub struct Player {
+    scene: &'static Scene,
+    next: usize,
+    generations_left: u32,
+    waiting_key: bool,
+    /// Settings before the scene, given back when it stops
+    previous_config: AppConfig,
+}
+
+impl Player {
+    /// Start a scene with the settings it can rely on
+    ///
+    /// Scenes always run synchronously with dead edges, so a scene gives the same board
+    /// every time.
+    pub fn start(scene: &'static Scene, config: &mut AppConfig) -> Self {
+        let previous_config = *config;
+        config.update_mode = UpdateMode::Synchronous;
+        config.boundary = BoundaryMode::Dead;
+        config.edge_buffer = false;
+        config.uncapped = false;
+        Self {
+            scene,
+            next: 0,
+            generations_left: 0,
+            waiting_key: false,
+            previous_config,
+        }
+    }
+
+    /// Carry out the scene until something has to be done by the main loop
+    pub fn step(&mut self, board: &mut Board<bool>, config: &mut AppConfig, exe: bool) -> Step {
+        if self.generations_left > 0 {
+            self.generations_left -= 1;
+            return Step::Generation;
+        }
+        if self.waiting_key {
+            if !exe {
+                return Step::Idle;
+            }
+            self.waiting_key = false;
+        }
+
+        let command = match self.scene.commands.get(self.next) {
+            Some(command) => command,
+            None => return Step::Done,
+        };
+        self.next += 1;
+        match *command {
+            Command::LoadPattern(pattern, at) => {
+                place_pattern(board, pattern, at);
+                Step::Redraw
+            }
+            Command::SetRule(rule) => {
+                config.rule = rule;
+                Step::Idle
+            }
+            Command::SetSpeed(speed) => {
+                config.speed = speed;
+                Step::Idle
+            }
+            Command::RunGens(generations) => {
+                self.generations_left = generations;
+                Step::Idle
+            }
+            Command::SkipGens(generations) => Step::Skip(generations),
+            Command::ShowText(text, duration_ms) => Step::Caption(text, duration_ms),
+            Command::WaitKey => {
+                self.waiting_key = true;
+                Step::Idle
+            }
+            Command::ClearBoard => {
+                *board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+                Step::Redraw
+            }
+        }
+    }
+
+    /// Give back the settings from before the scene
+    pub fn stop(self, config: &mut AppConfig) {
+        *config = self.previous_config;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::period::checksum;
+    use crate::rule::LifeLike;
+    use crate::run_once_sync;
+
+    /// The board at the end of a scene, the generations run and the captions shown
+    ///
+    /// EXE is pressed when asked, and generations are run the way the main loop does.
+    fn play(scene: &'static Scene) -> (Board<bool>, u32, u32) {
+        // Settings a scene mustn't depend on, and a board it has to clear
+        let mut config = AppConfig {
+            boundary: BoundaryMode::Wrap,
+            update_mode: UpdateMode::AsyncRandom,
+            rule: Rule::SEEDS,
+            ..AppConfig::default()
+        };
+        let before = config.to_bytes();
+        let mut board = [[true; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        let (mut generations, mut captions) = (0, 0);
+        let mut player = Player::start(scene, &mut config);
+        let generation = |board: &mut Board<bool>, config: &AppConfig| {
+            assert!(config.update_mode == UpdateMode::Synchronous);
+            let automaton = LifeLike {
+                rule: config.rule,
+                boundary: config.boundary,
+            };
+            run_once_sync(board, &automaton);
+        };
+        loop {
+            match player.step(&mut board, &mut config, true) {
+                Step::Generation => {
+                    generation(&mut board, &config);
+                    generations += 1;
+                }
+                Step::Skip(skipped) => {
+                    (0..skipped).for_each(|_| generation(&mut board, &config));
+                    generations += skipped;
+                }
+                Step::Caption(..) => captions += 1,
+                Step::Redraw | Step::Idle => {}
+                Step::Done => break,
+            }
+        }
+        player.stop(&mut config);
+        assert_eq!(config.to_bytes(), before);
+        (board, generations, captions)
+    }
+
+    // Checksums from `tools/golden.rs`, for the last pattern of each scene alone
+
+    #[test]
+    fn the_glider_gun_demo_ends_on_the_same_board() {
+        let (board, generations, captions) = play(&GLIDER_GUN_DEMO);
+        assert_eq!((generations, captions), (1020, 4));
+        assert_eq!(checksum(&board), 0x3139_809F);
+    }
+
+    #[test]
+    fn the_oscillator_showcase_ends_on_the_pulsar() {
+        let (board, generations, captions) = play(&OSCILLATORS);
+        assert_eq!((generations, captions), (54, 5));
+        assert_eq!(checksum(&board), 0x9AB1_5065);
+        let mut pulsar = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        place_pattern(&mut pulsar, PULSAR, LogicalPos::new(34, 24));
+        assert!(board == pulsar);
+    }
+
+    #[test]
+    fn scenes_wait_for_exe() {
+        let mut config = AppConfig::default();
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        let mut player = Player::start(&GLIDER_GUN_DEMO, &mut config);
+        // Up to the last caption, skipping the generations
+        while !matches!(player.step(&mut board, &mut config, false), Step::Caption(text, _) if text.starts_with("EXE"))
+        {
+        }
+        for _ in 0..10 {
+            assert!(matches!(
+                player.step(&mut board, &mut config, false),
+                Step::Idle
+            ));
+        }
+        assert!(matches!(
+            player.step(&mut board, &mut config, true),
+            Step::Done
+        ));
+    }
+}
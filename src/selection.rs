@@ -0,0 +1,184 @@
+use crate::bitgrid::{BitGrid, BlitMode};
+use crate::eadk::{display, Color, Rect, SCREEN_WIDTH};
+use crate::palette::ColorPalette;
+use crate::viewport::{LogicalPos, Viewport};
+use crate::{Board, COLUMN_SIZE, LINE_SIZE};
+
+/// Rectangle of cells on the board, bounds included
+#[derive(Clone, Copy)]
+pub struct CellRect {
+    pub left: u16,
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+}
+
+impl CellRect {
+    /// The rect between two opposite corners, in any order, clamped to the board
+    pub fn from_corners(a: LogicalPos, b: LogicalPos) -> Self {
+        Self {
+            left: a.x.min(b.x).min(LINE_SIZE - 1),
+            top: a.y.min(b.y).min(COLUMN_SIZE - 1),
+            right: a.x.max(b.x).min(LINE_SIZE - 1),
+            bottom: a.y.max(b.y).min(COLUMN_SIZE - 1),
+        }
+    }
+
+    pub fn cells(self) -> impl Iterator<Item = LogicalPos> {
+        (self.left..=self.right)
+            .flat_map(move |x| (self.top..=self.bottom).map(move |y| LogicalPos::new(x, y)))
+    }
+
+    /// Cells on the edges of the rect
+    pub fn border_cells(self) -> impl Iterator<Item = LogicalPos> {
+        self.cells().filter(move |&LogicalPos { x, y }| {
+            x == self.left || x == self.right || y == self.top || y == self.bottom
+        })
+    }
+
+    /// Draw a one pixel outline along the inside of the part of the rect in view
+    pub fn draw_outline(self, viewport: &Viewport, color: Color) {
+        let (columns, rows) = viewport.size();
+        let (first, last) = (
+            LogicalPos::new(
+                self.left.max(viewport.origin.x),
+                self.top.max(viewport.origin.y),
+            ),
+            LogicalPos::new(
+                self.right.min(viewport.origin.x + columns - 1),
+                self.bottom.min(viewport.origin.y + rows - 1),
+            ),
+        );
+        let (top_left, bottom_right) = match (viewport.cell_rect(first), viewport.cell_rect(last)) {
+            (Some(top_left), Some(bottom_right)) if first.x <= last.x && first.y <= last.y => {
+                (top_left, bottom_right)
+            }
+            _ => return,
+        };
+        let (x, y) = (top_left.x, top_left.y);
+        let width = bottom_right.x + bottom_right.width - x;
+        let height = bottom_right.y + bottom_right.height - y;
+        display::push_rect_uniform(Rect::new(x, y, width, 1), color);
+        display::push_rect_uniform(Rect::new(x, y + height - 1, width, 1), color);
+        display::push_rect_uniform(Rect::new(x, y, 1, height), color);
+        display::push_rect_uniform(Rect::new(x + width - 1, y, 1, height), color);
+    }
+}
+
+/// Flip every cell inside the rect, leaving the rest of the board untouched
+pub fn invert_region(board: &mut Board<bool>, rect: CellRect) {
+    BitGrid::filled(rect.right - rect.left + 1, rect.bottom - rect.top + 1).blit_onto(
+        board,
+        (rect.left as i16, rect.top as i16),
+        BlitMode::Xor,
+    );
+}
+
+/// Flip every cell inside the rect and redraw the part of it in view
+///
+/// The rect is drawn a line of pixels at a time rather than a cell at a time, so inverting
+/// a large selection costs about as much as drawing it once.
+pub fn toggle_region(
+    board: &mut Board<bool>,
+    rect: CellRect,
+    palette: &ColorPalette,
+    viewport: &Viewport,
+) {
+    invert_region(board, rect);
+
+    let (columns, rows) = viewport.size();
+    let (left, right) = (
+        rect.left.max(viewport.origin.x),
+        rect.right.min(viewport.origin.x + columns - 1),
+    );
+    let (top, bottom) = (
+        rect.top.max(viewport.origin.y),
+        rect.bottom.min(viewport.origin.y + rows - 1),
+    );
+    let (first, size) = match viewport.cell_rect(LogicalPos::new(left, top)) {
+        Some(first) if left <= right && top <= bottom => (first, viewport.cell_size),
+        _ => return,
+    };
+    let width = ((right - left + 1) * size) as usize;
+    let mut line = [Color::BLACK; SCREEN_WIDTH as usize];
+    for (row, y) in (top..=bottom).enumerate() {
+        for dy in 0..size {
+            for (i, pixel) in line[..width].iter_mut().enumerate() {
+                let (x, dx) = (left + i as u16 / size, i as u16 % size);
+                *pixel = if viewport.grid && (dx == 0 || dy == 0) {
+                    palette.grid
+                } else {
+                    palette.cell(board[x as usize][y as usize], LogicalPos::new(x, y))
+                };
+            }
+            display::push_row(first.y + row as u16 * size + dy, first.x, &line[..width]);
+        }
+    }
+}
+
+/// Kill the cells outside of a mask, where it's `false`
+pub fn apply_mask(board: &mut Board<bool>, mask: &Board<bool>) {
+    for (column, mask_column) in board.iter_mut().zip(mask.iter()) {
+        for (cell, &inside) in column.iter_mut().zip(mask_column.iter()) {
+            *cell &= inside;
+        }
+    }
+}
+
+/// A mask of the cells of a rect
+pub fn create_rect_mask(rect: CellRect) -> Board<bool> {
+    let mut mask = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    BitGrid::filled(rect.right - rect.left + 1, rect.bottom - rect.top + 1).blit_onto(
+        &mut mask,
+        (rect.left as i16, rect.top as i16),
+        BlitMode::Or,
+    );
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autosolve::seed_soup;
+    use crate::palette::PALETTES;
+
+    #[test]
+    fn toggled_regions_leave_the_cells_outside_untouched() {
+        let mut start = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        seed_soup(&mut start, 0x7066, 40);
+        let zoomed = Viewport {
+            origin: LogicalPos::new(10, 10),
+            cell_size: 8,
+            grid: true,
+        };
+        let corner = LogicalPos::new(LINE_SIZE - 1, COLUMN_SIZE - 1);
+        for (a, b, viewport) in [
+            (
+                LogicalPos::new(12, 15),
+                LogicalPos::new(30, 20),
+                Viewport::FULL,
+            ),
+            // Partly out of view, and with the corners swapped
+            (LogicalPos::new(40, 25), LogicalPos::new(5, 8), zoomed),
+            // Down to the corner of the board, all out of view
+            (LogicalPos::new(70, 50), corner, zoomed),
+            (corner, corner, Viewport::FULL),
+        ] {
+            let rect = CellRect::from_corners(a, b);
+            let mut board = start;
+            toggle_region(&mut board, rect, &PALETTES[0], &viewport);
+            for x in 0..LINE_SIZE {
+                for y in 0..COLUMN_SIZE {
+                    let inside = (rect.left..=rect.right).contains(&x)
+                        && (rect.top..=rect.bottom).contains(&y);
+                    let (before, after) =
+                        (start[x as usize][y as usize], board[x as usize][y as usize]);
+                    assert_eq!(after, before != inside, "({}, {})", x, y);
+                }
+            }
+            // Toggling twice gives the board back
+            toggle_region(&mut board, rect, &PALETTES[0], &viewport);
+            assert!(board == start);
+        }
+    }
+}
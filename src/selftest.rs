@@ -0,0 +1,270 @@
+use crate::eadk::{
+    self, backlight, display, key, keyboard, timing, Color, Point, Rect, State, SCREEN_HEIGHT,
+    SCREEN_WIDTH,
+};
+use crate::golden::CORPUS;
+use crate::palette::Theme;
+#[cfg(feature = "shadow_fb")]
+use crate::palette::PALETTES;
+#[cfg(feature = "shadow_fb")]
+use crate::rule::{BoundaryMode, LifeLike, Rule};
+#[cfg(feature = "shadow_fb")]
+use crate::viewport::{LogicalPos, Viewport};
+#[cfg(feature = "shadow_fb")]
+use crate::{draw_board, run_once_sync, Board, COLUMN_SIZE, LINE_SIZE};
+use core::fmt::Write;
+use heapless::String;
+
+/// A single on-device check
+///
+/// Visual checks draw something and ask the user to confirm it with OK (pass) or BACK (fail).
+struct Check {
+    name: &'static str,
+    run: fn() -> bool,
+}
+
+const CHECKS: &[Check] = &[
+    Check {
+        name: "Corner rects\0",
+        run: corner_rects,
+    },
+    Check {
+        name: "Long strings\0",
+        run: long_strings,
+    },
+    Check {
+        name: "Keyboard bits\0",
+        run: keyboard_bits,
+    },
+    Check {
+        name: "Backlight\0",
+        run: backlight_round_trip,
+    },
+    Check {
+        name: "Millis\0",
+        run: millis_monotonic,
+    },
+    Check {
+        name: "Random\0",
+        run: random_varies,
+    },
+    Check {
+        name: "Golden boards\0",
+        run: golden_boards,
+    },
+    #[cfg(feature = "shadow_fb")]
+    Check {
+        name: "Drawn generation\0",
+        run: drawn_generation,
+    },
+];
+
+/// Run every check, then show a pass/fail report until BACK is pressed
+pub fn run(theme: &Theme) {
+    let mut results = [false; CHECKS.len()];
+    for (result, check) in results.iter_mut().zip(CHECKS.iter()) {
+        display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+        *result = (check.run)();
+    }
+
+    display::push_rect_uniform(Rect::SCREEN, Color::WHITE);
+    draw_line(0, "Self-test (BACK to leave)\0", Color::BLACK);
+    for (i, (check, &passed)) in CHECKS.iter().zip(results.iter()).enumerate() {
+        let row = i as u16 + 2;
+        draw_line(row, check.name, Color::BLACK);
+        display::draw_string(
+            if passed { "PASS\0" } else { "FAIL\0" },
+            Point::new(SCREEN_WIDTH - 60, row * 20),
+            true,
+            if passed { theme.success } else { theme.warning },
+            Color::WHITE,
+        );
+    }
+
+    wait_back();
+}
+
+fn draw_line(row: u16, text: &str, color: Color) {
+    display::draw_string(text, Point::new(10, row * 20), true, color, Color::WHITE);
+}
+
+fn keys_down(state: &State) -> u32 {
+    (key::LEFT..=key::EXE)
+        .filter(|&k| state.key_down(k))
+        .count() as u32
+}
+
+/// Wait for BACK to be pressed, and released
+fn wait_back() {
+    wait_release();
+    while !keyboard::scan().key_down(key::BACK) {
+        timing::msleep(10);
+    }
+    wait_release();
+}
+
+fn wait_release() {
+    while keys_down(&keyboard::scan()) != 0 {
+        timing::msleep(10);
+    }
+}
+
+/// Ask a yes/no question in the middle of the screen
+fn confirm(question: &str) -> bool {
+    display::draw_string(
+        question,
+        Point::new(10, SCREEN_HEIGHT / 2),
+        false,
+        Color::BLACK,
+        Color::WHITE,
+    );
+    wait_release();
+    loop {
+        let state = keyboard::scan();
+        if state.key_down(key::OK) {
+            return true;
+        } else if state.key_down(key::BACK) {
+            return false;
+        }
+        timing::msleep(10);
+    }
+}
+
+fn corner_rects() -> bool {
+    let size = 10;
+    for (x, y) in [
+        (0, 0),
+        (SCREEN_WIDTH - size, 0),
+        (0, SCREEN_HEIGHT - size),
+        (SCREEN_WIDTH - size, SCREEN_HEIGHT - size),
+    ] {
+        display::push_rect_uniform(Rect::new(x, y, size, size), Color::BLACK);
+    }
+    confirm("4 full black squares in the corners? OK/BACK\0")
+}
+
+fn long_strings() -> bool {
+    // 32 large characters and 45 small ones fill the screen width
+    display::draw_string(
+        "0123456789ABCDEFGHIJKLMNOPQRSTUV\0",
+        Point::new(0, 10),
+        true,
+        Color::BLACK,
+        Color::WHITE,
+    );
+    display::draw_string(
+        "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghi\0",
+        Point::new(0, 40),
+        false,
+        Color::BLACK,
+        Color::WHITE,
+    );
+    confirm("Both lines complete, nothing after? OK/BACK\0")
+}
+
+fn keyboard_bits() -> bool {
+    draw_line(1, "Press EXE\0", Color::BLACK);
+    wait_release();
+    loop {
+        let state = keyboard::scan();
+        if keys_down(&state) != 0 {
+            return state.key_down(key::EXE) && keys_down(&state) == 1;
+        }
+        timing::msleep(10);
+    }
+}
+
+fn backlight_round_trip() -> bool {
+    let original = backlight::brightness();
+    let target = if original > 128 {
+        original - 64
+    } else {
+        original + 64
+    };
+    backlight::set_brightness(target);
+    let passed = backlight::brightness() == target;
+    backlight::set_brightness(original);
+    passed
+}
+
+fn millis_monotonic() -> bool {
+    let before = timing::millis();
+    timing::msleep(50);
+    let after = timing::millis();
+    after >= before + 50 && after < before + 1000
+}
+
+fn random_varies() -> bool {
+    let first = eadk::random();
+    (0..8).any(|_| eadk::random() != first)
+}
+
+/// Step every evolution of the golden corpus, all at once and in parts
+///
+/// Failures are listed with the checksum they got, and wait for OK or BACK to be read.
+fn golden_boards() -> bool {
+    draw_line(0, "Stepping golden boards...\0", Color::BLACK);
+    let mut failures = 0;
+    for golden in CORPUS.iter() {
+        let (whole, in_parts) = (golden.run(false), golden.run(true));
+        if whole == golden.expected && in_parts == golden.expected {
+            continue;
+        }
+        failures += 1;
+        let mut text: String<48> = String::new();
+        write!(text, "{:08X} / {:08X} parts\0", whole, in_parts).unwrap();
+        let row = 2 * failures;
+        draw_line(row - 1, golden.name, Color::BLACK);
+        display::draw_string(
+            &text,
+            Point::new(20, row * 20),
+            false,
+            Color::BLACK,
+            Color::WHITE,
+        );
+    }
+    if failures == 0 {
+        return true;
+    }
+    draw_line(11, "BACK to go on\0", Color::BLACK);
+    wait_back();
+    false
+}
+
+/// Draw a blinker, step it a generation and draw it again, then check the screen cell by cell
+///
+/// The screen is read back from the shadow frame buffer, so it needs the `shadow_fb` feature.
+#[cfg(feature = "shadow_fb")]
+fn drawn_generation() -> bool {
+    let palette = &PALETTES[0];
+    let viewport = Viewport::FULL;
+    let center = (LINE_SIZE / 2, COLUMN_SIZE / 2);
+    let mut board: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    for x in center.0 - 1..=center.0 + 1 {
+        board[x as usize][center.1 as usize] = true;
+    }
+    draw_board(&board, palette, &viewport);
+    run_once_sync(
+        &mut board,
+        &LifeLike {
+            rule: Rule::CONWAY,
+            boundary: BoundaryMode::Dead,
+        },
+    );
+    draw_board(&board, palette, &viewport);
+
+    // The blinker turned vertical, and the cells around it are dead again
+    (center.0 - 2..=center.0 + 2).all(|x| {
+        (center.1 - 2..=center.1 + 2).all(|y| {
+            let alive = x == center.0 && y.abs_diff(center.1) <= 1;
+            let color = if alive {
+                palette.live_cell
+            } else {
+                palette.dead_cell
+            };
+            viewport
+                .cell_rect(LogicalPos::new(x, y))
+                .is_some_and(|rect| display::region_matches_color(rect, color))
+        })
+    })
+}
@@ -0,0 +1,95 @@
+use crate::patterns::GLIDER_GUN;
+use crate::rng::Rng;
+use crate::viewport::LogicalPos;
+use crate::{place_pattern, Board, COLUMN_SIZE, LINE_SIZE};
+
+/// Seed of the stress board used for benchmarks
+pub const STRESS_SEED: u32 = 0x5EED;
+
+/// Fill the board with a chaotic but reproducible pattern
+///
+/// A soup of 30% density drawn from a [`Rng`] seeded with `seed`, with a glider gun
+/// in the top left and bottom right corners to keep it busy.
+/// It doesn't use [`eadk::random`](crate::eadk::random), so the same seed always gives
+/// the same board and performance work can be compared run to run.
+pub fn stress_board(board: &mut Board<bool>, seed: u32) {
+    let mut rng = Rng::new(seed);
+    board
+        .iter_mut()
+        .flatten()
+        .for_each(|cell| *cell = rng.below(100) < 30);
+
+    let (width, height) = (GLIDER_GUN.width, GLIDER_GUN.height);
+    for (x, y) in [(1, 1), (LINE_SIZE - width - 1, COLUMN_SIZE - height - 1)] {
+        board[x as usize..(x + width) as usize]
+            .iter_mut()
+            .for_each(|column| column[y as usize..(y + height) as usize].fill(false));
+        place_pattern(board, GLIDER_GUN, LogicalPos::new(x, y));
+    }
+}
+
+/// Cells of the board in a clockwise spiral from the center outward
+pub fn spiral() -> impl Iterator<Item = LogicalPos> {
+    // Right, down, left then up, clockwise on screen
+    const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+    let side = LINE_SIZE.max(COLUMN_SIZE) as usize;
+    // Runs of 1, 1, 2, 2, 3, 3... steps, turning after each, until the board is covered
+    (0..2 * side)
+        .flat_map(|run| core::iter::repeat_n(DIRECTIONS[run % 4], run / 2 + 1))
+        .scan(
+            (LINE_SIZE as i32 / 2, COLUMN_SIZE as i32 / 2),
+            |position, (dx, dy)| {
+                let cell = *position;
+                *position = (position.0 + dx, position.1 + dy);
+                Some(cell)
+            },
+        )
+        .filter(|&(x, y)| x >= 0 && y >= 0 && x < LINE_SIZE as i32 && y < COLUMN_SIZE as i32)
+        .map(|(x, y)| LogicalPos::new(x as u16, y as u16))
+}
+
+/// Set every cell to what `alive` gives for its coordinates, visiting them in a [`spiral`]
+///
+/// Drawing the cells in the same order afterwards shows the board building from the center.
+pub fn spiral_fill(board: &mut Board<bool>, mut alive: impl FnMut(u16, u16) -> bool) {
+    for cell in spiral() {
+        board[cell.x as usize][cell.y as usize] = alive(cell.x, cell.y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stressed(seed: u32) -> Board<bool> {
+        let mut board = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        stress_board(&mut board, seed);
+        board
+    }
+
+    #[test]
+    fn stress_boards_depend_on_the_seed_alone() {
+        // Whatever was on the board before
+        let mut board = [[true; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        stress_board(&mut board, STRESS_SEED);
+        assert!(board == stressed(STRESS_SEED));
+        assert!(stressed(STRESS_SEED) != stressed(STRESS_SEED + 1));
+        assert!(stressed(1) != stressed(2));
+    }
+
+    #[test]
+    fn stress_boards_have_a_glider_gun_in_two_corners() {
+        let board = stressed(STRESS_SEED);
+        let (width, height) = (GLIDER_GUN.width, GLIDER_GUN.height);
+        for (x0, y0) in [(1, 1), (LINE_SIZE - width - 1, COLUMN_SIZE - height - 1)] {
+            for x in 0..width {
+                for y in 0..height {
+                    let cell = board[(x0 + x) as usize][(y0 + y) as usize];
+                    assert_eq!(cell, GLIDER_GUN.get(x, y), "({}, {})", x0 + x, y0 + y);
+                }
+            }
+        }
+        let alive = board.iter().flatten().filter(|&&alive| alive).count();
+        assert!((1200..1700).contains(&alive), "{}", alive);
+    }
+}
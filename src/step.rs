@@ -0,0 +1,256 @@
+use crate::rule::Automaton;
+use crate::{run_cell, Board, Changes, BOARD_SIZE, COLUMN_SIZE, LINE_SIZE};
+
+/// How far a generation in parts went
+pub enum Progress {
+    InProgress,
+    Finished,
+}
+
+/// A synchronous generation computed a few cells at a time
+///
+/// The board is only changed by [`StepContext::commit`], so a generation dropped halfway
+/// leaves it as it was.
+pub struct StepContext<A: Automaton> {
+    automaton: A,
+    /// The board when the generation began, seen by every cell
+    previous: Board<bool>,
+    /// Cells already computed
+    updated: Board<bool>,
+    changes: Changes,
+    /// Index of the next cell whose neighborhood is computed, in column-major order
+    next: usize,
+    /// Calls to [`StepContext::advance`] so far, for a watchdog to notice a runaway
+    advances: u32,
+}
+
+impl<A: Automaton> StepContext<A> {
+    pub fn begin(board: &Board<bool>, automaton: A) -> Self {
+        Self {
+            automaton,
+            previous: *board,
+            updated: [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize],
+            changes: Changes::new(),
+            next: 0,
+            advances: 0,
+        }
+    }
+
+    /// Calls to [`StepContext::advance`] the generation took so far
+    pub fn advances(&self) -> u32 {
+        self.advances
+    }
+
+    /// Compute at least `cell_budget` cells, or the rest of the generation
+    ///
+    /// Only the neighborhoods of alive cells are visited when the automaton is quiescent.
+    /// The budget is checked between neighborhoods, so up to 8 more cells can be computed.
+    pub fn advance(&mut self, cell_budget: usize) -> Progress {
+        self.advances += 1;
+        let visit_all = !self.automaton.quiescent();
+        let mut computed = 0;
+        while computed < cell_budget {
+            if self.next >= BOARD_SIZE {
+                return Progress::Finished;
+            }
+            let (x, y) = (
+                self.next / COLUMN_SIZE as usize,
+                self.next % COLUMN_SIZE as usize,
+            );
+            self.next += 1;
+            if !(self.previous[x][y] || visit_all) {
+                continue;
+            }
+
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    // Wrapping around is harmless with dead edges, the far cells are
+                    // just visited for nothing
+                    let c = (
+                        (x as isize + dx).rem_euclid(LINE_SIZE as isize) as usize,
+                        (y as isize + dy).rem_euclid(COLUMN_SIZE as isize) as usize,
+                    );
+                    if !self.updated[c.0][c.1] {
+                        self.updated[c.0][c.1] = true;
+                        computed += 1;
+                        if let Some(alive) = run_cell(&self.previous, c, &self.automaton) {
+                            self.changes.push(c, alive);
+                        }
+                    }
+                }
+            }
+        }
+        if self.next >= BOARD_SIZE {
+            Progress::Finished
+        } else {
+            Progress::InProgress
+        }
+    }
+
+    /// Apply the changes of the generation to the board, once it's finished
+    pub fn commit(self, board: &mut Board<bool>) -> Changes {
+        debug_assert!(self.next >= BOARD_SIZE);
+        self.changes.born.iter().for_each(|&(x, y)| {
+            board[x as usize][y as usize] = true;
+        });
+        self.changes.died.iter().for_each(|&(x, y)| {
+            board[x as usize][y as usize] = false;
+        });
+        self.changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+    use crate::rule::{BoundaryMode, LifeLike, Neighborhood, Rule};
+    use crate::run_once_sync;
+
+    /// Every cell takes the opposite of its state
+    struct Invert;
+
+    impl Automaton for Invert {
+        fn next_state(&self, board: &Board<bool>, x: usize, y: usize) -> bool {
+            !board[x][y]
+        }
+    }
+
+    /// Every cell takes the state of the cell on its left, wrapping around
+    struct ShiftRight;
+
+    impl Automaton for ShiftRight {
+        fn next_state(&self, board: &Board<bool>, x: usize, y: usize) -> bool {
+            board[(x + LINE_SIZE as usize - 1) % LINE_SIZE as usize][y]
+        }
+
+        fn quiescent(&self) -> bool {
+            true
+        }
+    }
+
+    const EMPTY: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+    const CELLS: [(usize, usize); 4] = [
+        (0, 0),
+        (LINE_SIZE as usize - 1, 5),
+        (10, COLUMN_SIZE as usize - 1),
+        (11, COLUMN_SIZE as usize - 1),
+    ];
+
+    fn board_of(cells: impl Iterator<Item = (usize, usize)>) -> Board<bool> {
+        let mut board = EMPTY;
+        cells.for_each(|(x, y)| board[x][y] = true);
+        board
+    }
+
+    fn shifted(cells: &[(usize, usize)]) -> impl Iterator<Item = (usize, usize)> + '_ {
+        cells
+            .iter()
+            .map(|&(x, y)| ((x + 1) % LINE_SIZE as usize, y))
+    }
+
+    #[test]
+    fn automata_not_quiescent_reach_every_cell() {
+        let mut board = EMPTY;
+        let changes = run_once_sync(&mut board, &Invert);
+        assert_eq!((changes.born.len(), changes.died.len()), (BOARD_SIZE, 0));
+        assert!(board.iter().flatten().all(|&alive| alive));
+    }
+
+    #[test]
+    fn quiescent_automata_follow_the_alive_cells() {
+        let mut board = board_of(CELLS.iter().copied());
+        let changes = run_once_sync(&mut board, &ShiftRight);
+        assert_eq!(board, board_of(shifted(&CELLS)));
+        // The two cells side by side overlap once shifted
+        assert_eq!((changes.born.len(), changes.died.len()), (3, 3));
+    }
+
+    #[test]
+    fn generations_in_parts_match_generations_at_once() {
+        let mut at_once = board_of(CELLS.iter().copied());
+        let mut in_parts = at_once;
+        run_once_sync(&mut at_once, &ShiftRight);
+
+        let mut step = StepContext::begin(&in_parts, ShiftRight);
+        while let Progress::InProgress = step.advance(7) {}
+        assert!(step.advances() > 1);
+        step.commit(&mut in_parts);
+        assert_eq!(in_parts, at_once);
+    }
+
+    /// The next generation, computed cell by cell from the definition of the rule
+    fn reference(board: &Board<bool>, rule: Rule, boundary: BoundaryMode) -> Board<bool> {
+        let (width, height) = (LINE_SIZE as isize, COLUMN_SIZE as isize);
+        let alive = |x: isize, y: isize| match boundary {
+            BoundaryMode::Dead => {
+                (0..width).contains(&x) && (0..height).contains(&y) && board[x as usize][y as usize]
+            }
+            BoundaryMode::Wrap => {
+                board[x.rem_euclid(width) as usize][y.rem_euclid(height) as usize]
+            }
+        };
+        let mut next = EMPTY;
+        for x in 0..width {
+            for y in 0..height {
+                let neighbors = rule
+                    .neighborhood
+                    .offsets()
+                    .iter()
+                    .filter(|&&(dx, dy)| alive(x + dx, y + dy))
+                    .count();
+                let mask = if alive(x, y) {
+                    rule.survive
+                } else {
+                    rule.birth
+                };
+                next[x as usize][y as usize] = mask >> neighbors & 1 != 0;
+            }
+        }
+        next
+    }
+
+    #[test]
+    fn generations_in_any_parts_follow_the_rule() {
+        let mut rng = Rng::new(0x1DEA);
+        for case in 0..200 {
+            let neighborhood = [Neighborhood::Moore, Neighborhood::Diagonal][case % 2];
+            let counts = 1 << (neighborhood.size() + 1);
+            let rule = Rule {
+                // Rules with B0 included, every cell has to be visited for them
+                birth: rng.below(counts) as u16,
+                survive: rng.below(counts) as u16,
+                neighborhood,
+            };
+            let boundary = [BoundaryMode::Dead, BoundaryMode::Wrap][case / 2 % 2];
+            let density = rng.below(101);
+            let mut board = EMPTY;
+            board
+                .iter_mut()
+                .flatten()
+                .for_each(|cell| *cell = rng.below(100) < density);
+            let before = board;
+
+            let mut step = StepContext::begin(&board, LifeLike { rule, boundary });
+            let budget = 1 + rng.below(600) as usize;
+            while let Progress::InProgress = step.advance(budget) {
+                // Nothing is changed before the generation is over
+                assert_eq!(board, before);
+            }
+            let changes = step.commit(&mut board);
+
+            assert_eq!(board, reference(&before, rule, boundary), "case {}", case);
+            for &(x, y) in &changes.born {
+                assert!(!before[x as usize][y as usize] && board[x as usize][y as usize]);
+            }
+            for &(x, y) in &changes.died {
+                assert!(before[x as usize][y as usize] && !board[x as usize][y as usize]);
+            }
+            let changed = (0..LINE_SIZE as usize)
+                .flat_map(|x| (0..COLUMN_SIZE as usize).map(move |y| (x, y)))
+                .filter(|&(x, y)| board[x][y] != before[x][y])
+                .count();
+            assert_eq!(changes.len(), changed);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{Board, COLUMN_SIZE, LINE_SIZE};
+
+/// Mirror applied to the board
+#[derive(Clone, Copy)]
+pub enum SymmetryMode {
+    /// Left and right halves mirror each other
+    Horizontal,
+    /// Top and bottom halves mirror each other
+    Vertical,
+    Both,
+}
+
+impl SymmetryMode {
+    pub fn next(self) -> Self {
+        match self {
+            SymmetryMode::Horizontal => SymmetryMode::Vertical,
+            SymmetryMode::Vertical => SymmetryMode::Both,
+            SymmetryMode::Both => SymmetryMode::Horizontal,
+        }
+    }
+
+    /// Nul terminated name, to be drawn as is
+    pub fn name(self) -> &'static str {
+        match self {
+            SymmetryMode::Horizontal => "Symmetry: horizontal\0",
+            SymmetryMode::Vertical => "Symmetry: vertical\0",
+            SymmetryMode::Both => "Symmetry: both\0",
+        }
+    }
+}
+
+/// Make the board symmetric, a cell being alive if it or its reflection was
+pub fn symmetrize_board(board: &mut Board<bool>, mode: SymmetryMode) {
+    if let SymmetryMode::Horizontal | SymmetryMode::Both = mode {
+        let width = LINE_SIZE as usize;
+        for x in 0..width / 2 {
+            let mirrored = board[width - 1 - x];
+            board[x]
+                .iter_mut()
+                .zip(mirrored.iter())
+                .for_each(|(cell, &mirror)| *cell |= mirror);
+            board[width - 1 - x] = board[x];
+        }
+    }
+    if let SymmetryMode::Vertical | SymmetryMode::Both = mode {
+        for column in board.iter_mut() {
+            let mirrored = *column;
+            column
+                .iter_mut()
+                .zip(mirrored.iter().rev())
+                .for_each(|(cell, &mirror)| *cell |= mirror);
+        }
+    }
+}
+
+/// Half of the board copied onto the other one
+#[derive(Clone, Copy)]
+pub enum MirrorSource {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Overwrite a half of the board with the mirror image of the other half
+///
+/// With an odd size, the middle column or row is part of the source and stays as is.
+pub fn mirror_half(board: &mut Board<bool>, source: MirrorSource) {
+    let (width, height) = (LINE_SIZE as usize, COLUMN_SIZE as usize);
+    match source {
+        MirrorSource::Left => {
+            for x in 0..width / 2 {
+                board[width - 1 - x] = board[x];
+            }
+        }
+        MirrorSource::Right => {
+            for x in 0..width / 2 {
+                board[x] = board[width - 1 - x];
+            }
+        }
+        MirrorSource::Top => {
+            for column in board.iter_mut() {
+                for y in 0..height / 2 {
+                    column[height - 1 - y] = column[y];
+                }
+            }
+        }
+        MirrorSource::Bottom => {
+            for column in board.iter_mut() {
+                for y in 0..height / 2 {
+                    column[y] = column[height - 1 - y];
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::eadk::timing::ScheduledAction;
+use crate::eadk::{display, Color, Point, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::CELL_SIZE;
+
+const TOAST_HEIGHT: u16 = 5 * CELL_SIZE;
+
+/// Short message shown at the bottom of the board
+pub struct Toast {
+    hide: Option<ScheduledAction>,
+}
+
+impl Toast {
+    /// Part of the screen covered by the toast
+    pub const AREA: Rect = Rect {
+        x: 0,
+        y: SCREEN_HEIGHT - TOAST_HEIGHT,
+        width: SCREEN_WIDTH,
+        height: TOAST_HEIGHT,
+    };
+
+    pub const fn new() -> Self {
+        Self { hide: None }
+    }
+
+    /// Show a nul terminated message for `duration_ms`
+    pub fn show(&mut self, text: &str, duration_ms: u64) {
+        display::push_rect_uniform(Self::AREA, Color::BLACK);
+        display::draw_string(
+            text,
+            Point::new(4, SCREEN_HEIGHT - TOAST_HEIGHT + 3),
+            false,
+            Color::WHITE,
+            Color::BLACK,
+        );
+        self.hide = Some(ScheduledAction::schedule(duration_ms));
+    }
+
+    /// Forget the toast, when the screen is repainted anyway
+    pub fn hide(&mut self) {
+        self.hide = None;
+    }
+
+    /// Once the toast times out, call `erase` to redraw the cells under it
+    pub fn on_expiry<F: FnOnce()>(&mut self, erase: F) {
+        if let Some(hide) = &mut self.hide {
+            if hide.poll(erase) {
+                self.hide = None;
+            }
+        }
+    }
+}
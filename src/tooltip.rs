@@ -0,0 +1,60 @@
+use crate::eadk::{display, Point, Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::palette::ColorPalette;
+
+/// Space between the anchor and the box
+const GAP: u16 = 6;
+/// Space between the box border and its text
+const PADDING: u16 = 2;
+
+/// Small box of text next to a point of the screen, drawn for a single frame
+pub struct Tooltip {
+    shown: Option<Rect>,
+}
+
+impl Tooltip {
+    pub const fn new() -> Self {
+        Self { shown: None }
+    }
+
+    /// Draw a nul terminated text in a bordered box next to `anchor`
+    ///
+    /// The box goes below and to the right of the anchor, or to the other side where it
+    /// would leave the screen.
+    pub fn draw(&mut self, text: &str, anchor: Point, palette: &ColorPalette) {
+        let (_, glyph_height) = display::glyph_size(false);
+        let (width, height) = (
+            display::text_width(text, false) + 2 * (PADDING + 1),
+            glyph_height + 2 * (PADDING + 1),
+        );
+        let x = if anchor.x + GAP + width <= SCREEN_WIDTH {
+            anchor.x + GAP
+        } else {
+            anchor.x.saturating_sub(GAP + width)
+        };
+        let y = if anchor.y + GAP + height <= SCREEN_HEIGHT {
+            anchor.y + GAP
+        } else {
+            anchor.y.saturating_sub(GAP + height)
+        };
+
+        let area = Rect::new(x, y, width, height);
+        display::push_rect_uniform(area, palette.live_cell);
+        display::push_rect_uniform(
+            Rect::new(x + 1, y + 1, width - 2, height - 2),
+            palette.status_bg,
+        );
+        display::draw_string(
+            text,
+            Point::new(x + PADDING + 1, y + PADDING + 1),
+            false,
+            palette.live_cell,
+            palette.status_bg,
+        );
+        self.shown = Some(area);
+    }
+
+    /// Part of the screen covered by the last tooltip, to be erased on the next frame
+    pub fn take_area(&mut self) -> Option<Rect> {
+        self.shown.take()
+    }
+}
@@ -0,0 +1,308 @@
+use crate::eadk::{display, Color, Point};
+use crate::packed::PackedBoard;
+use crate::palette::ColorPalette;
+use crate::viewport::Viewport;
+use crate::{draw_board, Board, BOARD_SIZE, COLUMN_SIZE, LINE_SIZE};
+use core::fmt::Write;
+use heapless::{Deque, String};
+
+/// Entries of the log, flipped cells and ends of generations
+const LOG_ENTRIES: usize = 8192;
+/// Keyframes kept, each starting an interval of the log
+const KEYFRAMES: usize = 8;
+/// Entries after which a new interval starts
+///
+/// The last interval is always shorter than that before a generation is logged, so even
+/// a generation flipping every cell fits once the older intervals are dropped.
+const INTERVAL_ENTRIES: usize = LOG_ENTRIES / KEYFRAMES;
+/// Entry ending a generation, past the index of any cell
+///
+/// The entries above it end as many more generations in a row that didn't flip any cell, so
+/// a board that barely changes can be replayed much further back than one that's chaotic.
+const END_OF_GENERATION: u16 = BOARD_SIZE as u16;
+/// Time each generation of a replay is shown, in milliseconds, from the slowest speed
+pub const PLAYBACK_DELAYS_MS: [u64; 4] = [200, 50, 16, 0];
+
+/// A board to replay from, and where its generations start in the log
+struct Keyframe {
+    board: PackedBoard,
+    generation: u32,
+    /// Entries logged before it, dropped ones included
+    start: usize,
+}
+
+/// The generations of a run that isn't drawn, to replay them once it stops
+///
+/// Each generation is logged as the cells it flipped, found by comparing the boards, so
+/// noise and confinement are logged too. Boards are kept as keyframes to replay from every
+/// so many entries rather than generations, so they're far apart while the board is quiet
+/// and close together while it's chaotic. When the log is full, the oldest keyframe and the
+/// generations after it are dropped, and only the end of the run can be replayed.
+pub struct TurboLog {
+    entries: Deque<u16, LOG_ENTRIES>,
+    keyframes: Deque<Keyframe, KEYFRAMES>,
+    /// Entries dropped from the front of the log
+    dropped: usize,
+    /// The board after the last generation logged
+    last: PackedBoard,
+    /// Generations the run started and is at
+    first_generation: u32,
+    generation: u32,
+}
+
+impl TurboLog {
+    pub const fn new() -> Self {
+        Self {
+            entries: Deque::new(),
+            keyframes: Deque::new(),
+            dropped: 0,
+            last: PackedBoard::EMPTY,
+            first_generation: 0,
+            generation: 0,
+        }
+    }
+
+    /// Log a new run from `board` at `generation`, forgetting the previous one
+    pub fn start(&mut self, board: &Board<bool>, generation: u32) {
+        self.entries.clear();
+        self.keyframes.clear();
+        self.dropped = 0;
+        self.last = PackedBoard::pack(board);
+        self.first_generation = generation;
+        self.generation = generation;
+        self.push_keyframe();
+    }
+
+    fn push_keyframe(&mut self) {
+        if self.keyframes.is_full() {
+            self.drop_oldest_interval();
+        }
+        let keyframe = Keyframe {
+            board: self.last,
+            generation: self.generation,
+            start: self.dropped + self.entries.len(),
+        };
+        // There's room, one was just dropped if there wasn't
+        self.keyframes.push_back(keyframe).ok();
+    }
+
+    fn drop_oldest_interval(&mut self) {
+        self.keyframes.pop_front();
+        let start = self
+            .keyframes
+            .front()
+            .map_or(self.dropped, |next| next.start);
+        while self.dropped < start {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+    }
+
+    /// Log the next generation of the run, `board` being after it
+    pub fn push(&mut self, board: &Board<bool>) {
+        let next = PackedBoard::pack(board);
+        let flipped = self.last.differences(&next).count();
+        if flipped == 0 && self.extend_quiet_generations() {
+            self.generation += 1;
+            return;
+        }
+
+        while self.entries.capacity() - self.entries.len() < flipped + 1 && self.keyframes.len() > 1
+        {
+            self.drop_oldest_interval();
+        }
+        for i in self.last.differences(&next) {
+            self.entries.push_back(i).ok();
+        }
+        self.entries.push_back(END_OF_GENERATION).ok();
+        self.last = next;
+        self.generation += 1;
+
+        let interval = self.keyframes.back().map_or(0, |keyframe| keyframe.start);
+        if self.dropped + self.entries.len() - interval >= INTERVAL_ENTRIES {
+            self.push_keyframe();
+        }
+    }
+
+    /// End one more generation with the last entry, the board staying the same
+    ///
+    /// Entries before the last keyframe are left alone, so it still starts at its generation.
+    fn extend_quiet_generations(&mut self) -> bool {
+        let interval = self.keyframes.back().map_or(0, |keyframe| keyframe.start);
+        if self.dropped + self.entries.len() <= interval {
+            return false;
+        }
+        match self.entries.back_mut() {
+            Some(entry) if *entry >= END_OF_GENERATION && *entry < u16::MAX => {
+                *entry += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// First and last generations that can be replayed
+    pub fn range(&self) -> (u32, u32) {
+        let first = self
+            .keyframes
+            .front()
+            .map_or(self.generation, |keyframe| keyframe.generation);
+        (first, self.generation)
+    }
+
+    /// Whether the start of the run was dropped to make room
+    pub fn truncated(&self) -> bool {
+        self.range().0 > self.first_generation
+    }
+
+    /// Rebuild the board at `generation`, clamped to the range that can be replayed
+    fn board_at(&self, generation: u32, board: &mut Board<bool>) {
+        let keyframe = match self
+            .keyframes
+            .iter()
+            .take_while(|keyframe| keyframe.generation <= generation)
+            .last()
+            .or_else(|| self.keyframes.front())
+        {
+            Some(keyframe) => keyframe,
+            None => return,
+        };
+        keyframe.board.unpack(board);
+        let mut at = keyframe.generation;
+        for &entry in self.entries.iter().skip(keyframe.start - self.dropped) {
+            if at >= generation {
+                break;
+            }
+            if entry >= END_OF_GENERATION {
+                at += (entry - END_OF_GENERATION) as u32 + 1;
+            } else {
+                let (x, y) = (entry % LINE_SIZE, entry / LINE_SIZE);
+                let cell = &mut board[x as usize][y as usize];
+                *cell = !*cell;
+            }
+        }
+    }
+
+    /// Draw the board at `generation` of the run, with where it is in the replay
+    pub fn draw(&self, generation: u32, palette: &ColorPalette, viewport: &Viewport) {
+        let mut board: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        self.board_at(generation, &mut board);
+        draw_board(&board, palette, viewport);
+
+        let (first, last) = self.range();
+        let mut text: String<40> = String::new();
+        write!(text, "Gen {} of {}-{}", generation, first, last).unwrap();
+        if self.truncated() {
+            text.push_str(", start dropped").unwrap();
+        }
+        text.push('\0').unwrap();
+        display::draw_string(&text, Point::new(2, 2), false, Color::WHITE, Color::BLACK);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autosolve::seed_soup;
+    use crate::period::checksum;
+    use crate::rng::Rng;
+    use std::boxed::Box;
+    use std::vec::Vec;
+
+    /// Log a run flipping `flips(generation)` random cells a generation, and check that the
+    /// boards replayed are the ones logged, returning the range that can be replayed
+    fn replay(generations: u32, mut flips: impl FnMut(u32, &mut Rng) -> u32) -> (u32, u32) {
+        let mut rng = Rng::new(7);
+        let mut board: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        seed_soup(&mut board, 99, 30);
+        let mut log = Box::new(TurboLog::new());
+        log.start(&board, 0);
+        let mut history = Vec::with_capacity(generations as usize + 1);
+        history.push(checksum(&board));
+        for generation in 1..=generations {
+            for _ in 0..flips(generation, &mut rng) {
+                let (x, y) = (rng.below(LINE_SIZE as u32), rng.below(COLUMN_SIZE as u32));
+                let cell = &mut board[x as usize][y as usize];
+                *cell = !*cell;
+            }
+            log.push(&board);
+            history.push(checksum(&board));
+        }
+
+        let (first, last) = log.range();
+        assert_eq!(last, generations);
+        assert_eq!(log.truncated(), first > 0);
+        let mut replayed: Board<bool> = [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize];
+        let every = ((last - first) / 2000).max(1);
+        let checked = (first..=last).step_by(every as usize).chain([last]);
+        for generation in checked {
+            log.board_at(generation, &mut replayed);
+            assert_eq!(
+                checksum(&replayed),
+                history[generation as usize],
+                "gen {}",
+                generation
+            );
+        }
+        // Out of the range, the closest end of it
+        if first > 0 {
+            log.board_at(0, &mut replayed);
+            assert_eq!(checksum(&replayed), history[first as usize]);
+        }
+        (first, last)
+    }
+
+    #[test]
+    fn quiet_runs_are_replayed_from_their_start() {
+        let range = replay(20_000, |generation, _| (generation % 1000 == 0) as u32);
+        assert_eq!(range, (0, 20_000));
+    }
+
+    #[test]
+    fn static_runs_are_replayed_from_their_start() {
+        assert_eq!(replay(20_000, |_, _| 0), (0, 20_000));
+    }
+
+    #[test]
+    fn chaotic_runs_keep_their_end() {
+        let (first, last) = replay(2_000, |_, _| 300);
+        assert!(first > 0);
+        // Each interval holds a bit more than 3 generations of 300 flips
+        assert!(
+            last - first >= (KEYFRAMES as u32 - 1) * 3,
+            "{}",
+            last - first
+        );
+    }
+
+    #[test]
+    fn bursts_are_replayed() {
+        // Most of the board flipping for a few generations, then nothing for a while
+        replay(
+            10_000,
+            |generation, _| {
+                if generation % 500 < 10 {
+                    4000
+                } else {
+                    0
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn alternating_runs_are_replayed() {
+        replay(10_000, |generation, _| (generation % 2) * 3);
+    }
+
+    #[test]
+    fn mixed_runs_are_replayed() {
+        replay(10_000, |generation, rng| {
+            if (generation / 2000) % 2 == 0 {
+                rng.below(50)
+            } else {
+                0
+            }
+        });
+    }
+}
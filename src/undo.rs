@@ -0,0 +1,36 @@
+use crate::{Board, COLUMN_SIZE, LINE_SIZE};
+
+/// Snapshot of the board taken before a destructive operation
+pub struct Undo {
+    snapshot: Board<bool>,
+    available: bool,
+}
+
+impl Undo {
+    pub const fn new() -> Self {
+        Self {
+            snapshot: [[false; COLUMN_SIZE as usize]; LINE_SIZE as usize],
+            available: false,
+        }
+    }
+
+    pub fn save(&mut self, board: &Board<bool>) {
+        self.snapshot = *board;
+        self.available = true;
+    }
+
+    /// The board as it was saved last
+    pub fn snapshot(&self) -> &Board<bool> {
+        &self.snapshot
+    }
+
+    /// Swap the board with the snapshot, so undoing again redoes
+    ///
+    /// Returns whether there was a snapshot.
+    pub fn undo(&mut self, board: &mut Board<bool>) -> bool {
+        if self.available {
+            core::mem::swap(board, &mut self.snapshot);
+        }
+        self.available
+    }
+}
@@ -0,0 +1,277 @@
+use crate::eadk::{Rect, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::{CELL_SIZE, COLUMN_SIZE, LINE_SIZE};
+
+/// Cell sizes (in pixel) the board can be zoomed to, the smallest showing the whole board
+pub const ZOOM_LEVELS: [u16; 3] = [CELL_SIZE, 2 * CELL_SIZE, 4 * CELL_SIZE];
+
+/// A cell of the board, wherever the viewport is
+///
+/// What positions are stored as, so they stay valid when panning or zooming.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LogicalPos {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl LogicalPos {
+    pub const fn new(x: u16, y: u16) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A cell counted from the top left corner of the screen, only meaningful for a viewport
+#[derive(Clone, Copy, PartialEq)]
+pub struct ViewPos {
+    pub x: u16,
+    pub y: u16,
+}
+
+/// The part of the board shown on screen
+#[derive(Clone, Copy)]
+pub struct Viewport {
+    /// Cell in the top left corner of the screen
+    pub origin: LogicalPos,
+    /// Size in pixel of a cell on screen
+    pub cell_size: u16,
+    /// Whether lines are drawn between the cells
+    pub grid: bool,
+}
+
+impl Viewport {
+    /// The whole board, at the smallest cell size
+    pub const FULL: Self = Self {
+        origin: LogicalPos::new(0, 0),
+        cell_size: CELL_SIZE,
+        grid: false,
+    };
+
+    /// Number of cells shown horizontally and vertically
+    pub fn size(&self) -> (u16, u16) {
+        (
+            SCREEN_WIDTH / self.cell_size,
+            SCREEN_HEIGHT / self.cell_size,
+        )
+    }
+
+    /// Where a cell of the board is on screen, `None` if it's out of view
+    pub fn view_pos(&self, cell: LogicalPos) -> Option<ViewPos> {
+        let (columns, rows) = self.size();
+        let (x, y) = (
+            cell.x.checked_sub(self.origin.x)?,
+            cell.y.checked_sub(self.origin.y)?,
+        );
+        if x >= columns || y >= rows {
+            return None;
+        }
+        Some(ViewPos { x, y })
+    }
+
+    /// The cell of the board shown at a place of the screen
+    pub fn logical_pos(&self, cell: ViewPos) -> LogicalPos {
+        LogicalPos::new(self.origin.x + cell.x, self.origin.y + cell.y)
+    }
+
+    /// Rect of a cell on screen, `None` if it's out of view
+    pub fn cell_rect(&self, cell: LogicalPos) -> Option<Rect> {
+        let ViewPos { x, y } = self.view_pos(cell)?;
+        Some(Rect::new(
+            x * self.cell_size,
+            y * self.cell_size,
+            self.cell_size,
+            self.cell_size,
+        ))
+    }
+
+    /// The part of the rect of a cell left to it by the grid, all of it without a grid
+    pub fn inside_grid(&self, rect: Rect) -> Rect {
+        if self.grid {
+            Rect::new(rect.x + 1, rect.y + 1, rect.width - 1, rect.height - 1)
+        } else {
+            rect
+        }
+    }
+
+    /// Rect on screen of the cells of column `x` from `top` to `bottom` included
+    ///
+    /// The cells out of view are left out, `None` if none is in view.
+    pub fn column_rect(&self, x: u16, (top, bottom): (u16, u16)) -> Option<Rect> {
+        let (_, rows) = self.size();
+        let top = top.max(self.origin.y);
+        let bottom = bottom.min(self.origin.y + rows - 1);
+        if top > bottom {
+            return None;
+        }
+        Some(Rect {
+            height: (bottom - top + 1) * self.cell_size,
+            ..self.cell_rect(LogicalPos::new(x, top))?
+        })
+    }
+
+    /// Change the cell size, keeping `cursor` at the same place on screen
+    ///
+    /// The new origin is moved back inside the board when keeping the cursor in place
+    /// would show past its edges.
+    pub fn zoom_toward(&mut self, cursor: LogicalPos, cell_size: u16) {
+        // Top left pixel of the cursor on screen, before zooming
+        let screen = (
+            cursor.x.saturating_sub(self.origin.x) * self.cell_size,
+            cursor.y.saturating_sub(self.origin.y) * self.cell_size,
+        );
+        self.cell_size = cell_size;
+        let (columns, rows) = self.size();
+        self.origin = LogicalPos::new(
+            cursor
+                .x
+                .saturating_sub(screen.0 / cell_size)
+                .min(LINE_SIZE - columns),
+            cursor
+                .y
+                .saturating_sub(screen.1 / cell_size)
+                .min(COLUMN_SIZE - rows),
+        );
+    }
+
+    /// Pan the least possible for `cell` to be in view, returns whether it panned
+    pub fn follow(&mut self, cell: LogicalPos) -> bool {
+        let (columns, rows) = self.size();
+        let previous = self.origin;
+        self.origin = LogicalPos::new(
+            self.origin
+                .x
+                .min(cell.x)
+                .max((cell.x + 1).saturating_sub(columns)),
+            self.origin
+                .y
+                .min(cell.y)
+                .max((cell.y + 1).saturating_sub(rows)),
+        );
+        self.origin != previous
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zoomed(viewport: Viewport, cursor: (u16, u16), cell_size: u16) -> Viewport {
+        let mut viewport = viewport;
+        viewport.zoom_toward(LogicalPos::new(cursor.0, cursor.1), cell_size);
+        viewport
+    }
+
+    fn origin(viewport: &Viewport) -> (u16, u16) {
+        (viewport.origin.x, viewport.origin.y)
+    }
+
+    fn corner(viewport: &Viewport, cursor: (u16, u16)) -> (u16, u16) {
+        let rect = viewport
+            .cell_rect(LogicalPos::new(cursor.0, cursor.1))
+            .unwrap();
+        (rect.x, rect.y)
+    }
+
+    #[test]
+    fn zooming_keeps_the_cursor_in_place() {
+        for &cursor in &[(40, 28), (24, 20), (60, 44)] {
+            let zoomed_in = zoomed(Viewport::FULL, cursor, ZOOM_LEVELS[1]);
+            assert_eq!(corner(&zoomed_in, cursor), corner(&Viewport::FULL, cursor));
+            let further = zoomed(zoomed_in, cursor, ZOOM_LEVELS[2]);
+            assert_eq!(corner(&further, cursor), corner(&Viewport::FULL, cursor));
+        }
+        assert_eq!(origin(&zoomed(Viewport::FULL, (40, 30), 8)), (20, 15));
+        assert_eq!(origin(&zoomed(Viewport::FULL, (40, 30), 16)), (30, 23));
+    }
+
+    #[test]
+    fn zooming_in_rounds_toward_the_cursor() {
+        // The cursor is 4 pixels from the corner, half a zoomed cell
+        assert_eq!(origin(&zoomed(Viewport::FULL, (1, 1), 8)), (1, 1));
+        assert_eq!(origin(&zoomed(Viewport::FULL, (0, 0), 16)), (0, 0));
+    }
+
+    #[test]
+    fn zooming_stays_inside_the_board() {
+        let bottom_right = (LINE_SIZE - 1, COLUMN_SIZE - 1);
+        assert_eq!(origin(&zoomed(Viewport::FULL, bottom_right, 8)), (40, 30));
+        assert_eq!(origin(&zoomed(Viewport::FULL, bottom_right, 16)), (60, 45));
+
+        // Zooming out from the corner would show past the edges of the board
+        let corner_view = zoomed(Viewport::FULL, bottom_right, 16);
+        assert_eq!(origin(&zoomed(corner_view, bottom_right, 8)), (40, 30));
+        assert_eq!(
+            origin(&zoomed(corner_view, bottom_right, CELL_SIZE)),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn zooming_back_out_shows_the_whole_board() {
+        for &cursor in &[(0, 0), (40, 30), (79, 59), (5, 50)] {
+            let view = zoomed(zoomed(Viewport::FULL, cursor, 16), cursor, CELL_SIZE);
+            assert_eq!(origin(&view), (0, 0));
+        }
+    }
+
+    #[test]
+    fn following_pans_the_least_possible() {
+        let mut view = zoomed(Viewport::FULL, (40, 30), 8);
+        assert!(!view.follow(LogicalPos::new(20, 15)));
+        assert!(!view.follow(LogicalPos::new(59, 44)));
+        assert!(view.follow(LogicalPos::new(60, 45)));
+        assert_eq!(origin(&view), (21, 16));
+        assert!(view.follow(LogicalPos::new(3, 2)));
+        assert_eq!(origin(&view), (3, 2));
+    }
+
+    #[test]
+    fn board_and_screen_positions_convert_both_ways() {
+        for view in [
+            Viewport::FULL,
+            zoomed(Viewport::FULL, (40, 28), 8),
+            zoomed(Viewport::FULL, (LINE_SIZE - 1, COLUMN_SIZE - 1), 16),
+        ] {
+            let (columns, rows) = view.size();
+            for x in 0..LINE_SIZE {
+                for y in 0..COLUMN_SIZE {
+                    let cell = LogicalPos::new(x, y);
+                    let in_view = (view.origin.x..view.origin.x + columns).contains(&x)
+                        && (view.origin.y..view.origin.y + rows).contains(&y);
+                    match view.view_pos(cell) {
+                        Some(on_screen) => {
+                            assert!(in_view);
+                            assert!(view.logical_pos(on_screen) == cell);
+                        }
+                        None => assert!(!in_view),
+                    }
+                }
+            }
+            for x in 0..columns {
+                for y in 0..rows {
+                    let on_screen = ViewPos { x, y };
+                    assert!(view.view_pos(view.logical_pos(on_screen)) == Some(on_screen));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cells_are_drawn_where_they_are_in_view() {
+        let view = zoomed(Viewport::FULL, (40, 28), 8);
+        let rect = view.cell_rect(LogicalPos::new(21, 15)).unwrap();
+        assert_eq!((rect.x, rect.y, rect.width, rect.height), (8, 8, 8, 8));
+        assert!(view.cell_rect(LogicalPos::new(19, 15)).is_none());
+        assert!(view.cell_rect(LogicalPos::new(60, 15)).is_none());
+
+        let grid = Viewport { grid: true, ..view };
+        let inside = grid.inside_grid(rect);
+        assert_eq!(
+            (inside.x, inside.y, inside.width, inside.height),
+            (9, 9, 7, 7)
+        );
+        let inside = view.inside_grid(rect);
+        assert_eq!(
+            (inside.x, inside.y, inside.width, inside.height),
+            (8, 8, 8, 8)
+        );
+    }
+}
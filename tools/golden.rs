@@ -0,0 +1,203 @@
+//! Compute the expected checksum of a new golden evolution, for `src/golden.rs`
+//!
+//! A host program, on purpose written apart from the app: it steps the board cell by cell,
+//! the plainest way, so its results check the app's steppers instead of repeating them.
+//!
+//! ```text
+//! cargo make golden -- <start> <rule> <dead|wrap> <generations>
+//! ```
+//!
+//! The start is a pattern in RLE followed by where its top left corner goes, like
+//! `bo$2bo$3o!@10,10`, or a soup as `soup:<seed>:<density>`. The rule is in the B3/S23
+//! notation, ending with D for diagonal rules.
+
+use std::env;
+use std::process;
+
+const WIDTH: usize = 80;
+const HEIGHT: usize = 60;
+
+type Board = Vec<Vec<bool>>;
+
+const MOORE: &[(isize, isize)] = &[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+const DIAGONAL: &[(isize, isize)] = &[(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+struct Rule {
+    birth: Vec<usize>,
+    survive: Vec<usize>,
+    neighbors: &'static [(isize, isize)],
+}
+
+fn parse_rule(text: &str) -> Option<Rule> {
+    let (text, neighbors) = match text.strip_suffix('D') {
+        Some(text) => (text, DIAGONAL),
+        None => (text, MOORE),
+    };
+    let (birth, survive) = text.split_once('/')?;
+    let counts = |text: &str| -> Option<Vec<usize>> {
+        text.chars()
+            .map(|c| c.to_digit(10).map(|n| n as usize))
+            .collect()
+    };
+    Some(Rule {
+        birth: counts(birth.strip_prefix('B')?)?,
+        survive: counts(survive.strip_prefix('S')?)?,
+        neighbors,
+    })
+}
+
+fn place_rle(board: &mut Board, rle: &str, (left, top): (usize, usize)) -> Option<()> {
+    let (mut x, mut y) = (0, 0);
+    let mut count = String::new();
+    for c in rle.chars() {
+        if c.is_ascii_digit() {
+            count.push(c);
+            continue;
+        }
+        let n = if count.is_empty() {
+            1
+        } else {
+            count.parse().ok()?
+        };
+        count.clear();
+        match c {
+            'b' => x += n,
+            'o' => {
+                for _ in 0..n {
+                    *board.get_mut(left + x)?.get_mut(top + y)? = true;
+                    x += 1;
+                }
+            }
+            '$' => {
+                y += n;
+                x = 0;
+            }
+            '!' => break,
+            _ => return None,
+        }
+    }
+    Some(())
+}
+
+/// The soup the app draws from a seed: xorshift32, one number per cell, column by column
+fn place_soup(board: &mut Board, seed: u32, density: u32) {
+    let mut state = if seed == 0 { 0x9E37_79B9 } else { seed };
+    for column in board.iter_mut() {
+        for cell in column.iter_mut() {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            *cell = state % 100 < density;
+        }
+    }
+}
+
+fn step(board: &Board, rule: &Rule, wrap: bool) -> Board {
+    let mut next = vec![vec![false; HEIGHT]; WIDTH];
+    for x in 0..WIDTH {
+        for y in 0..HEIGHT {
+            let mut alive = 0;
+            for &(dx, dy) in rule.neighbors {
+                let (nx, ny) = (x as isize + dx, y as isize + dy);
+                let (nx, ny) = if wrap {
+                    (
+                        nx.rem_euclid(WIDTH as isize),
+                        ny.rem_euclid(HEIGHT as isize),
+                    )
+                } else if nx < 0 || ny < 0 || nx >= WIDTH as isize || ny >= HEIGHT as isize {
+                    continue;
+                } else {
+                    (nx, ny)
+                };
+                alive += board[nx as usize][ny as usize] as usize;
+            }
+            let counts = if board[x][y] {
+                &rule.survive
+            } else {
+                &rule.birth
+            };
+            next[x][y] = counts.contains(&alive);
+        }
+    }
+    next
+}
+
+/// FNV-1a of the cells, row by row, 8 to a byte from the lowest bit
+fn checksum(board: &Board) -> u32 {
+    let mut hash: u32 = 0x811C_9DC5;
+    for byte in 0..WIDTH * HEIGHT / 8 {
+        let bits = (0..8)
+            .map(|bit| byte * 8 + bit)
+            .filter(|&i| board[i % WIDTH][i / WIDTH])
+            .fold(0u8, |bits, i| bits | 1 << (i % 8));
+        hash = (hash ^ bits as u32).wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+fn parse_number(text: &str) -> Option<u32> {
+    match text.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+fn start_board(start: &str) -> Option<Board> {
+    let mut board = vec![vec![false; HEIGHT]; WIDTH];
+    if let Some(soup) = start.strip_prefix("soup:") {
+        let (seed, density) = soup.split_once(':')?;
+        place_soup(&mut board, parse_number(seed)?, parse_number(density)?);
+    } else {
+        let (rle, at) = start.split_once('@')?;
+        let (x, y) = at.split_once(',')?;
+        place_rle(&mut board, rle, (x.parse().ok()?, y.parse().ok()?))?;
+    }
+    Some(board)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let parsed = match args.as_slice() {
+        [start, rule, boundary, generations] => (|| {
+            let wrap = match boundary.as_str() {
+                "dead" => false,
+                "wrap" => true,
+                _ => return None,
+            };
+            Some((
+                start_board(start)?,
+                parse_rule(rule)?,
+                wrap,
+                parse_number(generations)?,
+            ))
+        })(),
+        _ => None,
+    };
+    let (mut board, rule, wrap, generations) = match parsed {
+        Some(parsed) => parsed,
+        None => {
+            eprintln!(
+                "usage: golden <rle@x,y | soup:seed:density> <rule> <dead|wrap> <generations>"
+            );
+            process::exit(2);
+        }
+    };
+    for _ in 0..generations {
+        board = step(&board, &rule, wrap);
+    }
+    let population: usize = board.iter().flatten().filter(|&&alive| alive).count();
+    println!(
+        "expected: 0x{:08X}, // {} cells",
+        checksum(&board),
+        population
+    );
+}